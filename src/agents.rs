@@ -0,0 +1,472 @@
+// Agents module: subagent drafts mined from repeated Task delegations
+// Offline (no AI): groups Task tool calls by the kind of work delegated and turns
+// recurring kinds into `.claude/agents/<name>.md` drafts with their own manifest under
+// `<drafts>/agents/`. Deployed with the regular skill deployer.
+
+use crate::error::SkillMinerError;
+use crate::manifest;
+use crate::types::{escape_yaml_double_quoted, Conversation, DraftEntry, DraftStatus, Manifest};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+/// Subdirectory of the drafts dir holding subagent drafts and their manifest.
+pub const AGENTS_SUBDIR: &str = "agents";
+
+/// Domain recorded in manifest entries for subagent drafts.
+pub const AGENT_DOMAIN: &str = "agent";
+
+/// Built-in subagent type that says nothing about the work delegated.
+const GENERIC_SUBAGENT: &str = "general-purpose";
+
+/// Most task descriptions kept as examples per agent.
+const MAX_EXAMPLES: usize = 5;
+
+/// A known kind of delegated work.
+struct AgentKind {
+    name: &'static str,
+    description: &'static str,
+    /// System prompt opening
+    role: &'static str,
+    /// Matched against task descriptions and prompts of general-purpose delegations
+    keywords: &'static [&'static str],
+    /// Used when no tool use was observed inside the delegations
+    tools: &'static [&'static str],
+}
+
+const KINDS: &[AgentKind] = &[
+    AgentKind {
+        name: "code-reviewer",
+        description: "Reviews code changes for bugs, risky patterns and missing tests",
+        role: "You are a code reviewer. Read the changes you are pointed at and report bugs, risky patterns and missing tests, most severe first.",
+        keywords: &["review", "audit"],
+        tools: &["Glob", "Grep", "Read"],
+    },
+    AgentKind {
+        name: "test-writer",
+        description: "Writes and fixes tests for existing code",
+        role: "You write tests. Cover the behavior you are asked about, follow the existing test layout, and run the tests before reporting back.",
+        keywords: &["test", "spec", "coverage"],
+        tools: &["Bash", "Edit", "Glob", "Grep", "Read", "Write"],
+    },
+    AgentKind {
+        name: "log-triage",
+        description: "Triages logs and error output to find the root cause",
+        role: "You triage logs and error output. Find the first real failure, trace it to its root cause, and report the evidence.",
+        keywords: &["log", "triage", "stack trace", "crash", "error"],
+        tools: &["Bash", "Glob", "Grep", "Read"],
+    },
+    AgentKind {
+        name: "researcher",
+        description: "Searches the codebase to answer questions about how things work",
+        role: "You research the codebase. Find the code that answers the question, and report file paths with a short explanation.",
+        keywords: &["search", "find", "investigate", "explore", "research"],
+        tools: &["Glob", "Grep", "Read"],
+    },
+];
+
+/// A single Task call and the tools used while the subagent ran.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delegation {
+    pub subagent_type: String,
+    pub description: String,
+    pub prompt: String,
+    pub tools: BTreeSet<String>,
+}
+
+/// A kind of delegated work that recurs across conversations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentPattern {
+    /// Agent name (file name and `name:` frontmatter)
+    pub name: String,
+    pub description: String,
+    /// System prompt opening
+    pub role: String,
+    /// Tool allowlist: tools observed inside the delegations (or the kind's defaults)
+    pub tools: Vec<String>,
+    /// Number of Task calls of this kind
+    pub calls: usize,
+    /// Conversation IDs the delegations were seen in
+    pub sources: Vec<String>,
+    /// Sample task descriptions
+    pub examples: Vec<String>,
+    /// Sample prompt
+    pub sample_prompt: String,
+}
+
+/// A subagent draft.
+#[derive(Debug, Clone)]
+pub struct AgentDraft {
+    pub slug: String,
+    pub pattern: AgentPattern,
+}
+
+impl AgentDraft {
+    /// Format as a `.claude/agents/<name>.md` file.
+    pub fn format_md(&self) -> String {
+        let p = &self.pattern;
+        let mut md = format!(
+            "---\nname: {}\ndescription: \"{}\"\n",
+            self.slug,
+            escape_yaml_double_quoted(&p.description)
+        );
+        if !p.tools.is_empty() {
+            md.push_str(&format!("tools: {}\n", p.tools.join(", ")));
+        }
+        md.push_str("---\n\n");
+        md.push_str(&p.role);
+        md.push_str("\n\n## Typical tasks\n\n");
+        for ex in &p.examples {
+            md.push_str(&format!("- {}\n", ex));
+        }
+        if !p.sample_prompt.is_empty() {
+            md.push_str("\n## Example request\n\n");
+            for line in p.sample_prompt.lines() {
+                md.push_str(&format!("> {}\n", line).replace("> \n", ">\n"));
+            }
+        }
+        md.push_str("\n## Reporting\n\n");
+        md.push_str("Stay within the delegated task. Finish with a concise summary of what you found or changed.\n");
+        md.push_str(&format!(
+            "\nDelegated {} times across {} conversations.\n",
+            p.calls,
+            p.sources.len()
+        ));
+        md
+    }
+}
+
+/// Task calls of a conversation with the tools used in the sidechain messages that follow.
+/// Parallel Task calls from one message share the tools of the sidechain that follows it.
+pub fn delegations(conv: &Conversation) -> Vec<Delegation> {
+    let mut found: Vec<Delegation> = Vec::new();
+    let mut active = 0..0;
+    for msg in &conv.messages {
+        if msg.sidechain {
+            for tu in &msg.tool_uses {
+                for d in &mut found[active.clone()] {
+                    d.tools.insert(tu.name.clone());
+                }
+            }
+            continue;
+        }
+        let start = found.len();
+        for task in msg.tool_uses.iter().filter_map(|tu| tu.task.as_ref()) {
+            found.push(Delegation {
+                subagent_type: task.subagent_type.clone(),
+                description: task.description.clone(),
+                prompt: task.prompt.clone(),
+                tools: BTreeSet::new(),
+            });
+        }
+        if found.len() > start {
+            active = start..found.len();
+        }
+    }
+    for d in &mut found {
+        d.tools.remove("Task");
+    }
+    found
+}
+
+/// Lowercase a subagent type into a file-safe agent name.
+fn agent_name(subagent_type: &str) -> String {
+    subagent_type
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Kind of work a delegation belongs to: its custom subagent type, or a known kind
+/// matched by keyword in its description and prompt. None if neither applies.
+pub fn classify(d: &Delegation) -> Option<String> {
+    if !d.subagent_type.is_empty() && d.subagent_type != GENERIC_SUBAGENT {
+        let name = agent_name(&d.subagent_type);
+        return (!name.is_empty()).then_some(name);
+    }
+    let text = format!("{} {}", d.description, d.prompt).to_lowercase();
+    KINDS
+        .iter()
+        .find(|k| k.keywords.iter().any(|w| text.contains(w)))
+        .map(|k| k.name.to_string())
+}
+
+/// Group delegations by kind and keep kinds seen in at least `min_support` conversations.
+pub fn mine_agents(conversations: &[Conversation], min_support: usize) -> Vec<AgentPattern> {
+    // kind -> (conversation ids, delegations)
+    let mut groups: BTreeMap<String, (BTreeSet<String>, Vec<Delegation>)> = BTreeMap::new();
+    for conv in conversations {
+        for d in delegations(conv) {
+            if let Some(kind) = classify(&d) {
+                let group = groups.entry(kind).or_default();
+                group.0.insert(conv.id.clone());
+                group.1.push(d);
+            }
+        }
+    }
+
+    let mut result: Vec<AgentPattern> = groups
+        .into_iter()
+        .filter(|(_, (ids, _))| ids.len() >= min_support)
+        .map(|(name, (ids, calls))| {
+            let known = KINDS.iter().find(|k| k.name == name);
+            let mut tools: BTreeSet<String> =
+                calls.iter().flat_map(|d| d.tools.iter().cloned()).collect();
+            if tools.is_empty() {
+                if let Some(kind) = known {
+                    tools = kind.tools.iter().map(|t| t.to_string()).collect();
+                }
+            }
+            let mut examples: Vec<String> = Vec::new();
+            for d in &calls {
+                let ex = d.description.trim();
+                if !ex.is_empty() && !examples.iter().any(|e| e == ex) && examples.len() < MAX_EXAMPLES {
+                    examples.push(ex.to_string());
+                }
+            }
+            let (description, role) = match known {
+                Some(kind) => (kind.description.to_string(), kind.role.to_string()),
+                None => (
+                    format!("Handles delegated {} tasks", name.replace('-', " ")),
+                    format!(
+                        "You are the {} subagent. Complete the delegated task below and report back.",
+                        name
+                    ),
+                ),
+            };
+            let description = match examples.first() {
+                Some(ex) => format!("{}. Use for tasks like \"{}\"", description, ex),
+                None => description,
+            };
+            let sample_prompt = calls
+                .iter()
+                .map(|d| d.prompt.trim())
+                .find(|p| !p.is_empty())
+                .unwrap_or_default()
+                .to_string();
+            AgentPattern {
+                name,
+                description,
+                role,
+                tools: tools.into_iter().collect(),
+                calls: calls.len(),
+                sources: ids.into_iter().collect(),
+                examples,
+                sample_prompt,
+            }
+        })
+        .collect();
+
+    result.sort_by(|a, b| {
+        b.sources
+            .len()
+            .cmp(&a.sources.len())
+            .then(b.calls.cmp(&a.calls))
+            .then(a.name.cmp(&b.name))
+    });
+    result
+}
+
+/// Turn mined agent patterns into drafts (names are unique per kind already).
+pub fn generate_drafts(patterns: &[AgentPattern]) -> Vec<AgentDraft> {
+    patterns
+        .iter()
+        .map(|p| AgentDraft {
+            slug: p.name.clone(),
+            pattern: p.clone(),
+        })
+        .collect()
+}
+
+/// Load the subagent manifest, or an empty one if none exists yet.
+pub fn load_manifest(drafts_dir: &Path) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(AGENTS_SUBDIR);
+    if dir.join("manifest.toml").exists() {
+        manifest::read_manifest(&dir)
+    } else {
+        Ok(Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: Vec::new(),
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        })
+    }
+}
+
+/// Write drafts as `<drafts_dir>/agents/<name>.md` and merge them into its manifest.
+pub fn write_drafts(drafts_dir: &Path, drafts: &[AgentDraft]) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(AGENTS_SUBDIR);
+    std::fs::create_dir_all(&dir)?;
+    let mut mf = load_manifest(drafts_dir)?;
+
+    for draft in drafts {
+        let content = draft.format_md();
+        std::fs::write(dir.join(format!("{}.md", draft.slug)), &content)?;
+
+        manifest::merge_entry(
+            &mut mf,
+            DraftEntry {
+                slug: draft.slug.clone(),
+                domain: AGENT_DOMAIN.to_string(),
+                status: DraftStatus::Draft,
+                pattern_count: draft.pattern.calls,
+                conversation_count: draft.pattern.sources.len(),
+                generated_at: chrono::Utc::now(),
+                content_hash: manifest::compute_hash(&content),
+                ..Default::default()
+            },
+        );
+    }
+
+    mf.generated_at = chrono::Utc::now();
+    manifest::write_manifest(&dir, &mf)?;
+    Ok(mf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deployer;
+    use crate::types::{Message, Role, TaskCall, ToolUse};
+    use std::path::PathBuf;
+
+    fn tool(name: &str) -> ToolUse {
+        ToolUse {
+            name: name.to_string(),
+            input_summary: String::new(),
+            file_path: None,
+            command: None,
+            task: None,
+            failed: false,
+        }
+    }
+
+    fn task(subagent_type: &str, description: &str) -> ToolUse {
+        ToolUse {
+            task: Some(TaskCall {
+                subagent_type: subagent_type.to_string(),
+                description: description.to_string(),
+                prompt: format!("Please {}", description.to_lowercase()),
+            }),
+            ..tool("Task")
+        }
+    }
+
+    fn msg(sidechain: bool, tool_uses: Vec<ToolUse>) -> Message {
+        Message {
+            role: Role::Assistant,
+            content: String::new(),
+            timestamp: None,
+            tool_uses,
+            sidechain,
+        }
+    }
+
+    fn conv(id: &str, messages: Vec<Message>) -> Conversation {
+        Conversation {
+            id: id.to_string(),
+            source_path: PathBuf::from(format!("{}.jsonl", id)),
+            messages,
+            start_time: None,
+            end_time: None,
+            cwd: None,
+            git_branch: None,
+        }
+    }
+
+    fn review_conv(id: &str) -> Conversation {
+        conv(
+            id,
+            vec![
+                msg(false, vec![tool("Edit"), task(GENERIC_SUBAGENT, "Review the parser changes")]),
+                msg(true, vec![tool("Read"), tool("Grep")]),
+                msg(true, vec![tool("Read")]),
+                msg(false, vec![tool("Bash")]),
+            ],
+        )
+    }
+
+    #[test]
+    fn delegations_collect_sidechain_tools() {
+        let found = delegations(&review_conv("a"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].description, "Review the parser changes");
+        let tools: Vec<&str> = found[0].tools.iter().map(|s| s.as_str()).collect();
+        // Bash ran in the main thread, not inside the subagent
+        assert_eq!(tools, vec!["Grep", "Read"]);
+    }
+
+    #[test]
+    fn classify_by_type_or_keyword() {
+        let d = |t: &str, desc: &str| Delegation {
+            subagent_type: t.to_string(),
+            description: desc.to_string(),
+            prompt: String::new(),
+            tools: BTreeSet::new(),
+        };
+        assert_eq!(classify(&d("Security Auditor", "x")).as_deref(), Some("security-auditor"));
+        assert_eq!(classify(&d(GENERIC_SUBAGENT, "Write unit tests for util")).as_deref(), Some("test-writer"));
+        assert_eq!(classify(&d(GENERIC_SUBAGENT, "Triage the CI logs")).as_deref(), Some("log-triage"));
+        assert_eq!(classify(&d(GENERIC_SUBAGENT, "Rename variables")), None);
+    }
+
+    #[test]
+    fn mine_requires_support_and_uses_observed_tools() {
+        let mut convs = vec![review_conv("1"), review_conv("2"), review_conv("3")];
+        convs.push(conv("4", vec![msg(false, vec![task(GENERIC_SUBAGENT, "Write tests for the lexer")])]));
+
+        let found = mine_agents(&convs, 3);
+        assert_eq!(found.len(), 1);
+        let p = &found[0];
+        assert_eq!(p.name, "code-reviewer");
+        assert_eq!(p.calls, 3);
+        assert_eq!(p.tools, vec!["Grep", "Read"]);
+        assert_eq!(p.examples, vec!["Review the parser changes"]);
+
+        // Without observed tools the kind's defaults are used
+        let found = mine_agents(&convs, 1);
+        let tests = found.iter().find(|p| p.name == "test-writer").unwrap();
+        assert!(tests.tools.contains(&"Write".to_string()));
+    }
+
+    #[test]
+    fn format_md_has_frontmatter_and_prompt() {
+        let found = mine_agents(&[review_conv("1")], 1);
+        let md = generate_drafts(&found)[0].format_md();
+        assert!(md.starts_with("---\nname: code-reviewer\ndescription: \"Reviews code changes"));
+        assert!(md.contains("Use for tasks like \\\"Review the parser changes\\\""));
+        assert!(md.contains("\ntools: Grep, Read\n---\n\nYou are a code reviewer."));
+        assert!(md.contains("> Please review the parser changes\n"));
+    }
+
+    #[test]
+    fn write_and_deploy_with_deployer() {
+        let drafts_dir = tempfile::tempdir().unwrap();
+        let agents_dir = tempfile::tempdir().unwrap();
+        let convs = vec![review_conv("1"), review_conv("2")];
+        let drafts = generate_drafts(&mine_agents(&convs, 2));
+
+        let mut mf = write_drafts(drafts_dir.path(), &drafts).unwrap();
+        assert_eq!(mf.entries.len(), 1);
+        assert_eq!(mf.entries[0].domain, AGENT_DOMAIN);
+        assert!(drafts_dir.path().join("agents/code-reviewer.md").exists());
+
+        let dir = drafts_dir.path().join(AGENTS_SUBDIR);
+        let results =
+            deployer::deploy_by_names(&dir, agents_dir.path(), &mut mf, &["code-reviewer".to_string()])
+                .unwrap();
+        assert_eq!(results[0].target_path, agents_dir.path().join("code-reviewer.md"));
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+        manifest::write_manifest(&dir, &mf).unwrap();
+
+        // Re-mining keeps the deployed status
+        let mf = write_drafts(drafts_dir.path(), &drafts).unwrap();
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+    }
+}
@@ -0,0 +1,258 @@
+// Attribution module: which sections of a skill the invocations actually followed
+// Each pattern (a `## N.` section of the body) names commands and files in its steps and code
+// examples. After an invocation, the tool calls up to the next user message are matched
+// against those cues: a Bash command with the same prefix (`cargo test`), or a file path or
+// command argument naming the same file (`Cargo.toml`, `src/main.rs`).
+
+use crate::domains;
+use crate::generator;
+use crate::types::{DomainCluster, KnowledgePattern, SectionUsage, SkillInvocation, ToolUse};
+
+/// Fence languages whose lines are commands
+const SHELL_LANGUAGES: &[&str] =
+    &["", "bash", "sh", "shell", "console", "zsh", "powershell", "ps1", "cmd"];
+
+/// Commands run by almost every skill; following them says nothing about a section
+const GENERIC_COMMANDS: &[&str] = &[
+    "cat", "echo", "find", "grep", "head", "ls", "pwd", "rg", "tail", "git diff", "git log",
+    "git status",
+];
+
+/// Commands and files a section tells the assistant to use.
+#[derive(Debug, Default, PartialEq)]
+struct Cues {
+    commands: Vec<String>,
+    files: Vec<String>,
+}
+
+/// Whether a word names a file: a name and a short extension with a letter (`Cargo.toml`,
+/// `src/main.rs`), not a version, a URL, an abbreviation or a method call on a variable.
+fn is_file(word: &str) -> bool {
+    if word.contains("://") {
+        return false;
+    }
+    let Some((stem, ext)) = word.rsplit_once('.') else {
+        return false;
+    };
+    let name = stem.rsplit('/').next().unwrap_or(stem);
+    name.len() > 1
+        && (1..=5).contains(&ext.len())
+        && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        && ext.chars().any(|c| c.is_ascii_alphabetic())
+        && name.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// File names mentioned in a line of text or code (paths with `/` separators).
+fn files_in(line: &str) -> Vec<String> {
+    line.split(|c: char| c.is_whitespace() || "`'\"(),;:<>=[]{}".contains(c))
+        .map(|w| w.trim_end_matches('.').replace('\\', "/"))
+        .map(|w| w.trim_start_matches("./").to_string())
+        .filter(|w| is_file(w))
+        .collect()
+}
+
+/// Command prefixes of a command line, minus generic ones and file names.
+fn commands_in(line: &str) -> Vec<String> {
+    let line = line.trim().trim_start_matches("$ ");
+    generator::bash_prefixes(line)
+        .into_iter()
+        .filter(|p| !GENERIC_COMMANDS.contains(&p.as_str()) && !is_file(p))
+        .collect()
+}
+
+/// Inline code spans of a step (`like this`).
+fn inline_code(step: &str) -> impl Iterator<Item = &str> {
+    step.split('`').skip(1).step_by(2)
+}
+
+fn cues(pattern: &KnowledgePattern) -> Cues {
+    let mut cues = Cues::default();
+    let mut add = |commands: Vec<String>, files: Vec<String>| {
+        for c in commands {
+            if !cues.commands.contains(&c) {
+                cues.commands.push(c);
+            }
+        }
+        for f in files {
+            if !cues.files.contains(&f) {
+                cues.files.push(f);
+            }
+        }
+    };
+
+    for step in &pattern.steps {
+        add(inline_code(step).flat_map(commands_in).collect(), files_in(step));
+    }
+    for example in &pattern.code_examples {
+        let mut lines = example.lines().peekable();
+        // Unfenced examples are taken as command lines, like a fence without language
+        let lang = match lines.peek().and_then(|l| l.trim_start().strip_prefix("```")) {
+            Some(lang) => {
+                let lang = lang.trim().to_string();
+                lines.next();
+                lang
+            }
+            None => String::new(),
+        };
+        let shell = SHELL_LANGUAGES.contains(&lang.as_str());
+        for line in lines.filter(|l| !l.trim_start().starts_with("```")) {
+            let commands = if shell { commands_in(line) } else { Vec::new() };
+            add(commands, files_in(line));
+        }
+    }
+    cues
+}
+
+/// Whether a path is the cued file (same path, or ends with it at a `/` boundary).
+fn names_file(path: &str, file: &str) -> bool {
+    let path = path.replace('\\', "/");
+    path == file || path.ends_with(&format!("/{}", file))
+}
+
+/// Whether a tool call follows one of the cues.
+fn follows(cues: &Cues, tool: &ToolUse) -> bool {
+    if let Some(ref path) = tool.file_path {
+        if cues.files.iter().any(|f| names_file(path, f)) {
+            return true;
+        }
+    }
+    let Some(ref command) = tool.command else {
+        return false;
+    };
+    commands_in(command).iter().any(|c| cues.commands.contains(c))
+        || command
+            .split_whitespace()
+            .any(|word| cues.files.iter().any(|f| names_file(word, f)))
+}
+
+/// Skill a pattern of a cluster is generated into (see `generator::generate_skills`).
+pub fn skill_of<'a>(cluster: &DomainCluster, pattern: &'a KnowledgePattern) -> &'a str {
+    pattern
+        .skill_slug
+        .as_deref()
+        .unwrap_or(domains::normalize(&cluster.domain).slug.as_str())
+}
+
+/// The patterns of one skill, with the domain and conversations of their cluster.
+pub fn skill_cluster(clusters: &[DomainCluster], slug: &str) -> Option<DomainCluster> {
+    clusters.iter().find_map(|cluster| {
+        let patterns: Vec<KnowledgePattern> = cluster
+            .patterns
+            .iter()
+            .filter(|p| skill_of(cluster, p) == slug)
+            .cloned()
+            .collect();
+        (!patterns.is_empty()).then(|| DomainCluster {
+            domain: cluster.domain.clone(),
+            conversations: cluster.conversations.clone(),
+            patterns,
+        })
+    })
+}
+
+/// Per pattern of a cluster (same order), how many invocations of its skill were followed
+/// by a tool call matching the pattern.
+pub fn section_usage(
+    cluster: &DomainCluster,
+    invocations: &[SkillInvocation],
+) -> Vec<SectionUsage> {
+    cluster
+        .patterns
+        .iter()
+        .map(|pattern| {
+            let cues = cues(pattern);
+            let skill = skill_of(cluster, pattern);
+            let fired: Vec<&SkillInvocation> =
+                invocations.iter().filter(|i| i.skill_name == skill).collect();
+            let used = fired
+                .iter()
+                .filter(|i| i.followed.iter().any(|t| follows(&cues, t)))
+                .count();
+            SectionUsage {
+                title: pattern.title.clone(),
+                used,
+                invocations: fired.len(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(title: &str, steps: &[&str], examples: &[&str]) -> KnowledgePattern {
+        KnowledgePattern {
+            title: title.to_string(),
+            description: String::new(),
+            steps: steps.iter().map(|s| s.to_string()).collect(),
+            code_examples: examples.iter().map(|s| s.to_string()).collect(),
+            source_ids: vec![],
+            frequency: 1,
+            skill_slug: Some("rust-build".to_string()),
+            project: None,
+        }
+    }
+
+    fn tool(command: Option<&str>, file_path: Option<&str>) -> ToolUse {
+        ToolUse {
+            name: if command.is_some() { "Bash" } else { "Edit" }.to_string(),
+            input_summary: String::new(),
+            file_path: file_path.map(String::from),
+            command: command.map(String::from),
+            task: None,
+            failed: false,
+        }
+    }
+
+    fn invocation(followed: Vec<ToolUse>) -> SkillInvocation {
+        SkillInvocation {
+            skill_name: "rust-build".to_string(),
+            conversation_id: "c".to_string(),
+            timestamp: None,
+            was_productive: true,
+            trigger_context: None,
+            outcome: Default::default(),
+            followed,
+        }
+    }
+
+    #[test]
+    fn cues_from_steps_and_examples() {
+        let p = pattern(
+            "Features",
+            &["Add the feature to Cargo.toml, e.g. v1.2", "Run `cargo build --features x`"],
+            &["```bash\n$ cargo test -p core\nls\n```", "```rust\nlet n = v.len();\n```"],
+        );
+        assert_eq!(
+            cues(&p),
+            Cues {
+                commands: vec!["cargo build".to_string(), "cargo test".to_string()],
+                files: vec!["Cargo.toml".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn usage_counts_followed_invocations() {
+        let cluster = DomainCluster {
+            domain: "Rust".to_string(),
+            conversations: vec![],
+            patterns: vec![
+                pattern("Tests", &["Run `cargo test`"], &[]),
+                pattern("Lints", &["Allow lints in src/lib.rs"], &[]),
+                pattern("Unused", &["Run `cargo bench`"], &[]),
+            ],
+        };
+        let invocations = vec![
+            invocation(vec![tool(Some("cd /r && cargo test --lib"), None)]),
+            invocation(vec![tool(None, Some("C:\\r\\src\\lib.rs"))]),
+            invocation(vec![tool(Some("git status"), None), tool(Some("cat src/lib.rs"), None)]),
+        ];
+        let usage: Vec<(usize, usize)> = section_usage(&cluster, &invocations)
+            .iter()
+            .map(|u| (u.used, u.invocations))
+            .collect();
+        assert_eq!(usage, vec![(1, 3), (2, 3), (0, 3)]);
+    }
+}
@@ -0,0 +1,271 @@
+// Audit module: append-only event log of manifest changes
+// Events are derived by diffing the manifest on disk against the one being written,
+// so every status change is recorded no matter which command made it.
+
+use crate::error::SkillMinerError;
+use crate::types::{AuditAction, AuditEvent, DraftStatus, Manifest};
+use std::io::Write;
+use std::path::Path;
+
+/// Audit log file name, next to manifest.toml.
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+/// User running the command, from $USER (or %USERNAME% on Windows).
+pub fn actor() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|u| !u.is_empty())
+}
+
+fn status_action(status: &DraftStatus) -> AuditAction {
+    match status {
+        DraftStatus::Draft => AuditAction::Reopened,
+        DraftStatus::Approved => AuditAction::Approved,
+        DraftStatus::Rejected => AuditAction::Rejected,
+        DraftStatus::Deployed => AuditAction::Deployed,
+    }
+}
+
+/// Events describing how `next` differs from `previous` (None = no manifest yet).
+/// Notes in `next.audit_notes` supply reasons and may override (or force) the action.
+pub fn diff(previous: Option<&Manifest>, next: &Manifest) -> Vec<AuditEvent> {
+    let now = chrono::Utc::now();
+    let actor = actor();
+    let mut events = Vec::new();
+
+    for entry in &next.entries {
+        let old = previous.and_then(|p| p.entries.iter().find(|e| e.slug == entry.slug));
+        let (action, from) = match old {
+            None => (Some(AuditAction::Generated), None),
+            Some(old) if old.status != entry.status => {
+                (Some(status_action(&entry.status)), Some(old.status.clone()))
+            }
+            Some(old) if entry.deployed_at.is_some() && old.deployed_at != entry.deployed_at => {
+                (Some(AuditAction::Deployed), Some(old.status.clone()))
+            }
+            Some(old) if old.score != entry.score && entry.score.is_some() => {
+                (Some(AuditAction::Consolidated), Some(old.status.clone()))
+            }
+            Some(old) if old.content_hash != entry.content_hash => {
+                (Some(AuditAction::Regenerated), Some(old.status.clone()))
+            }
+            Some(old) => (None, Some(old.status.clone())),
+        };
+        let note = next.audit_notes.get(&entry.slug);
+        // An explicit action is recorded even if no field it would be inferred from changed
+        let Some(action) = action.or_else(|| note.and_then(|n| n.action)) else {
+            continue;
+        };
+        events.push(AuditEvent {
+            at: now,
+            slug: entry.slug.clone(),
+            action: note.and_then(|n| n.action).unwrap_or(action),
+            from,
+            to: Some(entry.status.clone()),
+            score: entry.score.filter(|_| old.is_none_or(|o| o.score != entry.score)),
+            reason: note.and_then(|n| n.reason.clone()),
+            actor: actor.clone(),
+        });
+    }
+
+    if let Some(previous) = previous {
+        for old in &previous.entries {
+            if !next.entries.iter().any(|e| e.slug == old.slug) {
+                events.push(AuditEvent {
+                    at: now,
+                    slug: old.slug.clone(),
+                    action: AuditAction::Removed,
+                    from: Some(old.status.clone()),
+                    to: None,
+                    score: None,
+                    reason: next.audit_notes.get(&old.slug).and_then(|n| n.reason.clone()),
+                    actor: actor.clone(),
+                });
+            }
+        }
+    }
+
+    events
+}
+
+/// Append events to `<dir>/audit.jsonl`, one JSON object per line.
+pub fn append(dir: &Path, events: &[AuditEvent]) -> Result<(), SkillMinerError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let mut lines = String::new();
+    for event in events {
+        let line =
+            serde_json::to_string(event).map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(AUDIT_FILE))?;
+    file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+/// Read all events of a directory's audit log in time order.
+/// Unparsable lines (e.g. left by a merge) are skipped.
+pub fn read_log(dir: &Path) -> Result<Vec<AuditEvent>, SkillMinerError> {
+    let path = dir.join(AUDIT_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    let mut events: Vec<AuditEvent> = content
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    events.sort_by_key(|e| e.at);
+    Ok(events)
+}
+
+/// Timeline of one slug.
+pub fn history(dir: &Path, slug: &str) -> Result<Vec<AuditEvent>, SkillMinerError> {
+    Ok(read_log(dir)?.into_iter().filter(|e| e.slug == slug).collect())
+}
+
+/// Format an event as one timeline line.
+pub fn format_event(event: &AuditEvent) -> String {
+    let mut line = format!(
+        "{}  {:<12}",
+        event.at.format("%Y-%m-%d %H:%M:%S"),
+        event.action.to_string()
+    );
+    match (&event.from, &event.to) {
+        (Some(from), Some(to)) if from != to => line.push_str(&format!("  {} → {}", from, to)),
+        (None, Some(to)) => line.push_str(&format!("  → {}", to)),
+        _ => {}
+    }
+    if let Some(score) = event.score {
+        line.push_str(&format!("  score: {:.3}", score));
+    }
+    if let Some(ref actor) = event.actor {
+        line.push_str(&format!("  by {}", actor));
+    }
+    if let Some(ref reason) = event.reason {
+        line.push_str(&format!("  ({})", reason));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest;
+    use crate::types::{AuditNote, DraftEntry};
+    use std::collections::HashSet;
+
+    fn entry(slug: &str, status: DraftStatus) -> DraftEntry {
+        DraftEntry {
+            slug: slug.to_string(),
+            domain: "Test".to_string(),
+            status,
+            pattern_count: 1,
+            conversation_count: 1,
+            generated_at: chrono::Utc::now(),
+            content_hash: "abc".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn manifest_of(entries: Vec<DraftEntry>) -> Manifest {
+        Manifest {
+            version: "1.0".to_string(),
+            generated_at: chrono::Utc::now(),
+            entries,
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn diff_infers_actions() {
+        let before = manifest_of(vec![
+            entry("a", DraftStatus::Draft),
+            entry("b", DraftStatus::Approved),
+            entry("gone", DraftStatus::Draft),
+        ]);
+        let mut after = manifest_of(vec![
+            entry("a", DraftStatus::Approved),
+            entry("b", DraftStatus::Approved),
+            entry("new", DraftStatus::Draft),
+        ]);
+        after.entries[1].score = Some(0.42);
+
+        let events = diff(Some(&before), &after);
+        let actions: Vec<(&str, AuditAction)> =
+            events.iter().map(|e| (e.slug.as_str(), e.action)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("a", AuditAction::Approved),
+                ("b", AuditAction::Consolidated),
+                ("new", AuditAction::Generated),
+                ("gone", AuditAction::Removed),
+            ]
+        );
+        assert_eq!(events[0].from, Some(DraftStatus::Draft));
+        assert_eq!(events[1].score, Some(0.42));
+        assert!(diff(Some(&after), &after).is_empty());
+    }
+
+    #[test]
+    fn notes_add_reason_and_override_action() {
+        let before = manifest_of(vec![entry("a", DraftStatus::Deployed)]);
+        let mut after = manifest_of(vec![entry("a", DraftStatus::Rejected), entry("b", DraftStatus::Draft)]);
+        after.entries[0].score = Some(0.01);
+        after.audit_notes.insert(
+            "a".to_string(),
+            AuditNote {
+                action: None,
+                reason: Some("score below 0.1".to_string()),
+            },
+        );
+        after.audit_notes.insert(
+            "b".to_string(),
+            AuditNote {
+                action: Some(AuditAction::Imported),
+                reason: Some("bundle team-pack".to_string()),
+            },
+        );
+
+        let events = diff(Some(&before), &after);
+        assert_eq!(events[0].action, AuditAction::Rejected);
+        assert_eq!(events[0].score, Some(0.01));
+        assert_eq!(events[0].reason.as_deref(), Some("score below 0.1"));
+        assert_eq!(events[1].action, AuditAction::Imported);
+    }
+
+    #[test]
+    fn write_manifest_appends_timeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mf = manifest_of(vec![entry("a", DraftStatus::Draft)]);
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        manifest::update_status(&mut mf, "a", DraftStatus::Approved).unwrap();
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        // Writing an unchanged manifest records nothing
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+
+        let events = history(dir.path(), "a").unwrap();
+        let actions: Vec<AuditAction> = events.iter().map(|e| e.action).collect();
+        assert_eq!(actions, vec![AuditAction::Generated, AuditAction::Approved]);
+        assert!(format_event(&events[1]).contains("approved      draft → approved"));
+
+        // Garbage lines (e.g. from a bad merge) are skipped
+        append(dir.path(), &[]).unwrap();
+        std::fs::write(
+            dir.path().join(AUDIT_FILE),
+            std::fs::read_to_string(dir.path().join(AUDIT_FILE)).unwrap() + "<<<<<<< HEAD\n",
+        )
+        .unwrap();
+        assert_eq!(read_log(dir.path()).unwrap().len(), 2);
+    }
+}
@@ -3,9 +3,11 @@
 
 use crate::error::SkillMinerError;
 use crate::graph;
+use crate::layout;
 use crate::manifest;
 use crate::types::{
-    BundleSkill, BundleStats, DraftEntry, DraftStatus, ImportResult, Manifest, SkillBundle,
+    AuditAction, BundleSkill, BundleStats, DraftEntry, DraftMeta, DraftStatus, ImportResult, Manifest,
+    SkillBundle,
 };
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -82,13 +84,12 @@ pub fn export_bundle(
     let mut total_patterns = 0;
 
     for entry in &entries {
-        let source = draft_dir.join(format!("{}.md", entry.slug));
-        if !source.exists() {
+        if !layout::exists(draft_dir, &entry.slug) {
             eprintln!("warn: draft file missing for {}, skipping", entry.slug);
             continue;
         }
 
-        let original_content = std::fs::read_to_string(&source)?;
+        let original_content = layout::read_skill_md(draft_dir, &entry.slug)?;
         let content = if opts.public_sanitized {
             sanitize_public_content(&original_content)
         } else {
@@ -97,16 +98,36 @@ pub fn export_bundle(
         let hash = manifest::compute_hash(&content);
 
         // Verify hash matches
-        if hash != entry.content_hash {
+        if layout::hash_skill(draft_dir, &entry.slug)? != entry.content_hash {
             eprintln!(
                 "warn: hash mismatch for {} (file changed since manifest), using current hash",
                 entry.slug
             );
         }
 
-        // Copy to bundle
-        let dest = skills_dir.join(format!("{}.md", entry.slug));
-        std::fs::write(&dest, &content)?;
+        // Copy to bundle (folder skills keep their layout and supporting files)
+        let files = layout::supporting_files(draft_dir, &entry.slug)?;
+        if layout::is_folder(draft_dir, &entry.slug) {
+            let dest_root = skills_dir.join(&entry.slug);
+            std::fs::create_dir_all(&dest_root)?;
+            std::fs::write(dest_root.join(layout::SKILL_FILE), &content)?;
+            for rel in &files {
+                let raw = std::fs::read(draft_dir.join(&entry.slug).join(rel))?;
+                let data = match String::from_utf8(raw) {
+                    Ok(text) if opts.public_sanitized => sanitize_public_content(&text).into_bytes(),
+                    Ok(text) => text.into_bytes(),
+                    Err(e) => e.into_bytes(),
+                };
+                let dest = dest_root.join(rel);
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, data)?;
+            }
+        } else {
+            let dest = skills_dir.join(format!("{}.md", entry.slug));
+            std::fs::write(&dest, &content)?;
+        }
 
         // Extract dependency references from skill content
         let deps: Vec<String> = graph::extract_refs(&content)
@@ -123,6 +144,12 @@ pub fn export_bundle(
             fire_count: entry.fire_count,
             deployed_at: entry.deployed_at,
             dependencies: deps,
+            files,
+            meta: if opts.public_sanitized {
+                DraftMeta::default()
+            } else {
+                entry.meta.clone()
+            },
         });
 
         total_patterns += entry.pattern_count;
@@ -230,8 +257,7 @@ pub fn import_bundle(
     let bundle_skills_dir = bundle_path.join("skills");
 
     for skill in &bundle.skills {
-        let source = bundle_skills_dir.join(format!("{}.md", skill.slug));
-        if !source.exists() {
+        if !layout::exists(&bundle_skills_dir, &skill.slug) {
             eprintln!(
                 "warn: skill file missing in bundle: {}, skipping",
                 skill.slug
@@ -239,18 +265,21 @@ pub fn import_bundle(
             continue;
         }
 
-        let content = std::fs::read_to_string(&source)?;
-        let actual_hash = manifest::compute_hash(&content);
+        let content = layout::read_skill_md(&bundle_skills_dir, &skill.slug)?;
+        let actual_hash = layout::hash_skill(&bundle_skills_dir, &skill.slug)?;
 
-        // Verify integrity
-        if actual_hash != skill.content_hash {
+        // Verify integrity (the bundle records the hash of SKILL.md alone)
+        if manifest::compute_hash(&content) != skill.content_hash {
             eprintln!(
                 "warn: hash mismatch for {} in bundle (possibly corrupted)",
                 skill.slug
             );
         }
 
-        // Check if already exists in drafts
+        // Check if already exists in drafts; review metadata is merged either way
+        if let Some(e) = manifest::find_entry_mut(manifest, &skill.slug) {
+            e.meta.merge(&skill.meta);
+        }
         let existing = manifest.entries.iter().find(|e| e.slug == skill.slug);
 
         match existing {
@@ -261,14 +290,13 @@ pub fn import_bundle(
             Some(_) => {
                 // Different content, conflict
                 // Write with .imported suffix for manual review
-                let dest = draft_dir.join(format!("{}.imported.md", skill.slug));
-                std::fs::write(&dest, &content)?;
+                let imported_slug = format!("{}.imported", skill.slug);
+                layout::copy_skill(&bundle_skills_dir, &skill.slug, draft_dir, &imported_slug)?;
                 result.conflicted.push(skill.slug.clone());
             }
             None => {
                 // New skill, import
-                let dest = draft_dir.join(format!("{}.md", skill.slug));
-                std::fs::write(&dest, &content)?;
+                layout::copy_skill(&bundle_skills_dir, &skill.slug, draft_dir, &skill.slug)?;
 
                 manifest.entries.push(DraftEntry {
                     slug: skill.slug.clone(),
@@ -277,11 +305,16 @@ pub fn import_bundle(
                     pattern_count: skill.pattern_count,
                     conversation_count: 0,
                     generated_at: chrono::Utc::now(),
-                    deployed_at: None,
                     content_hash: actual_hash,
-                    score: None,
-                    fire_count: None,
+                    meta: skill.meta.clone(),
+                    ..Default::default()
                 });
+                manifest::annotate(
+                    manifest,
+                    &skill.slug,
+                    Some(AuditAction::Imported),
+                    &format!("bundle {} v{}", bundle.name, bundle.version),
+                );
 
                 result.imported.push(skill.slug.clone());
             }
@@ -312,13 +345,17 @@ pub fn verify_bundle(bundle_path: &Path) -> Result<Vec<String>, SkillMinerError>
     let mut errors = Vec::new();
 
     for skill in &bundle.skills {
-        let path = skills_dir.join(format!("{}.md", skill.slug));
-        if !path.exists() {
+        if !layout::exists(&skills_dir, &skill.slug) {
             errors.push(format!("{}: file missing", skill.slug));
             continue;
         }
+        for rel in &skill.files {
+            if !skills_dir.join(&skill.slug).join(rel).is_file() {
+                errors.push(format!("{}: supporting file missing: {}", skill.slug, rel));
+            }
+        }
 
-        let content = std::fs::read_to_string(&path)?;
+        let content = layout::read_skill_md(&skills_dir, &skill.slug)?;
         let actual_hash = manifest::compute_hash(&content);
         if actual_hash != skill.content_hash {
             errors.push(format!(
@@ -374,7 +411,7 @@ pub fn validate_bundle(
 
     let skills_dir = bundle_path.join("skills");
     for skill in &bundle.skills {
-        let path = skills_dir.join(format!("{}.md", skill.slug));
+        let path = layout::skill_md_path(&skills_dir, &skill.slug);
         if !path.exists() {
             continue; // already reported by integrity check
         }
@@ -407,6 +444,33 @@ pub fn validate_bundle(
                 .push(format!("{}: missing frontmatter 'description'", skill.slug));
         }
 
+        match parse_allowed_tools(&content) {
+            None => {}
+            Some(Err(e)) => report
+                .errors
+                .push(format!("{}: invalid 'allowed-tools': {}", skill.slug, e)),
+            Some(Ok(rules)) => {
+                if rules.is_empty() {
+                    report
+                        .warnings
+                        .push(format!("{}: 'allowed-tools' is empty", skill.slug));
+                }
+                for rule in &rules {
+                    if !is_valid_tool_rule(rule) {
+                        report.errors.push(format!(
+                            "{}: malformed 'allowed-tools' rule '{}'",
+                            skill.slug, rule
+                        ));
+                    } else if rule == "Bash" {
+                        report.warnings.push(format!(
+                            "{}: 'allowed-tools' grants unrestricted Bash",
+                            skill.slug
+                        ));
+                    }
+                }
+            }
+        }
+
         if !content.lines().any(|l| l.starts_with("## ")) {
             report
                 .warnings
@@ -455,7 +519,7 @@ pub fn fix_bundle(bundle_path: &Path, opts: &ValidateOptions) -> Result<FixRepor
     }
 
     for skill in &mut bundle.skills {
-        let path = skills_dir.join(format!("{}.md", skill.slug));
+        let path = layout::skill_md_path(&skills_dir, &skill.slug);
         if !path.exists() {
             continue;
         }
@@ -506,6 +570,9 @@ fn parse_frontmatter(content: &str) -> Option<HashMap<String, String>> {
         if trimmed == "---" {
             return Some(map);
         }
+        if trimmed.starts_with('-') {
+            continue; // list item of the previous key
+        }
         if let Some((k, v)) = trimmed.split_once(':') {
             let key = k.trim().to_string();
             let value = v.trim().trim_matches('"').trim_matches('\'').to_string();
@@ -518,6 +585,73 @@ fn parse_frontmatter(content: &str) -> Option<HashMap<String, String>> {
     None
 }
 
+/// Rules of the `allowed-tools` frontmatter key, given as a block list, a flow list
+/// (`[A, B]`) or a comma-separated string. None if the key is absent; Err on bad quoting.
+fn parse_allowed_tools(content: &str) -> Option<Result<Vec<String>, String>> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let front: Vec<&str> = lines.take_while(|l| l.trim() != "---").collect();
+    let idx = front.iter().position(|l| l.starts_with("allowed-tools:"))?;
+    let inline = front[idx]["allowed-tools:".len()..].trim();
+
+    let items: Vec<&str> = if inline.is_empty() {
+        front[idx + 1..]
+            .iter()
+            .map(|l| l.trim())
+            .take_while(|l| l.starts_with('-'))
+            .map(|l| l[1..].trim())
+            .collect()
+    } else if let Some(flow) = inline.strip_prefix('[') {
+        match flow.strip_suffix(']') {
+            Some(inner) => inner.split(',').map(|i| i.trim()).filter(|i| !i.is_empty()).collect(),
+            None => return Some(Err("unterminated flow list".to_string())),
+        }
+    } else {
+        vec![inline]
+    };
+
+    let mut rules = Vec::new();
+    for item in items {
+        let value = if let Some(rest) = item.strip_prefix('"') {
+            match rest.strip_suffix('"') {
+                Some(v) => v.replace("\\\"", "\"").replace("\\\\", "\\"),
+                None => return Some(Err(format!("unterminated quote in {}", item))),
+            }
+        } else if let Some(rest) = item.strip_prefix('\'') {
+            match rest.strip_suffix('\'') {
+                Some(v) => v.replace("''", "'"),
+                None => return Some(Err(format!("unterminated quote in {}", item))),
+            }
+        } else {
+            item.to_string()
+        };
+        // A plain scalar may hold several comma-separated rules
+        if inline.is_empty() || inline.starts_with('[') {
+            rules.push(value);
+        } else {
+            rules.extend(value.split(',').map(|r| r.trim().to_string()).filter(|r| !r.is_empty()));
+        }
+    }
+    Some(Ok(rules))
+}
+
+/// Whether an allowed-tools rule looks like `Tool` or `Tool(specifier)`.
+fn is_valid_tool_rule(rule: &str) -> bool {
+    let (name, spec) = match rule.split_once('(') {
+        Some((name, rest)) => match rest.strip_suffix(')') {
+            Some(spec) => (name, Some(spec)),
+            None => return false,
+        },
+        None => (rule, None),
+    };
+    let name_ok = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let spec_ok = spec.is_none_or(|s| !s.trim().is_empty() && !s.contains(['(', ')']));
+    name_ok && spec_ok
+}
+
 fn fix_skill_content(content: &str, slug: &str) -> (String, bool, Vec<String>) {
     let mut notes = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
@@ -796,10 +930,8 @@ mod tests {
             pattern_count: 3,
             conversation_count: 5,
             generated_at: Utc::now(),
-            deployed_at: None,
             content_hash: manifest::compute_hash(&content),
-            score: None,
-            fire_count: None,
+            ..Default::default()
         }
     }
 
@@ -824,6 +956,8 @@ mod tests {
             ],
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         };
 
         // Export
@@ -845,6 +979,8 @@ mod tests {
             entries: vec![],
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         };
 
         let result =
@@ -855,6 +991,55 @@ mod tests {
         assert_eq!(import_manifest.entries.len(), 2);
     }
 
+    #[test]
+    fn export_import_keeps_review_meta() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let import_dir = tempfile::tempdir().unwrap();
+        std::fs::write(draft_dir.path().join("skill-a.md"), "---\nname: skill-a\n---\n\n# A\n").unwrap();
+
+        let mut entry = make_entry("skill-a", "A", DraftStatus::Approved);
+        entry.meta.add_tags(&["windows".to_string()]);
+        entry.meta.owner = Some("yuuji".to_string());
+        let first = entry.meta.add_comment("needs a Windows example", Some("a".into()), None).unwrap();
+        entry.meta.add_comment("added", Some("b".into()), Some(first)).unwrap();
+        let mut manifest = Manifest {
+            version: "1.0".to_string(),
+            generated_at: Utc::now(),
+            entries: vec![entry.clone()],
+            mined_ids: std::collections::HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        };
+        let opts = ExportOptions {
+            approved_only: false,
+            name: "meta".to_string(),
+            author: None,
+            description: "meta export".to_string(),
+            include_context: false,
+            public_sanitized: false,
+        };
+        export_bundle(draft_dir.path(), bundle_dir.path(), &manifest, &opts).unwrap();
+
+        let mut import_manifest = Manifest { entries: vec![], ..manifest.clone() };
+        import_bundle(bundle_dir.path(), import_dir.path(), &mut import_manifest).unwrap();
+        assert_eq!(import_manifest.entries[0].meta, entry.meta);
+
+        // Re-importing into the source keeps its own metadata and adds nothing twice
+        manifest.entries[0].meta.add_tags(&["local".to_string()]);
+        import_bundle(bundle_dir.path(), draft_dir.path(), &mut manifest).unwrap();
+        let meta = &manifest.entries[0].meta;
+        assert_eq!(meta.tags, vec!["windows", "local"]);
+        assert_eq!(meta.comments.len(), 2);
+        assert_eq!(meta.comments[1].reply_to, Some(first));
+
+        let public = ExportOptions { public_sanitized: true, ..opts };
+        let public_dir = tempfile::tempdir().unwrap();
+        let bundle = export_bundle(draft_dir.path(), public_dir.path(), &manifest, &public).unwrap();
+        assert!(bundle.skills[0].meta.is_empty());
+    }
+
     #[test]
     fn verify_valid_bundle() {
         let bundle_dir = tempfile::tempdir().unwrap();
@@ -885,6 +1070,8 @@ mod tests {
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -923,6 +1110,8 @@ mod tests {
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -969,6 +1158,8 @@ mod tests {
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -985,13 +1176,13 @@ mod tests {
                 pattern_count: 2,
                 conversation_count: 5,
                 generated_at: Utc::now(),
-                deployed_at: None,
                 content_hash: hash,
-                score: None,
-                fire_count: None,
+                ..Default::default()
             }],
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         };
 
         let result =
@@ -1022,9 +1213,12 @@ mod tests {
                 content_hash: manifest::compute_hash(content),
                 score: Some(0.85),
                 fire_count: Some(12),
+                ..Default::default()
             }],
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         };
 
         let opts = ExportOptions {
@@ -1176,6 +1370,8 @@ content_hash = "{}"
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1228,6 +1424,8 @@ Use C:/Users/someone/ path and ~/.claude/history.jsonl
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1245,6 +1443,62 @@ Use C:/Users/someone/ path and ~/.claude/history.jsonl
         assert!(report.warnings.iter().any(|w| w.contains("C:/Users/")));
     }
 
+    #[test]
+    fn validate_bundle_checks_allowed_tools() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let skills_dir = bundle_dir.path().join("skills");
+        std::fs::create_dir_all(&skills_dir).unwrap();
+
+        let skills = [
+            ("good", "allowed-tools:\n  - \"Read\"\n  - \"Bash(cargo test:*)\"\n"),
+            ("inline", "allowed-tools: Read, Grep\n"),
+            ("shell", "allowed-tools: [Read, Bash]\n"),
+            ("broken", "allowed-tools:\n  - \"Bash(cargo test:*\"\n  - \"Read\n"),
+            ("paren", "allowed-tools:\n  - \"Bash(cargo test:*\"\n"),
+        ];
+        let mut entries = Vec::new();
+        for (slug, allowed) in skills {
+            let content = format!(
+                "---\nname: {}\ndescription: \"d\"\n{}---\n\n## Section\n",
+                slug, allowed
+            );
+            std::fs::write(skills_dir.join(format!("{}.md", slug)), &content).unwrap();
+            entries.push(BundleSkill {
+                slug: slug.to_string(),
+                domain: "Test".to_string(),
+                pattern_count: 1,
+                content_hash: manifest::compute_hash(&content),
+                score: None,
+                fire_count: None,
+                deployed_at: None,
+                dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
+            });
+        }
+        let bundle = SkillBundle {
+            name: "tools-test".to_string(),
+            version: "1.0".to_string(),
+            author: None,
+            description: "test".to_string(),
+            created_at: Utc::now(),
+            source: BundleStats {
+                conversations: 1,
+                domains: 5,
+                patterns: 5,
+            },
+            skills: entries,
+        };
+        let manifest_toml = toml::to_string_pretty(&bundle).unwrap();
+        std::fs::write(bundle_dir.path().join("manifest.toml"), manifest_toml).unwrap();
+
+        let report = validate_bundle(bundle_dir.path(), &ValidateOptions::default()).unwrap();
+        assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+        assert!(report.errors[0].starts_with("broken: invalid 'allowed-tools'"));
+        assert!(report.errors[1].starts_with("paren: malformed 'allowed-tools' rule"));
+        assert_eq!(report.warnings, vec!["shell: 'allowed-tools' grants unrestricted Bash"]);
+    }
+
     #[test]
     fn validate_bundle_handles_multibyte_frontmatter() {
         let bundle_dir = tempfile::tempdir().unwrap();
@@ -1281,6 +1535,8 @@ text
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1326,6 +1582,8 @@ text
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1386,6 +1644,8 @@ body
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1448,6 +1708,8 @@ Handle user data
                 fire_count: None,
                 deployed_at: None,
                 dependencies: vec![],
+                files: Vec::new(),
+                meta: Default::default(),
             }],
         };
 
@@ -1506,13 +1768,13 @@ author: yuuji
                 pattern_count: 3,
                 conversation_count: 2,
                 generated_at: Utc::now(),
-                deployed_at: None,
                 content_hash: manifest::compute_hash(content),
-                score: None,
-                fire_count: None,
+                ..Default::default()
             }],
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         };
 
         let opts = ExportOptions {
@@ -0,0 +1,463 @@
+// Commands module: slash-command drafts mined from recurring Bash command sequences
+// Offline (no AI): counts command n-grams per project and turns frequent ones into
+// `.claude/commands/<name>.md` drafts with their own manifest under `<drafts>/commands/`.
+
+use crate::deployer;
+use crate::error::SkillMinerError;
+use crate::manifest;
+use crate::project;
+use crate::types::{
+    escape_yaml_double_quoted, Conversation, DeployResult, DraftEntry, DraftStatus, Manifest,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Subdirectory of the drafts dir holding slash-command drafts and their manifest.
+pub const COMMANDS_SUBDIR: &str = "commands";
+
+/// Domain recorded in manifest entries for slash-command drafts.
+pub const COMMAND_DOMAIN: &str = "command";
+
+/// Commands that carry no workflow meaning on their own.
+const NOISE_COMMANDS: &[&str] = &["cd", "ls", "pwd", "clear", "echo"];
+
+/// Longest slug built from command words.
+const MAX_NAME_LEN: usize = 48;
+
+/// A command sequence that recurs across conversations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSequence {
+    pub commands: Vec<String>,
+    /// Number of distinct conversations containing the sequence
+    pub support: usize,
+    /// Project root if the sequence only recurs in one project (None = global)
+    pub project: Option<String>,
+    /// Conversation IDs the sequence was seen in
+    pub sources: Vec<String>,
+}
+
+/// A slash-command draft.
+#[derive(Debug, Clone)]
+pub struct CommandDraft {
+    /// Manifest slug: `<name>` for global commands, `<project-key>/<name>` for project commands
+    pub slug: String,
+    /// Command name (`/name` in Claude Code)
+    pub name: String,
+    pub sequence: CommandSequence,
+}
+
+impl CommandDraft {
+    pub fn description(&self) -> String {
+        format!("Run {}", self.sequence.commands.join(" → "))
+    }
+
+    /// Format as a slash-command markdown file.
+    pub fn format_md(&self) -> String {
+        let mut md = format!(
+            "---\ndescription: \"{}\"\n---\n\n",
+            escape_yaml_double_quoted(&self.description())
+        );
+        md.push_str("Run these commands in order. Stop at the first failure and report its output.\n\n");
+        for (i, cmd) in self.sequence.commands.iter().enumerate() {
+            md.push_str(&format!("{}. `{}`\n", i + 1, cmd));
+        }
+        md.push_str(&format!(
+            "\nRecurring sequence seen in {} conversations",
+            self.sequence.support
+        ));
+        if let Some(ref p) = self.sequence.project {
+            md.push_str(&format!(" (project: {})", p));
+        }
+        md.push_str(".\n");
+        md
+    }
+}
+
+/// Normalize a Bash command for counting: first line, collapsed whitespace,
+/// leading `cd <dir> &&` stripped. Returns None for noise commands.
+pub fn normalize_command(cmd: &str) -> Option<String> {
+    let line = cmd.lines().next()?.trim();
+    let mut cmd = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    while cmd.starts_with("cd ") {
+        match cmd.split_once("&&") {
+            Some((_, rest)) => cmd = rest.trim().to_string(),
+            None => break,
+        }
+    }
+    let program = cmd.split_whitespace().next()?;
+    if NOISE_COMMANDS.contains(&program) {
+        None
+    } else {
+        Some(cmd)
+    }
+}
+
+/// Bash commands of a conversation in order, normalized, with immediate repeats collapsed.
+pub fn command_sequence(conv: &Conversation) -> Vec<String> {
+    let mut seq: Vec<String> = Vec::new();
+    for tu in conv.messages.iter().flat_map(|m| &m.tool_uses) {
+        if tu.name != "Bash" {
+            continue;
+        }
+        if let Some(cmd) = tu.command.as_deref().and_then(normalize_command) {
+            if seq.last() != Some(&cmd) {
+                seq.push(cmd);
+            }
+        }
+    }
+    seq
+}
+
+/// Find command n-grams (length 2..=max_len) seen in at least `min_support` conversations.
+/// A sequence seen in two or more projects becomes global; otherwise it is scoped to its project.
+/// Sequences contained in a longer sequence with the same support are dropped.
+pub fn mine_sequences(
+    conversations: &[Conversation],
+    min_support: usize,
+    max_len: usize,
+) -> Vec<CommandSequence> {
+    // n-gram -> project ("" = unknown cwd) -> conversation ids
+    let mut counts: BTreeMap<Vec<String>, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+    for conv in conversations {
+        let seq = command_sequence(conv);
+        let project = conv.cwd.as_deref().map(project::normalize_project).unwrap_or_default();
+        let mut seen: HashSet<&[String]> = HashSet::new();
+        for n in 2..=max_len.max(2) {
+            for gram in seq.windows(n) {
+                if seen.insert(gram) {
+                    counts
+                        .entry(gram.to_vec())
+                        .or_default()
+                        .entry(project.clone())
+                        .or_default()
+                        .insert(conv.id.clone());
+                }
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    for (commands, by_project) in counts {
+        let sources: BTreeSet<String> = by_project.values().flatten().cloned().collect();
+        let candidate = if by_project.len() >= 2 || by_project.contains_key("") {
+            (sources.len() >= min_support).then(|| CommandSequence {
+                commands,
+                support: sources.len(),
+                project: None,
+                sources: sources.into_iter().collect(),
+            })
+        } else {
+            by_project.into_iter().next().and_then(|(project, ids)| {
+                (ids.len() >= min_support).then(|| CommandSequence {
+                    commands,
+                    support: ids.len(),
+                    project: Some(project),
+                    sources: ids.into_iter().collect(),
+                })
+            })
+        };
+        found.extend(candidate);
+    }
+
+    let maximal: Vec<CommandSequence> = found
+        .iter()
+        .filter(|s| {
+            !found.iter().any(|longer| {
+                longer.commands.len() > s.commands.len()
+                    && longer.project == s.project
+                    && longer.support >= s.support
+                    && longer.commands.windows(s.commands.len()).any(|w| w == s.commands.as_slice())
+            })
+        })
+        .cloned()
+        .collect();
+
+    let mut result = maximal;
+    result.sort_by(|a, b| {
+        b.support
+            .cmp(&a.support)
+            .then(b.commands.len().cmp(&a.commands.len()))
+            .then(a.commands.cmp(&b.commands))
+    });
+    result
+}
+
+/// Slug words for a command: program plus its first subcommand-like word.
+fn command_words(cmd: &str) -> Vec<String> {
+    cmd.split_whitespace()
+        .filter(|w| !w.starts_with('-') && !w.contains(['/', '\\', '=', '.', '"', '\'']))
+        .take(2)
+        .map(|w| {
+            w.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+                .collect::<String>()
+                .trim_matches('-')
+                .to_string()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Build a command name from its sequence, e.g. `cargo-build-cargo-test`.
+pub fn command_name(commands: &[String]) -> String {
+    let mut name = String::new();
+    for word in commands.iter().flat_map(|c| command_words(c)) {
+        if name.len() + word.len() + 1 > MAX_NAME_LEN {
+            break;
+        }
+        if !name.is_empty() {
+            name.push('-');
+        }
+        name.push_str(&word);
+    }
+    if name.is_empty() {
+        "command-sequence".to_string()
+    } else {
+        name
+    }
+}
+
+/// Turn mined sequences into drafts with unique slugs.
+pub fn generate_drafts(sequences: &[CommandSequence]) -> Vec<CommandDraft> {
+    let mut used: HashSet<String> = HashSet::new();
+    sequences
+        .iter()
+        .map(|seq| {
+            let base = command_name(&seq.commands);
+            let prefix = seq
+                .project
+                .as_deref()
+                .map(|p| format!("{}/", project::project_key(p)))
+                .unwrap_or_default();
+            let mut name = base.clone();
+            let mut n = 2;
+            while !used.insert(format!("{}{}", prefix, name)) {
+                name = format!("{}-{}", base, n);
+                n += 1;
+            }
+            CommandDraft {
+                slug: format!("{}{}", prefix, name),
+                name,
+                sequence: seq.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Load the slash-command manifest, or an empty one if none exists yet.
+pub fn load_manifest(drafts_dir: &Path) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(COMMANDS_SUBDIR);
+    if dir.join("manifest.toml").exists() {
+        manifest::read_manifest(&dir)
+    } else {
+        Ok(Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: Vec::new(),
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        })
+    }
+}
+
+/// Write drafts under `<drafts_dir>/commands/` and merge them into its manifest.
+pub fn write_drafts(drafts_dir: &Path, drafts: &[CommandDraft]) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(COMMANDS_SUBDIR);
+    let mut mf = load_manifest(drafts_dir)?;
+
+    for draft in drafts {
+        let path = dir.join(format!("{}.md", draft.slug));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = draft.format_md();
+        std::fs::write(&path, &content)?;
+
+        manifest::merge_entry(
+            &mut mf,
+            DraftEntry {
+                slug: draft.slug.clone(),
+                domain: COMMAND_DOMAIN.to_string(),
+                status: DraftStatus::Draft,
+                pattern_count: draft.sequence.commands.len(),
+                conversation_count: draft.sequence.support,
+                generated_at: chrono::Utc::now(),
+                content_hash: manifest::compute_hash(&content),
+                project: draft.sequence.project.clone(),
+                ..Default::default()
+            },
+        );
+    }
+
+    std::fs::create_dir_all(&dir)?;
+    mf.generated_at = chrono::Utc::now();
+    manifest::write_manifest(&dir, &mf)?;
+    Ok(mf)
+}
+
+/// Deploy target of a command draft: `<project>/.claude/commands/` for project commands,
+/// `commands_dir` (~/.claude/commands) otherwise.
+pub fn target_dir(commands_dir: &Path, entry: &DraftEntry) -> PathBuf {
+    match entry.project {
+        Some(ref p) => Path::new(p).join(".claude").join("commands"),
+        None => commands_dir.to_path_buf(),
+    }
+}
+
+/// Deploy command drafts by slug to their targets (see `deployer::deploy_to_dirs`).
+pub fn deploy_commands(
+    drafts_dir: &Path,
+    commands_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    deployer::deploy_to_dirs(&drafts_dir.join(COMMANDS_SUBDIR), manifest, slugs, |entry| {
+        target_dir(commands_dir, entry)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Message, Role, ToolUse};
+
+    fn bash(cmd: &str) -> ToolUse {
+        ToolUse {
+            name: "Bash".to_string(),
+            input_summary: cmd.to_string(),
+            file_path: None,
+            command: Some(cmd.to_string()),
+            task: None,
+            failed: false,
+        }
+    }
+
+    fn conv(id: &str, cwd: Option<&str>, cmds: &[&str]) -> Conversation {
+        Conversation {
+            id: id.to_string(),
+            source_path: PathBuf::from(format!("{}.jsonl", id)),
+            messages: vec![Message {
+                role: Role::Assistant,
+                content: String::new(),
+                timestamp: None,
+                tool_uses: cmds.iter().map(|c| bash(c)).collect(),
+                sidechain: false,
+            }],
+            start_time: None,
+            end_time: None,
+            cwd: cwd.map(|s| s.to_string()),
+            git_branch: None,
+        }
+    }
+
+    #[test]
+    fn normalize_strips_cd_and_noise() {
+        assert_eq!(
+            normalize_command("cd /repo && cargo   test").as_deref(),
+            Some("cargo test")
+        );
+        assert_eq!(normalize_command("ls -la"), None);
+        assert_eq!(normalize_command("cd /repo"), None);
+    }
+
+    #[test]
+    fn sequence_collapses_repeats() {
+        let c = conv("a", None, &["cargo build", "cargo build", "ls", "cargo test"]);
+        assert_eq!(command_sequence(&c), vec!["cargo build", "cargo test"]);
+    }
+
+    #[test]
+    fn mine_project_and_global_sequences() {
+        let convs = vec![
+            conv("1", Some("/r/app"), &["cargo fmt", "cargo clippy", "cargo test"]),
+            conv("2", Some("/r/app"), &["cargo fmt", "cargo clippy", "cargo test"]),
+            conv("3", Some("/r/app"), &["git status", "cargo fmt", "cargo clippy", "cargo test"]),
+            conv("4", Some("/r/lib"), &["git add -A", "git commit"]),
+            conv("5", Some("/r/app"), &["git add -A", "git commit"]),
+            conv("6", Some("/r/web"), &["git add -A", "git commit"]),
+        ];
+        let found = mine_sequences(&convs, 3, 4);
+        assert_eq!(found.len(), 2);
+
+        let fmt = found.iter().find(|s| s.commands[0] == "cargo fmt").unwrap();
+        // The 2-gram prefix is subsumed by the 3-gram with the same support
+        assert_eq!(fmt.commands.len(), 3);
+        assert_eq!(fmt.support, 3);
+        assert_eq!(fmt.project.as_deref(), Some("/r/app"));
+
+        let git = found.iter().find(|s| s.commands[0] == "git add -A").unwrap();
+        assert_eq!(git.project, None);
+        assert_eq!(git.sources, vec!["4", "5", "6"]);
+    }
+
+    #[test]
+    fn names_and_slugs() {
+        let seq = CommandSequence {
+            commands: vec!["cargo build --release".to_string(), "./target/run.sh".to_string(), "cargo test".to_string()],
+            support: 3,
+            project: Some("/r/my app".to_string()),
+            sources: vec![],
+        };
+        assert_eq!(command_name(&seq.commands), "cargo-build-cargo-test");
+        let drafts = generate_drafts(&[seq.clone(), seq]);
+        assert_eq!(drafts[0].slug, "my-app/cargo-build-cargo-test");
+        assert_eq!(drafts[1].slug, "my-app/cargo-build-cargo-test-2");
+        let md = drafts[0].format_md();
+        assert!(md.starts_with("---\ndescription: \"Run cargo build --release → "));
+        assert!(md.contains("1. `cargo build --release`"));
+        assert!(md.contains("(project: /r/my app)"));
+    }
+
+    #[test]
+    fn write_and_deploy_commands() {
+        let drafts_dir = tempfile::tempdir().unwrap();
+        let commands_dir = tempfile::tempdir().unwrap();
+        let project_dir = tempfile::tempdir().unwrap();
+        let project = project_dir.path().to_string_lossy().to_string();
+        let drafts = generate_drafts(&[
+            CommandSequence {
+                commands: vec!["git add -A".to_string(), "git commit".to_string()],
+                support: 3,
+                project: None,
+                sources: vec![],
+            },
+            CommandSequence {
+                commands: vec!["make".to_string(), "make check".to_string()],
+                support: 4,
+                project: Some(project.clone()),
+                sources: vec![],
+            },
+        ]);
+
+        let mut mf = write_drafts(drafts_dir.path(), &drafts).unwrap();
+        assert_eq!(mf.entries.len(), 2);
+        assert!(mf.entries.iter().all(|e| e.domain == COMMAND_DOMAIN));
+        assert_eq!(load_manifest(drafts_dir.path()).unwrap().entries.len(), 2);
+
+        // Unreviewed drafts are not deployed
+        let slugs: Vec<String> = drafts.iter().map(|d| d.slug.clone()).collect();
+        let results = deploy_commands(drafts_dir.path(), commands_dir.path(), &mut mf, &slugs).unwrap();
+        assert!(results.is_empty());
+
+        for e in &mut mf.entries {
+            e.status = DraftStatus::Approved;
+        }
+        let results = deploy_commands(drafts_dir.path(), commands_dir.path(), &mut mf, &slugs).unwrap();
+        assert_eq!(results[0].target_path, commands_dir.path().join("git-add-git-commit.md"));
+        assert_eq!(
+            results[1].target_path,
+            project_dir.path().join(".claude").join("commands").join("make-make-check.md")
+        );
+        assert!(results[1].target_path.exists());
+        assert!(mf.entries.iter().all(|e| e.status == DraftStatus::Deployed));
+        assert!(mf.entries.iter().all(|e| e.deployed_hash.is_some()));
+
+        // A hand edit to a deployed command is kept
+        std::fs::write(&results[0].target_path, "---\ndescription: \"mine\"\n---\n").unwrap();
+        let results = deploy_commands(drafts_dir.path(), commands_dir.path(), &mut mf, &slugs).unwrap();
+        assert!(results[0].merged);
+        assert!(!results[1].merged);
+    }
+}
@@ -1,42 +1,163 @@
 // Deployer module: deploy skills, diff, prune
 // Issue #22
 
+pub use crate::diff::{DiffLine, DiffResult};
+use crate::diff;
 use crate::error::SkillMinerError;
+use crate::layout;
 use crate::manifest;
-use crate::types::{DeployResult, DraftEntry, DraftStatus, Manifest, PruneOptions, SkillDraft};
+use crate::merge;
+use crate::targets;
+use crate::transaction::{self, Transaction};
+use crate::types::{
+    DeployResult, DraftEntry, DraftStatus, Manifest, PruneOptions, SkillDraft, UndeployOptions,
+    UndeployResult,
+};
 use std::collections::HashMap;
-use std::fmt;
 use std::path::Path;
 
 /// Deploy a single skill draft to the skills directory.
+/// Folder drafts (`<slug>/SKILL.md` + supporting files) are copied as a folder.
+/// If the deployed skill was edited by hand since the last deploy (its hash no longer
+/// matches `entry.deployed_hash`, or `entry.content_hash` for deployed entries that have
+/// none), the new SKILL.md is three-way merged into it instead of overwriting; conflicting
+/// sections are left with markers and listed in the result.
 pub fn deploy_skill(
     draft_dir: &Path,
     skills_dir: &Path,
     entry: &DraftEntry,
 ) -> Result<DeployResult, SkillMinerError> {
-    let source = draft_dir.join(format!("{}.md", entry.slug));
-    if !source.exists() {
+    deploy_as(draft_dir, entry, skills_dir, &entry.slug)
+}
+
+/// Deploy a draft to `dest_dir` under the name `dest_slug`, like `deploy_skill`, and record
+/// the draft as the merge base of its next deploy.
+fn deploy_as(
+    draft_dir: &Path,
+    entry: &DraftEntry,
+    dest_dir: &Path,
+    dest_slug: &str,
+) -> Result<DeployResult, SkillMinerError> {
+    let base = load_base(draft_dir, &entry.slug)?;
+    let result = deploy_onto(draft_dir, entry, dest_dir, dest_slug, Some(&base))?;
+    save_base(draft_dir, &entry.slug)?;
+    Ok(result)
+}
+
+/// Merge base of a draft's next deploy. Without a recorded base every differing section
+/// is treated as a conflict.
+fn load_base(draft_dir: &Path, slug: &str) -> Result<String, SkillMinerError> {
+    Ok(merge::load_base(draft_dir, slug)?.unwrap_or_default())
+}
+
+fn save_base(draft_dir: &Path, slug: &str) -> Result<(), SkillMinerError> {
+    merge::save_base(draft_dir, slug, &layout::read_skill_md(draft_dir, slug)?)
+}
+
+/// Copy a draft to `dest_dir` under the name `dest_slug`. Hand edits are three-way merged
+/// against `base`, or replaced without one. The merge base is left alone.
+fn deploy_onto(
+    draft_dir: &Path,
+    entry: &DraftEntry,
+    dest_dir: &Path,
+    dest_slug: &str,
+    base: Option<&str>,
+) -> Result<DeployResult, SkillMinerError> {
+    if !layout::exists(draft_dir, &entry.slug) {
         return Err(SkillMinerError::Config(format!(
             "draft file not found: {}",
-            source.display()
+            layout::skill_md_path(draft_dir, &entry.slug).display()
         )));
     }
 
-    std::fs::create_dir_all(skills_dir)?;
+    std::fs::create_dir_all(dest_dir)?;
+
+    let was_update = layout::exists(dest_dir, dest_slug);
+    let draft_content = layout::read_skill_md(draft_dir, &entry.slug)?;
+    let deployed_content = if was_update {
+        Some(layout::read_skill_md(dest_dir, dest_slug)?)
+    } else {
+        None
+    };
+    let expected = match (&entry.deployed_hash, &entry.status) {
+        (Some(hash), _) => Some(hash),
+        (None, DraftStatus::Deployed) => Some(&entry.content_hash),
+        (None, _) => None,
+    };
+    let hand_edited = match (&deployed_content, expected) {
+        (Some(_), Some(hash)) if base.is_some() => layout::hash_skill(dest_dir, dest_slug)? != *hash,
+        _ => false,
+    };
 
-    let target = skills_dir.join(format!("{}.md", entry.slug));
-    let was_update = target.exists();
+    let target = layout::copy_skill(draft_dir, &entry.slug, dest_dir, dest_slug)?;
 
-    std::fs::copy(&source, &target)?;
+    let mut conflicts = Vec::new();
+    if let (true, Some(deployed), Some(base)) = (hand_edited, &deployed_content, base) {
+        let result = merge::merge_skill(base, deployed, &draft_content);
+        std::fs::write(&target, &result.content)?;
+        conflicts = result.conflicts;
+    }
 
     Ok(DeployResult {
         slug: entry.slug.clone(),
         target_path: target,
         was_update,
+        merged: hand_edited,
+        conflicts,
     })
 }
 
-/// Deploy all approved drafts.
+/// Deploy a draft to each of its targets: the global `skills_dir` and/or
+/// `<project>/.claude/skills`. Hand edits are detected per target and merged against the
+/// same base, which is replaced once every target is written.
+pub fn deploy_to_targets(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    entry: &DraftEntry,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    deploy_targets(draft_dir, skills_dir, entry, true)
+}
+
+fn deploy_targets(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    entry: &DraftEntry,
+    merge: bool,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    let base = if merge {
+        Some(load_base(draft_dir, &entry.slug)?)
+    } else {
+        None
+    };
+    let mut results = Vec::new();
+    for target in targets::of(entry) {
+        let mut at_target = entry.clone();
+        at_target.deployed_hash = targets::deployed_hash(entry, &target).map(str::to_string);
+        let dir = targets::dir(skills_dir, &target);
+        results.push(deploy_onto(draft_dir, &at_target, &dir, &entry.slug, base.as_deref())?);
+    }
+    save_base(draft_dir, &entry.slug)?;
+    Ok(results)
+}
+
+/// Mark a manifest entry as deployed to all its targets, recording the hash of the
+/// deployed draft content.
+fn mark_deployed(
+    manifest: &mut Manifest,
+    draft_dir: &Path,
+    slug: &str,
+) -> Result<(), SkillMinerError> {
+    let hash = layout::hash_skill(draft_dir, slug)?;
+    if let Some(e) = manifest.entries.iter_mut().find(|e| e.slug == slug) {
+        e.status = DraftStatus::Deployed;
+        for target in targets::of(e) {
+            targets::mark_deployed(e, &target, &hash);
+        }
+    }
+    Ok(())
+}
+
+/// Deploy all approved drafts to their targets and write the manifest of `draft_dir`.
 pub fn deploy_approved(
     draft_dir: &Path,
     skills_dir: &Path,
@@ -49,109 +170,378 @@ pub fn deploy_approved(
         .map(|e| e.slug.clone())
         .collect();
 
+    deploy_transaction(draft_dir, skills_dir, manifest, &approved_slugs, true)
+}
+
+/// Deploy specific drafts by slug names to their targets and write the manifest of
+/// `draft_dir`.
+pub fn deploy_by_names(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    names: &[String],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    if let Some(name) = names.iter().find(|n| manifest::find_entry(manifest, n).is_none()) {
+        return Err(SkillMinerError::Config(format!("draft not found: {}", name)));
+    }
+    deploy_transaction(draft_dir, skills_dir, manifest, names, true)
+}
+
+/// Deploy drafts by slug like `deploy_by_names`, but replace the deployed copies instead of
+/// merging hand edits into them (a rollback deploys exactly the restored version).
+/// `deploy --undo` brings the replaced copies back.
+pub fn deploy_replacing(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    names: &[String],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    if let Some(name) = names.iter().find(|n| manifest::find_entry(manifest, n).is_none()) {
+        return Err(SkillMinerError::Config(format!("draft not found: {}", name)));
+    }
+    deploy_transaction(draft_dir, skills_dir, manifest, names, false)
+}
+
+/// Deploy drafts by slug like `deploy_by_names` and delete the deployed copies of
+/// `removed` entries (already dropped from the manifest), as one transaction that writes
+/// the manifest once.
+pub fn deploy_and_remove(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    removed: &[DraftEntry],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    transaction::run(draft_dir, manifest, |tx, manifest| {
+        let results = apply_deploys(tx, draft_dir, skills_dir, manifest, slugs, true)?;
+        for entry in removed.iter().filter(|e| e.status == DraftStatus::Deployed) {
+            for target in targets::of(entry) {
+                let dir = targets::dir(skills_dir, &target);
+                tx.snapshot_skill(&dir, &entry.slug)?;
+                layout::remove_skill(&dir, &entry.slug)?;
+            }
+        }
+        Ok(results)
+    })
+}
+
+/// Deploy drafts as one transaction: every skill path (and merge base) is snapshotted
+/// before it is written, and the manifest is written before the transaction commits. Any
+/// error restores the snapshots and the manifest entries.
+fn deploy_transaction(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    merge: bool,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    transaction::run(draft_dir, manifest, |tx, manifest| {
+        apply_deploys(tx, draft_dir, skills_dir, manifest, slugs, merge)
+    })
+}
+
+fn apply_deploys(
+    tx: &mut Transaction,
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    merge: bool,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
     let mut results = Vec::new();
-    for slug in &approved_slugs {
-        let entry = manifest
-            .entries
-            .iter()
-            .find(|e| e.slug == *slug)
+    for slug in slugs {
+        let entry = manifest::find_entry(manifest, slug)
             .cloned()
-            .unwrap();
-        let result = deploy_skill(draft_dir, skills_dir, &entry)?;
-        results.push(result);
+            .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", slug)))?;
+        tx.remember(&entry)?;
+        for target in targets::of(&entry) {
+            tx.snapshot_skill(&targets::dir(skills_dir, &target), slug)?;
+        }
+        tx.snapshot(&merge::base_path(draft_dir, slug))?;
+        results.extend(deploy_targets(draft_dir, skills_dir, &entry, merge)?);
 
         // Update manifest
-        if let Some(e) = manifest.entries.iter_mut().find(|e| e.slug == *slug) {
+        mark_deployed(manifest, draft_dir, slug)?;
+    }
+    Ok(results)
+}
+
+/// Deploy drafts by slug to the directory `dest` gives for each, as one transaction like
+/// `deploy_by_names`. A draft is written under the last component of its slug
+/// (`<key>/<name>` deploys as `<name>`), hand edits are merged like skills and the deployed
+/// hash is recorded. Drafts that are not approved (or deployed before) are skipped.
+pub fn deploy_to_dirs(
+    draft_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    dest: impl Fn(&DraftEntry) -> std::path::PathBuf,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    transaction::run(draft_dir, manifest, |tx, manifest| {
+        apply_to_dirs(tx, draft_dir, manifest, slugs, dest)
+    })
+}
+
+fn apply_to_dirs(
+    tx: &mut Transaction,
+    draft_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    dest: impl Fn(&DraftEntry) -> std::path::PathBuf,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    let mut results = Vec::new();
+    for slug in slugs {
+        let entry = manifest::find_entry(manifest, slug)
+            .cloned()
+            .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", slug)))?;
+        if !matches!(entry.status, DraftStatus::Approved | DraftStatus::Deployed) {
+            continue;
+        }
+        let dir = dest(&entry);
+        let name = slug.rsplit('/').next().unwrap_or(slug);
+        tx.remember(&entry)?;
+        tx.snapshot_skill(&dir, name)?;
+        tx.snapshot(&merge::base_path(draft_dir, slug))?;
+        results.push(deploy_as(draft_dir, &entry, &dir, name)?);
+
+        let hash = layout::hash_skill(draft_dir, slug)?;
+        if let Some(e) = manifest::find_entry_mut(manifest, slug) {
             e.status = DraftStatus::Deployed;
             e.deployed_at = Some(chrono::Utc::now());
+            e.deployed_hash = Some(hash);
         }
     }
-
     Ok(results)
 }
 
-/// Deploy specific drafts by slug names.
-pub fn deploy_by_names(
+/// Directory (inside the drafts dir) holding archived copies of undeployed skills.
+pub const ARCHIVE_DIR: &str = ".archive";
+
+/// Remove deployed copies of skills from their targets (or one target), as one transaction
+/// that `deploy --undo` can revert, and write the manifest. A deployed entry no longer deployed anywhere becomes approved,
+/// or rejected with `opts.reject`.
+pub fn undeploy(
     draft_dir: &Path,
     skills_dir: &Path,
     manifest: &mut Manifest,
-    names: &[String],
-) -> Result<Vec<DeployResult>, SkillMinerError> {
+    slugs: &[String],
+    opts: &UndeployOptions,
+) -> Result<Vec<UndeployResult>, SkillMinerError> {
+    if let Some(slug) = slugs.iter().find(|s| manifest::find_entry(manifest, s).is_none()) {
+        return Err(SkillMinerError::Config(format!("draft not found: {}", slug)));
+    }
+    transaction::run(draft_dir, manifest, |tx, manifest| {
+        apply_undeploys(tx, draft_dir, skills_dir, manifest, slugs, opts)
+    })
+}
+
+fn apply_undeploys(
+    tx: &mut Transaction,
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    opts: &UndeployOptions,
+) -> Result<Vec<UndeployResult>, SkillMinerError> {
+    let archive_root = draft_dir
+        .join(ARCHIVE_DIR)
+        .join(chrono::Utc::now().format("%Y%m%dT%H%M%S").to_string());
     let mut results = Vec::new();
-    for name in names {
-        let entry = manifest
-            .entries
-            .iter()
-            .find(|e| e.slug == *name)
-            .cloned()
-            .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", name)))?;
-        let result = deploy_skill(draft_dir, skills_dir, &entry)?;
-        results.push(result);
+    for slug in slugs {
+        let Some(entry) = manifest::find_entry_mut(manifest, slug) else {
+            continue;
+        };
+        tx.remember(entry)?;
+        let mut result = UndeployResult {
+            slug: slug.clone(),
+            removed: Vec::new(),
+            archived: Vec::new(),
+            status: entry.status.clone(),
+        };
+        let chosen: Vec<String> = targets::of(entry)
+            .into_iter()
+            .filter(|t| opts.target.as_ref().is_none_or(|want| want == t))
+            .collect();
+        for target in &chosen {
+            let dir = targets::dir(skills_dir, target);
+            if layout::exists(&dir, slug) {
+                tx.snapshot_skill(&dir, slug)?;
+                if opts.archive {
+                    let key = if target == targets::GLOBAL {
+                        targets::GLOBAL.to_string()
+                    } else {
+                        crate::project::project_key(target)
+                    };
+                    let archived = layout::copy_skill(&dir, slug, &archive_root.join(key), slug)?;
+                    result.archived.push(archived);
+                }
+                result.removed.push(layout::skill_md_path(&dir, slug));
+                layout::remove_skill(&dir, slug)?;
+            }
+            targets::clear_deployed(entry, target);
+        }
 
-        if let Some(e) = manifest.entries.iter_mut().find(|e| e.slug == *name) {
-            e.status = DraftStatus::Deployed;
-            e.deployed_at = Some(chrono::Utc::now());
+        let still_deployed = targets::of(entry)
+            .iter()
+            .any(|t| targets::deployed_hash(entry, t).is_some());
+        // A skill still deployed to other targets stays deployed, even with --reject.
+        // Entries that were not deployed keep their status.
+        if !still_deployed && entry.status == DraftStatus::Deployed {
+            let status = if opts.reject {
+                DraftStatus::Rejected
+            } else {
+                DraftStatus::Approved
+            };
+            manifest::update_status(manifest, slug, status)?;
         }
+        result.status = manifest::find_entry(manifest, slug)
+            .map(|e| e.status.clone())
+            .unwrap_or(result.status);
+        results.push(result);
     }
     Ok(results)
 }
 
 /// Show diff between a draft and its deployed version.
+/// Prints context hunks of SKILL.md, or with `sections` a per-pattern summary
+/// ("pattern 3 added", "pattern 1 steps changed").
 pub fn diff_skill(
     draft_dir: &Path,
     skills_dir: &Path,
     slug: &str,
+    sections: bool,
 ) -> Result<String, SkillMinerError> {
-    let draft_path = draft_dir.join(format!("{}.md", slug));
-    let deployed_path = skills_dir.join(format!("{}.md", slug));
-
-    if !draft_path.exists() {
+    if !layout::exists(draft_dir, slug) {
         return Err(SkillMinerError::Config(format!(
             "draft not found: {}",
             slug
         )));
     }
 
-    if !deployed_path.exists() {
+    if !layout::exists(skills_dir, slug) {
         return Ok(format!("[NEW] {} — not yet deployed", slug));
     }
 
-    let draft_content = std::fs::read_to_string(&draft_path)?;
-    let deployed_content = std::fs::read_to_string(&deployed_path)?;
+    let draft_content = layout::read_skill_md(draft_dir, slug)?;
+    let deployed_content = layout::read_skill_md(skills_dir, slug)?;
+    let changed_files = diff_supporting_files(draft_dir, skills_dir, slug)?;
 
     let draft_hash = manifest::compute_hash(&draft_content);
     let deployed_hash = manifest::compute_hash(&deployed_content);
 
-    if draft_hash == deployed_hash {
+    if draft_hash == deployed_hash && changed_files.is_empty() {
         return Ok(format!("[IDENTICAL] {} — no changes", slug));
     }
 
-    // Line-level diff
-    let draft_lines: Vec<&str> = draft_content.lines().collect();
-    let deployed_lines: Vec<&str> = deployed_content.lines().collect();
-
-    let mut diff = String::new();
-    diff.push_str(&format!(
+    let mut out = String::new();
+    out.push_str(&format!(
         "[CHANGED] {} — draft vs deployed\n",
         slug
     ));
 
-    // Simple unified-style output
-    let max_lines = draft_lines.len().max(deployed_lines.len());
-    for i in 0..max_lines {
-        let d = deployed_lines.get(i).copied().unwrap_or("");
-        let n = draft_lines.get(i).copied().unwrap_or("");
-        if d != n {
-            if !d.is_empty() {
-                diff.push_str(&format!("-{}\n", d));
-            }
-            if !n.is_empty() {
-                diff.push_str(&format!("+{}\n", n));
+    for file in &changed_files {
+        out.push_str(&format!("  file {}\n", file));
+    }
+
+    if sections {
+        for change in diff::sections(&deployed_content, &draft_content) {
+            out.push_str(&format!("  {}\n", change));
+        }
+    } else {
+        out.push_str(&diff::compute(&deployed_content, &draft_content).to_string());
+    }
+
+    Ok(out)
+}
+
+/// Unified diff turning the deployed skill into the draft, for SKILL.md and every changed
+/// text supporting file. Paths are `a/<slug>/...` and `b/<slug>/...`, so the patch applies
+/// with `patch -p1` inside the skills directory. Empty when nothing differs.
+pub fn skill_patch(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    slug: &str,
+) -> Result<String, SkillMinerError> {
+    if !layout::exists(draft_dir, slug) {
+        return Err(SkillMinerError::Config(format!(
+            "draft not found: {}",
+            slug
+        )));
+    }
+    let deployed = layout::exists(skills_dir, slug);
+    let draft_content = layout::read_skill_md(draft_dir, slug)?;
+    let deployed_content = if deployed {
+        layout::read_skill_md(skills_dir, slug)?
+    } else {
+        String::new()
+    };
+
+    let mut files = vec![(
+        "SKILL.md".to_string(),
+        deployed.then_some(deployed_content),
+        Some(draft_content),
+    )];
+    let mut rels = layout::supporting_files(draft_dir, slug)?;
+    if deployed {
+        for rel in layout::supporting_files(skills_dir, slug)? {
+            if !rels.contains(&rel) {
+                rels.push(rel);
             }
         }
     }
+    for rel in rels {
+        // Binary supporting files are left out of the patch
+        let read = |dir: &Path| std::fs::read_to_string(dir.join(slug).join(&rel)).ok();
+        let old = if deployed { read(skills_dir) } else { None };
+        files.push((rel.clone(), old, read(draft_dir)));
+    }
 
-    Ok(diff)
+    let mut patch = String::new();
+    for (rel, old, new) in files {
+        if old.is_none() && new.is_none() {
+            continue;
+        }
+        let result = diff::compute(old.as_deref().unwrap_or(""), new.as_deref().unwrap_or(""));
+        if result.is_empty() {
+            continue;
+        }
+        let label = |prefix: &str, side: &Option<String>| match side {
+            Some(_) => format!("{}/{}/{}", prefix, slug, rel),
+            None => "/dev/null".to_string(),
+        };
+        patch.push_str(&result.unified(&label("a", &old), &label("b", &new)));
+    }
+    Ok(patch)
+}
+
+/// Supporting files that differ between draft and deployed folder skills,
+/// formatted as "<path> (added|removed|changed)".
+fn diff_supporting_files(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    slug: &str,
+) -> Result<Vec<String>, SkillMinerError> {
+    let draft_files = layout::supporting_files(draft_dir, slug)?;
+    let deployed_files = layout::supporting_files(skills_dir, slug)?;
+    let mut changed = Vec::new();
+
+    for rel in &draft_files {
+        if !deployed_files.contains(rel) {
+            changed.push(format!("{} (added)", rel));
+            continue;
+        }
+        let a = std::fs::read(draft_dir.join(slug).join(rel))?;
+        let b = std::fs::read(skills_dir.join(slug).join(rel))?;
+        if a != b {
+            changed.push(format!("{} (changed)", rel));
+        }
+    }
+    for rel in &deployed_files {
+        if !draft_files.contains(rel) {
+            changed.push(format!("{} (removed)", rel));
+        }
+    }
+
+    Ok(changed)
 }
 
 /// Prune drafts based on options.
@@ -168,11 +558,8 @@ pub fn prune(
             || (opts.duplicates && is_duplicate_japanese_name(&entry.slug));
 
         if should_remove {
-            // Try to remove the file
-            let path = draft_dir.join(format!("{}.md", entry.slug));
-            if path.exists() {
-                let _ = std::fs::remove_file(&path);
-            }
+            // Try to remove the file (or skill folder)
+            let _ = layout::remove_skill(draft_dir, &entry.slug);
             removed.push(entry.slug.clone());
             false // remove from manifest
         } else {
@@ -216,7 +603,7 @@ pub fn check_existing_skills(
             if let Ok(content) = std::fs::read_to_string(path) {
                 let existing_body = extract_body(&content);
                 let diff = compute_diff(&existing_body, &draft.body);
-                if !diff.lines.is_empty() {
+                if !diff.is_empty() {
                     draft.diff = Some(diff.to_string());
                 }
             }
@@ -227,37 +614,7 @@ pub fn check_existing_skills(
 }
 
 fn load_existing_skills(skills_dir: &Path) -> Result<HashMap<String, std::path::PathBuf>, SkillMinerError> {
-    let mut skills = HashMap::new();
-
-    if !skills_dir.exists() {
-        return Ok(skills);
-    }
-
-    for entry in std::fs::read_dir(skills_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            let name = path
-                .file_stem()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-            skills.insert(name, path);
-        } else if path.is_dir() {
-            let skill_md = path.join("SKILL.md");
-            if skill_md.exists() {
-                let name = path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-                skills.insert(name, skill_md);
-            }
-        }
-    }
-
-    Ok(skills)
+    Ok(layout::list_skills(skills_dir)?.into_iter().collect())
 }
 
 /// Extract the body portion of a skill .md file, skipping YAML frontmatter.
@@ -279,105 +636,9 @@ pub fn extract_body(content: &str) -> String {
     }
 }
 
-/// Result of a line-level diff
-pub struct DiffResult {
-    pub lines: Vec<DiffLine>,
-}
-
-pub enum DiffLine {
-    Added(String),
-    Removed(String),
-}
-
-impl DiffResult {
-    pub fn added_count(&self) -> usize {
-        self.lines.iter().filter(|l| matches!(l, DiffLine::Added(_))).count()
-    }
-
-    pub fn removed_count(&self) -> usize {
-        self.lines.iter().filter(|l| matches!(l, DiffLine::Removed(_))).count()
-    }
-}
-
-impl fmt::Display for DiffResult {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for line in &self.lines {
-            match line {
-                DiffLine::Added(s) => writeln!(f, "+{}", s)?,
-                DiffLine::Removed(s) => writeln!(f, "-{}", s)?,
-            }
-        }
-        Ok(())
-    }
-}
-
-/// Compute a simple line-level diff between old and new text.
-fn compute_diff(old: &str, new: &str) -> DiffResult {
-    let old_lines: Vec<&str> = old.lines().collect();
-    let new_lines: Vec<&str> = new.lines().collect();
-
-    let mut diff_lines = Vec::new();
-
-    let old_set: HashMap<&str, usize> = {
-        let mut m = HashMap::new();
-        for line in &old_lines {
-            *m.entry(*line).or_insert(0) += 1;
-        }
-        m
-    };
-    let new_set: HashMap<&str, usize> = {
-        let mut m = HashMap::new();
-        for line in &new_lines {
-            *m.entry(*line).or_insert(0) += 1;
-        }
-        m
-    };
-
-    let mut old_counts = old_set.clone();
-    for (line, &new_count) in &new_set {
-        if let Some(old_count) = old_counts.get_mut(line) {
-            if new_count >= *old_count {
-                *old_count = 0;
-            } else {
-                *old_count -= new_count;
-            }
-        }
-    }
-    for line in &old_lines {
-        if let Some(count) = old_counts.get_mut(line) {
-            if *count > 0 {
-                let trimmed = line.trim();
-                if !trimmed.is_empty() {
-                    diff_lines.push(DiffLine::Removed(line.to_string()));
-                    *count -= 1;
-                }
-            }
-        }
-    }
-
-    let mut new_counts = new_set;
-    for (line, &old_count) in &old_set {
-        if let Some(new_count) = new_counts.get_mut(line) {
-            if old_count >= *new_count {
-                *new_count = 0;
-            } else {
-                *new_count -= old_count;
-            }
-        }
-    }
-    for line in &new_lines {
-        if let Some(count) = new_counts.get_mut(*line) {
-            if *count > 0 {
-                let trimmed = line.trim();
-                if !trimmed.is_empty() {
-                    diff_lines.push(DiffLine::Added(line.to_string()));
-                    *count -= 1;
-                }
-            }
-        }
-    }
-
-    DiffResult { lines: diff_lines }
+/// Line diff between old and new text (see `diff::compute`).
+pub fn compute_diff(old: &str, new: &str) -> DiffResult {
+    crate::diff::compute(old, new)
 }
 
 /// Parse diff summary from a SkillDraft's diff string.
@@ -409,10 +670,8 @@ mod tests {
             pattern_count: 3,
             conversation_count: 5,
             generated_at: Utc::now(),
-            deployed_at: None,
             content_hash: manifest::compute_hash("test"),
-            score: None,
-            fire_count: None,
+            ..Default::default()
         }
     }
 
@@ -423,6 +682,8 @@ mod tests {
             entries,
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         }
     }
 
@@ -502,7 +763,7 @@ mod tests {
 
         std::fs::write(draft_dir.path().join("new-skill.md"), "new content").unwrap();
 
-        let result = diff_skill(draft_dir.path(), skills_dir.path(), "new-skill").unwrap();
+        let result = diff_skill(draft_dir.path(), skills_dir.path(), "new-skill", false).unwrap();
         assert!(result.contains("[NEW]"));
     }
 
@@ -524,7 +785,7 @@ mod tests {
     fn test_compute_diff_identical() {
         let text = "# Title\n\nLine 1\nLine 2\n";
         let diff = compute_diff(text, text);
-        assert!(diff.lines.is_empty());
+        assert!(diff.is_empty());
     }
 
     #[test]
@@ -575,7 +836,221 @@ mod tests {
         std::fs::write(draft_dir.path().join("same.md"), content).unwrap();
         std::fs::write(skills_dir.path().join("same.md"), content).unwrap();
 
-        let result = diff_skill(draft_dir.path(), skills_dir.path(), "same").unwrap();
+        let result = diff_skill(draft_dir.path(), skills_dir.path(), "same", false).unwrap();
+        assert!(result.contains("[IDENTICAL]"));
+    }
+
+    #[test]
+    fn deploy_and_diff_folder_skill() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+
+        let root = draft_dir.path().join("folder-skill");
+        std::fs::create_dir_all(root.join("examples")).unwrap();
+        std::fs::write(root.join("SKILL.md"), "body").unwrap();
+        std::fs::write(root.join("examples").join("1-1.rs"), "fn a() {}").unwrap();
+
+        let entry = make_entry("folder-skill", "Test", DraftStatus::Approved);
+        let r = deploy_skill(draft_dir.path(), skills_dir.path(), &entry).unwrap();
+        assert!(r.target_path.ends_with("folder-skill/SKILL.md"));
+        assert!(skills_dir.path().join("folder-skill/examples/1-1.rs").exists());
+
+        let result = diff_skill(draft_dir.path(), skills_dir.path(), "folder-skill", false).unwrap();
         assert!(result.contains("[IDENTICAL]"));
+
+        std::fs::write(root.join("examples").join("1-1.rs"), "fn b() {}").unwrap();
+        let result = diff_skill(draft_dir.path(), skills_dir.path(), "folder-skill", false).unwrap();
+        assert!(result.contains("[CHANGED]"));
+        assert!(result.contains("file examples/1-1.rs (changed)"));
+
+        let patch = skill_patch(draft_dir.path(), skills_dir.path(), "folder-skill").unwrap();
+        assert_eq!(
+            patch,
+            "--- a/folder-skill/examples/1-1.rs\n+++ b/folder-skill/examples/1-1.rs\n\
+             @@ -1,1 +1,1 @@\n-fn a() {}\n+fn b() {}\n"
+        );
+    }
+
+    #[test]
+    fn deploy_writes_each_target() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        std::fs::write(draft_dir.path().join("s.md"), "# S\n").unwrap();
+
+        let mut entry = make_entry("s", "D", DraftStatus::Approved);
+        targets::set(&mut entry, &[targets::GLOBAL.to_string(), repo_path.clone()]);
+        let mut mf = make_manifest_with(vec![entry]);
+        let results = deploy_approved(draft_dir.path(), skills_dir.path(), &mut mf).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(skills_dir.path().join("s.md").exists());
+        assert!(repo.path().join(".claude/skills/s.md").exists());
+        let entry = &mf.entries[0];
+        assert!(entry.deployed_hash.is_some());
+        assert_eq!(targets::deployed_hash(entry, &repo_path), entry.deployed_hash.as_deref());
+    }
+
+    #[test]
+    fn failed_deploy_rolls_back_and_undo_restores() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        std::fs::write(draft_dir.path().join("a.md"), "a v2").unwrap();
+        std::fs::write(skills_dir.path().join("a.md"), "a v1").unwrap();
+        // "b" has no draft file: deploying it fails after "a" was written
+        let mut mf = make_manifest_with(vec![
+            make_entry("a", "D", DraftStatus::Approved),
+            make_entry("b", "D", DraftStatus::Approved),
+        ]);
+        assert!(deploy_approved(draft_dir.path(), skills_dir.path(), &mut mf).is_err());
+        assert_eq!(std::fs::read_to_string(skills_dir.path().join("a.md")).unwrap(), "a v1");
+        assert_eq!(mf.entries[0].status, DraftStatus::Approved);
+        assert!(mf.entries[0].deployed_hash.is_none());
+
+        let names = ["a".to_string()];
+        deploy_by_names(draft_dir.path(), skills_dir.path(), &mut mf, &names).unwrap();
+        assert_eq!(std::fs::read_to_string(skills_dir.path().join("a.md")).unwrap(), "a v2");
+        crate::transaction::undo(draft_dir.path(), &mut mf).unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(skills_dir.path().join("a.md")).unwrap(), "a v1");
+        assert_eq!(mf.entries[0].status, DraftStatus::Approved);
+    }
+
+    #[test]
+    fn undeploy_removes_or_archives_per_target() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        std::fs::write(draft_dir.path().join("s.md"), "# S\n").unwrap();
+        let mut entry = make_entry("s", "D", DraftStatus::Approved);
+        targets::set(&mut entry, &[targets::GLOBAL.to_string(), repo_path.clone()]);
+        let mut mf = make_manifest_with(vec![entry]);
+        deploy_approved(draft_dir.path(), skills_dir.path(), &mut mf).unwrap();
+
+        // One target: the entry stays deployed, even with --reject
+        let opts = UndeployOptions {
+            target: Some(repo_path.clone()),
+            archive: true,
+            reject: true,
+        };
+        let names = ["s".to_string()];
+        let r = undeploy(draft_dir.path(), skills_dir.path(), &mut mf, &names, &opts).unwrap();
+        assert_eq!(r[0].status, DraftStatus::Deployed);
+        assert!(!repo.path().join(".claude/skills/s.md").exists());
+        assert!(r[0].archived[0].exists());
+        assert!(targets::deployed_hash(&mf.entries[0], &repo_path).is_none());
+
+        let r = undeploy(draft_dir.path(), skills_dir.path(), &mut mf, &names, &Default::default())
+            .unwrap();
+        assert_eq!(r[0].status, DraftStatus::Approved);
+        assert!(!skills_dir.path().join("s.md").exists());
+        assert!(mf.entries[0].deployed_hash.is_none());
+
+        // Undeploys are transactions too
+        crate::transaction::undo(draft_dir.path(), &mut mf).unwrap();
+        assert!(skills_dir.path().join("s.md").exists());
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+
+        // Removed from the last target: --reject applies
+        let opts = UndeployOptions { reject: true, ..Default::default() };
+        let r = undeploy(draft_dir.path(), skills_dir.path(), &mut mf, &names, &opts).unwrap();
+        assert_eq!(r[0].status, DraftStatus::Rejected);
+
+        // Approved → rejected is not a valid transition: entries that were not deployed
+        // keep their status
+        mf.entries.push(make_entry("t", "D", DraftStatus::Approved));
+        let names = ["t".to_string()];
+        let r = undeploy(draft_dir.path(), skills_dir.path(), &mut mf, &names, &opts).unwrap();
+        assert_eq!(r[0].status, DraftStatus::Approved);
+    }
+
+    #[test]
+    fn deploy_merges_hand_edits() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        let draft_path = draft_dir.path().join("s.md");
+        let v1 = "# D\n\n## 1. Alpha\n\nalpha v1\n\n## 2. Beta\n\nbeta v1\n";
+
+        std::fs::write(&draft_path, v1).unwrap();
+        let mut mf = make_manifest_with(vec![make_entry("s", "D", DraftStatus::Approved)]);
+        deploy_by_names(draft_dir.path(), skills_dir.path(), &mut mf, &["s".to_string()]).unwrap();
+        assert!(mf.entries[0].deployed_hash.is_some());
+
+        // Hand edit to the deployed file, then a regenerated draft
+        let deployed = skills_dir.path().join("s.md");
+        std::fs::write(&deployed, v1.replace("alpha v1", "alpha by hand")).unwrap();
+        std::fs::write(&draft_path, v1.replace("beta v1", "beta v2")).unwrap();
+
+        let results =
+            deploy_by_names(draft_dir.path(), skills_dir.path(), &mut mf, &["s".to_string()]).unwrap();
+        assert!(results[0].merged);
+        assert!(results[0].conflicts.is_empty());
+        let content = std::fs::read_to_string(&deployed).unwrap();
+        assert!(content.contains("alpha by hand"));
+        assert!(content.contains("beta v2"));
+    }
+
+    #[test]
+    fn removed_entries_lose_their_deployed_copies_in_the_transaction() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        std::fs::write(skills_dir.path().join("gone.md"), "# Gone\n").unwrap();
+        let removed = vec![make_entry("gone", "D", DraftStatus::Deployed)];
+        let mut mf = make_manifest_with(Vec::new());
+
+        deploy_and_remove(draft_dir.path(), skills_dir.path(), &mut mf, &[], &removed).unwrap();
+        assert!(!skills_dir.path().join("gone.md").exists());
+        assert!(draft_dir.path().join("manifest.toml").exists());
+
+        crate::transaction::undo(draft_dir.path(), &mut mf).unwrap().unwrap();
+        assert!(skills_dir.path().join("gone.md").exists());
+    }
+
+    #[test]
+    fn every_target_merges_against_the_same_base() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let draft_path = draft_dir.path().join("s.md");
+        let v1 = "# D\n\n## 1. Alpha\n\nalpha v1\n\n## 2. Beta\n\nbeta v1\n";
+
+        std::fs::write(&draft_path, v1).unwrap();
+        let mut entry = make_entry("s", "D", DraftStatus::Approved);
+        let repo_path = repo.path().to_string_lossy().to_string();
+        targets::set(&mut entry, &[targets::GLOBAL.to_string(), repo_path]);
+        let mut mf = make_manifest_with(vec![entry]);
+        deploy_approved(draft_dir.path(), skills_dir.path(), &mut mf).unwrap();
+
+        // Only the second target is edited by hand
+        let edited = repo.path().join(".claude/skills/s.md");
+        std::fs::write(&edited, v1.replace("alpha v1", "alpha by hand")).unwrap();
+        std::fs::write(&draft_path, v1.replace("beta v1", "beta v2")).unwrap();
+
+        let results =
+            deploy_by_names(draft_dir.path(), skills_dir.path(), &mut mf, &["s".to_string()]).unwrap();
+        assert_eq!(results.iter().map(|r| r.merged).collect::<Vec<_>>(), vec![false, true]);
+        let content = std::fs::read_to_string(&edited).unwrap();
+        assert!(content.contains("alpha by hand"));
+        assert!(content.contains("beta v2"));
+    }
+
+    #[test]
+    fn hand_edits_without_deployed_hash_use_content_hash() {
+        let draft_dir = tempfile::tempdir().unwrap();
+        let skills_dir = tempfile::tempdir().unwrap();
+        std::fs::write(draft_dir.path().join("s.md"), "# S v2\n").unwrap();
+        let mut entry = make_entry("s", "D", DraftStatus::Deployed);
+        entry.content_hash = manifest::compute_hash("# S v1\n");
+
+        // Unchanged since deploy: overwritten
+        std::fs::write(skills_dir.path().join("s.md"), "# S v1\n").unwrap();
+        let r = deploy_skill(draft_dir.path(), skills_dir.path(), &entry).unwrap();
+        assert!(!r.merged);
+
+        // Edited by hand since deploy: merged
+        std::fs::write(skills_dir.path().join("s.md"), "# S by hand\n").unwrap();
+        let r = deploy_skill(draft_dir.path(), skills_dir.path(), &entry).unwrap();
+        assert!(r.merged);
     }
 }
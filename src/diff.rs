@@ -0,0 +1,410 @@
+// Diff module: ordered line diff (Myers) with context hunks and unified output
+// Shared by `diff`, the update preview of `mine`/`generate`, version diffs and bundle imports.
+// The section-aware mode reports changes per `## N. Title` pattern section.
+
+use crate::merge;
+use std::fmt;
+
+/// Context lines kept around each change, as in `diff -u`.
+pub const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+/// A run of changes with surrounding context. Starts are 1-based line numbers
+/// (or the line before, when the side is empty, as `diff -u` does).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Result of a line diff: the hunks in file order (empty when the texts are identical).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffResult {
+    pub hunks: Vec<Hunk>,
+}
+
+impl DiffResult {
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    fn count(&self, pred: fn(&DiffLine) -> bool) -> usize {
+        self.hunks.iter().flat_map(|h| &h.lines).filter(|l| pred(l)).count()
+    }
+
+    pub fn added_count(&self) -> usize {
+        self.count(|l| matches!(l, DiffLine::Added(_)))
+    }
+
+    pub fn removed_count(&self) -> usize {
+        self.count(|l| matches!(l, DiffLine::Removed(_)))
+    }
+
+    /// Unified diff with `---`/`+++` headers, suitable for a `.patch` file.
+    /// Use `/dev/null` as a label for a file that does not exist on that side.
+    pub fn unified(&self, old_label: &str, new_label: &str) -> String {
+        format!("--- {}\n+++ {}\n{}", old_label, new_label, self)
+    }
+}
+
+impl fmt::Display for Hunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        )?;
+        for line in &self.lines {
+            match line {
+                DiffLine::Context(s) => writeln!(f, " {}", s)?,
+                DiffLine::Added(s) => writeln!(f, "+{}", s)?,
+                DiffLine::Removed(s) => writeln!(f, "-{}", s)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiffResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for hunk in &self.hunks {
+            write!(f, "{}", hunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Shortest edit script between `a` and `b` (Myers' O(ND) algorithm).
+/// Deletions come before insertions within a change.
+fn edit_script(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    let idx = |k: isize| (k + max) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let mut trace = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk the trace back from (n, m) to recover the path
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[idx(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(Op::Equal);
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                ops.push(Op::Insert);
+            } else {
+                x -= 1;
+                ops.push(Op::Delete);
+            }
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// Line diff between two texts, grouped into hunks with `CONTEXT` lines of context.
+pub fn compute(old: &str, new: &str) -> DiffResult {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let ops = edit_script(&a, &b);
+
+    // Position in both files before each op
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut i, mut j) = (0, 0);
+    for op in &ops {
+        positions.push((i, j));
+        match op {
+            Op::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k] != Op::Equal).collect();
+    let mut hunks = Vec::new();
+    let mut c = 0;
+    while c < changes.len() {
+        let start = changes[c].saturating_sub(CONTEXT);
+        let mut last = changes[c];
+        // Changes separated by at most 2 * CONTEXT equal lines share a hunk
+        while c + 1 < changes.len() && changes[c + 1] <= last + 2 * CONTEXT + 1 {
+            c += 1;
+            last = changes[c];
+        }
+        let end = (last + CONTEXT + 1).min(ops.len());
+
+        let (old_pos, new_pos) = positions[start];
+        let mut hunk = Hunk {
+            old_start: old_pos,
+            old_len: 0,
+            new_start: new_pos,
+            new_len: 0,
+            lines: Vec::new(),
+        };
+        for (k, op) in ops.iter().enumerate().take(end).skip(start) {
+            let (i, j) = positions[k];
+            match op {
+                Op::Equal => {
+                    hunk.lines.push(DiffLine::Context(a[i].to_string()));
+                    hunk.old_len += 1;
+                    hunk.new_len += 1;
+                }
+                Op::Delete => {
+                    hunk.lines.push(DiffLine::Removed(a[i].to_string()));
+                    hunk.old_len += 1;
+                }
+                Op::Insert => {
+                    hunk.lines.push(DiffLine::Added(b[j].to_string()));
+                    hunk.new_len += 1;
+                }
+            }
+        }
+        if hunk.old_len > 0 {
+            hunk.old_start += 1;
+        }
+        if hunk.new_len > 0 {
+            hunk.new_start += 1;
+        }
+        hunks.push(hunk);
+        c += 1;
+    }
+
+    DiffResult { hunks }
+}
+
+/// A change to one block of a skill file, from the section-aware diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionChange {
+    /// Frontmatter or the header before the first section
+    Block(String),
+    Added { number: usize, title: String },
+    Removed { number: usize, title: String },
+    /// `parts` are the changed parts of the section: description, steps, examples, score, ...
+    Changed {
+        number: usize,
+        title: String,
+        parts: Vec<String>,
+    },
+}
+
+impl fmt::Display for SectionChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionChange::Block(name) => write!(f, "{} changed", name),
+            SectionChange::Added { number, title } => {
+                write!(f, "pattern {} added ({})", number, title)
+            }
+            SectionChange::Removed { number, title } => {
+                write!(f, "pattern {} removed ({})", number, title)
+            }
+            SectionChange::Changed {
+                number,
+                title,
+                parts,
+            } => write!(f, "pattern {} {} changed ({})", number, parts.join(", "), title),
+        }
+    }
+}
+
+/// Split a section body into named parts: the description before the first `###` heading,
+/// one part per `### Heading` (lowercased), and the trailing Frequency/Score line.
+fn section_parts(body: &str) -> Vec<(String, String)> {
+    let mut parts: Vec<(String, String)> = vec![("description".to_string(), String::new())];
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence {
+            if let Some(heading) = line.strip_prefix("### ") {
+                parts.push((heading.trim().to_lowercase(), String::new()));
+                continue;
+            }
+            if line.starts_with("Frequency:") || line.starts_with("Score:") {
+                parts.push(("score".to_string(), String::new()));
+            }
+        }
+        let part = &mut parts.last_mut().expect("parts starts non-empty").1;
+        part.push_str(line.trim_end());
+        part.push('\n');
+    }
+    parts
+}
+
+fn changed_parts(old: &str, new: &str) -> Vec<String> {
+    let old = section_parts(old);
+    let new = section_parts(new);
+    let text = |parts: &[(String, String)], name: &str| {
+        parts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, t)| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+    let mut names: Vec<&String> = new.iter().map(|(n, _)| n).collect();
+    for (name, _) in &old {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+        .into_iter()
+        .filter(|name| text(&old, name) != text(&new, name))
+        .cloned()
+        .collect()
+}
+
+/// Section-aware diff of two skill files: which patterns were added, removed or changed,
+/// and which parts of a changed pattern differ. Sections are matched by title, so
+/// renumbering alone is not a change. Numbers refer to the new file (the old one for removals).
+pub fn sections(old: &str, new: &str) -> Vec<SectionChange> {
+    let old_blocks = merge::split_blocks(old);
+    let new_blocks = merge::split_blocks(new);
+    let number = |blocks: &[merge::Block], key: &str| {
+        blocks
+            .iter()
+            .filter(|b| b.title.is_some())
+            .position(|b| b.key == key)
+            .map(|p| p + 1)
+            .unwrap_or(0)
+    };
+
+    let mut changes = Vec::new();
+    for block in &new_blocks {
+        let old_block = old_blocks.iter().find(|b| b.key == block.key);
+        match (&block.title, old_block) {
+            (None, Some(o)) if o.text() != block.text() => {
+                changes.push(SectionChange::Block(block.key.clone()));
+            }
+            (None, None) => changes.push(SectionChange::Block(block.key.clone())),
+            (Some(title), None) => changes.push(SectionChange::Added {
+                number: number(&new_blocks, &block.key),
+                title: title.clone(),
+            }),
+            (Some(title), Some(o)) if o.text() != block.text() => {
+                changes.push(SectionChange::Changed {
+                    number: number(&new_blocks, &block.key),
+                    title: title.clone(),
+                    parts: changed_parts(&o.body, &block.body),
+                });
+            }
+            _ => {}
+        }
+    }
+    for block in &old_blocks {
+        if new_blocks.iter().any(|b| b.key == block.key) {
+            continue;
+        }
+        changes.push(match &block.title {
+            Some(title) => SectionChange::Removed {
+                number: number(&old_blocks, &block.key),
+                title: title.clone(),
+            },
+            None => SectionChange::Block(block.key.clone()),
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let diff = compute(old, new);
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(
+            diff.hunks[0].to_string(),
+            "@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n"
+        );
+        assert_eq!(diff.hunks[1].to_string(), "@@ -8,3 +8,4 @@\n h\n i\n j\n+k\n");
+        assert_eq!((diff.added_count(), diff.removed_count()), (2, 1));
+    }
+
+    #[test]
+    fn moved_line_is_removed_and_added() {
+        // A multiset diff would see no change here
+        let diff = compute("one\ntwo\n", "two\none\n");
+        assert_eq!((diff.added_count(), diff.removed_count()), (1, 1));
+        assert!(compute("same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn unified_new_file() {
+        let patch = compute("", "x\ny\n").unified("/dev/null", "b/s/SKILL.md");
+        assert_eq!(patch, "--- /dev/null\n+++ b/s/SKILL.md\n@@ -0,0 +1,2 @@\n+x\n+y\n");
+    }
+
+    #[test]
+    fn section_summary() {
+        let old = "# Rust\n\n## 1. Errors\n\nUse thiserror.\n\n### Steps\n\n1. Define enum\n\n\
+                   ## 2. Lifetimes\n\nBorrowing.\n";
+        let new = "# Rust\n\n## 1. Lifetimes\n\nBorrowing.\n\n## 2. Errors\n\nUse thiserror.\n\n\
+                   ### Steps\n\n1. Define enum\n2. Derive Error\n\n## 3. Traits\n\nGenerics.\n";
+        let changes = sections(old, new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].to_string(), "pattern 2 steps changed (Errors)");
+        assert_eq!(changes[1].to_string(), "pattern 3 added (Traits)");
+
+        let removed = sections(new, old);
+        assert!(removed.contains(&SectionChange::Removed {
+            number: 3,
+            title: "Traits".to_string()
+        }));
+    }
+}
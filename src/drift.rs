@@ -0,0 +1,285 @@
+// Drift module: reconcile deployed skills with the manifest
+// Deployed files get edited by hand, deleted, or added by other tools. Each skills dir the
+// manifest deploys to is compared against the recorded deploy hashes, and hand-edited or
+// unknown skills can be adopted back into the drafts. The `projects/` and `commands/`
+// manifests are read too, so their deployed files are known.
+
+use crate::commands;
+use crate::error::SkillMinerError;
+use crate::layout;
+use crate::manifest;
+use crate::merge;
+use crate::project;
+use crate::targets;
+use crate::types::{AuditAction, DeployTarget, DraftStatus, DriftKind, Manifest, SkillDrift};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Compare the skills dirs of every target with the manifest of `draft_dir`, and deployed
+/// slash commands with its `commands/` manifest.
+/// Deployed entries are checked against the hash recorded at deploy (the draft's
+/// `content_hash` for entries deployed before hashes were recorded); skills in a target dir
+/// without a manifest entry, and not deployed there from `projects/`, are reported as unknown.
+pub fn detect(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    commands_dir: &Path,
+    manifest: &Manifest,
+) -> Result<Vec<SkillDrift>, SkillMinerError> {
+    let mut drifts = Vec::new();
+    let mut dirs: BTreeSet<String> = BTreeSet::from([targets::GLOBAL.to_string()]);
+
+    for entry in &manifest.entries {
+        dirs.extend(entry.targets.iter().map(|t| t.path.clone()));
+        if entry.status != DraftStatus::Deployed {
+            continue;
+        }
+        for target in targets::of(entry) {
+            // A project target added after the last deploy has nothing there yet
+            if target != targets::GLOBAL && targets::deployed_at(entry, &target).is_none() {
+                continue;
+            }
+            let dir = targets::dir(skills_dir, &target);
+            let path = layout::skill_md_path(&dir, &entry.slug);
+            let kind = if !layout::exists(&dir, &entry.slug) {
+                DriftKind::Missing
+            } else {
+                let expected = targets::deployed_hash(entry, &target).unwrap_or(&entry.content_hash);
+                if layout::hash_skill(&dir, &entry.slug)? == expected {
+                    continue;
+                }
+                DriftKind::Modified
+            };
+            drifts.push(SkillDrift {
+                slug: entry.slug.clone(),
+                target,
+                kind,
+                path,
+                command: false,
+            });
+        }
+    }
+
+    // Project drafts deploy as `<repo>/.claude/skills/<name>` (the last part of their slug)
+    let projects = project::load_manifest(draft_dir)?;
+    let project_skills: BTreeSet<(&str, &str)> = projects
+        .entries
+        .iter()
+        .filter(|e| e.status == DraftStatus::Deployed)
+        .filter_map(|e| Some((e.project.as_deref()?, deployed_name(&e.slug))))
+        .collect();
+
+    for target in dirs {
+        for (slug, path) in layout::list_skills(&targets::dir(skills_dir, &target))? {
+            let known = manifest::find_entry(manifest, &slug).is_some()
+                || project_skills.contains(&(target.as_str(), slug.as_str()));
+            if !known {
+                drifts.push(SkillDrift {
+                    slug,
+                    target: target.clone(),
+                    kind: DriftKind::Unknown,
+                    path,
+                    command: false,
+                });
+            }
+        }
+    }
+
+    let commands = commands::load_manifest(draft_dir)?;
+    for entry in commands.entries.iter().filter(|e| e.status == DraftStatus::Deployed) {
+        let dir = commands::target_dir(commands_dir, entry);
+        let name = deployed_name(&entry.slug);
+        let kind = if !layout::exists(&dir, name) {
+            DriftKind::Missing
+        } else {
+            let expected = entry.deployed_hash.as_deref().unwrap_or(&entry.content_hash);
+            if layout::hash_skill(&dir, name)? == expected {
+                continue;
+            }
+            DriftKind::Modified
+        };
+        drifts.push(SkillDrift {
+            slug: entry.slug.clone(),
+            target: entry.project.clone().unwrap_or_else(|| targets::GLOBAL.to_string()),
+            kind,
+            path: layout::skill_md_path(&dir, name),
+            command: true,
+        });
+    }
+
+    Ok(drifts)
+}
+
+/// Name a sub-manifest draft is deployed under: `<key>/<name>` deploys as `<name>`.
+fn deployed_name(slug: &str) -> &str {
+    slug.rsplit('/').next().unwrap_or(slug)
+}
+
+/// Copy a modified or unknown deployed skill back into the drafts dir, like
+/// `manifest::create_from_directory` does for a whole dir. The entry (created for unknown
+/// skills) is marked deployed to the target with the adopted content, and that content
+/// becomes the merge base for the next deploy.
+pub fn adopt(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &mut Manifest,
+    drift: &SkillDrift,
+) -> Result<(), SkillMinerError> {
+    if drift.kind == DriftKind::Missing {
+        return Err(SkillMinerError::Config(format!(
+            "nothing to adopt: {} is missing",
+            drift.path.display()
+        )));
+    }
+    if drift.command {
+        return Err(SkillMinerError::Config(format!(
+            "slash commands are not adopted: copy {} into the commands drafts by hand",
+            drift.path.display()
+        )));
+    }
+    let dir = targets::dir(skills_dir, &drift.target);
+    let slug = &drift.slug;
+    let content = layout::read_skill_md(&dir, slug)?;
+
+    if layout::is_folder(&dir, slug) {
+        layout::copy_skill(&dir, slug, draft_dir, slug)?;
+    } else {
+        // A legacy file replaces the draft's SKILL.md in whichever layout the draft uses
+        let path = layout::skill_md_path(draft_dir, slug);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, &content)?;
+    }
+
+    if manifest::find_entry(manifest, slug).is_none() {
+        let mut entry = manifest::entry_from_skill(slug, &content);
+        entry.status = DraftStatus::Deployed;
+        if drift.target != targets::GLOBAL {
+            entry.targets = vec![DeployTarget::new(&drift.target)];
+            entry.targets_pinned = true;
+        }
+        manifest.entries.push(entry);
+    }
+    let hash = layout::hash_skill(&dir, slug)?;
+    if let Some(entry) = manifest::find_entry_mut(manifest, slug) {
+        entry.content_hash = hash.clone();
+        targets::mark_deployed(entry, &drift.target, &hash);
+    }
+    merge::save_base(draft_dir, slug, &content)?;
+    manifest::annotate(
+        manifest,
+        slug,
+        Some(AuditAction::Adopted),
+        &format!("adopted from {}", drift.path.display()),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deployer;
+    use crate::types::DraftEntry;
+
+    fn write_draft(dir: &Path, slug: &str, body: &str) {
+        std::fs::create_dir_all(dir.join(slug)).unwrap();
+        std::fs::write(
+            dir.join(slug).join(layout::SKILL_FILE),
+            format!("---\nname: {}\ndescription: d\n---\n\n{}\n", slug, body),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn detect_and_adopt() {
+        let drafts = tempfile::tempdir().unwrap();
+        let skills = tempfile::tempdir().unwrap();
+        write_draft(drafts.path(), "edited", "# Edited");
+        write_draft(drafts.path(), "deleted", "# Deleted");
+        write_draft(drafts.path(), "clean", "# Clean");
+        let mut mf = manifest::create_from_directory(drafts.path()).unwrap();
+        let slugs: Vec<String> = ["edited", "deleted", "clean"].map(String::from).to_vec();
+        deployer::deploy_by_names(drafts.path(), skills.path(), &mut mf, &slugs).unwrap();
+
+        let edited = skills.path().join("edited").join(layout::SKILL_FILE);
+        std::fs::write(&edited, "---\nname: edited\ndescription: d\n---\n\n# Edited by hand\n")
+            .unwrap();
+        layout::remove_skill(skills.path(), "deleted").unwrap();
+        write_draft(skills.path(), "other-tool", "# Other");
+
+        let drifts = detect(drafts.path(), skills.path(), skills.path(), &mf).unwrap();
+        let kinds: Vec<(&str, DriftKind)> =
+            drifts.iter().map(|d| (d.slug.as_str(), d.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("deleted", DriftKind::Missing),
+                ("edited", DriftKind::Modified),
+                ("other-tool", DriftKind::Unknown),
+            ]
+        );
+        assert!(adopt(drafts.path(), skills.path(), &mut mf, &drifts[0]).is_err());
+
+        adopt(drafts.path(), skills.path(), &mut mf, &drifts[1]).unwrap();
+        adopt(drafts.path(), skills.path(), &mut mf, &drifts[2]).unwrap();
+        assert!(layout::read_skill_md(drafts.path(), "edited").unwrap().contains("by hand"));
+        let other = manifest::find_entry(&mf, "other-tool").unwrap();
+        assert_eq!(other.status, DraftStatus::Deployed);
+
+        let drifts = detect(drafts.path(), skills.path(), skills.path(), &mf).unwrap();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].kind, DriftKind::Missing);
+    }
+
+    #[test]
+    fn project_skills_and_commands_are_tracked() {
+        let drafts = tempfile::tempdir().unwrap();
+        let skills = tempfile::tempdir().unwrap();
+        let commands_dir = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        let repo_skills = repo.path().join(".claude").join("skills");
+
+        // A skill targeting the repo, and a project skill deployed next to it
+        write_draft(drafts.path(), "shared", "# Shared");
+        let mut mf = manifest::create_from_directory(drafts.path()).unwrap();
+        targets::set(&mut mf.entries[0], std::slice::from_ref(&repo_path));
+        deployer::deploy_by_names(drafts.path(), skills.path(), &mut mf, &["shared".to_string()])
+            .unwrap();
+        write_draft(&repo_skills, "test-db", "# Test DB");
+        let projects = Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: vec![DraftEntry {
+                slug: "repo/test-db".to_string(),
+                status: DraftStatus::Deployed,
+                project: Some(repo_path.clone()),
+                ..Default::default()
+            }],
+            mined_ids: Default::default(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        };
+        let projects_dir = drafts.path().join(project::PROJECTS_SUBDIR);
+        std::fs::create_dir_all(&projects_dir).unwrap();
+        manifest::write_manifest(&projects_dir, &projects).unwrap();
+
+        // A deployed slash command, then edited by hand
+        let command_drafts = drafts.path().join(commands::COMMANDS_SUBDIR);
+        std::fs::create_dir_all(&command_drafts).unwrap();
+        std::fs::write(command_drafts.join("fmt.md"), "Run cargo fmt.\n").unwrap();
+        let mut cmds = manifest::create_from_directory(&command_drafts).unwrap();
+        cmds.entries[0].status = DraftStatus::Approved;
+        commands::deploy_commands(drafts.path(), commands_dir.path(), &mut cmds, &["fmt".into()])
+            .unwrap();
+        std::fs::write(commands_dir.path().join("fmt.md"), "Run cargo fmt --all.\n").unwrap();
+
+        let drifts = detect(drafts.path(), skills.path(), commands_dir.path(), &mf).unwrap();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!((drifts[0].slug.as_str(), drifts[0].kind), ("fmt", DriftKind::Modified));
+        assert!(drifts[0].command);
+        assert!(adopt(drafts.path(), skills.path(), &mut mf, &drifts[0]).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::SkillMinerError;
 use crate::parser;
+use crate::project;
 use crate::types::{ClassifiedConversation, Conversation, DomainCluster, KnowledgePattern, Role};
 use crate::util;
 use cli_ai_analyzer::{prompt, AnalyzeOptions};
@@ -100,6 +101,9 @@ fn build_extraction_context(
                 &conv.summary.id[..8.min(conv.summary.id.len())],
             );
             // Append tool usage metadata if available
+            if let Some(ref cwd) = conv.summary.cwd {
+                header.push_str(&format!("\ncwd: {}", cwd));
+            }
             if !conv.summary.files_touched.is_empty() {
                 let files: Vec<_> = conv.summary.files_touched.iter().take(MAX_FILES_IN_HEADER).map(|f| f.as_str()).collect();
                 header.push_str(&format!("\nfiles: [{}]", files.join(", ")));
@@ -157,10 +161,9 @@ pub fn extract_patterns(
     let patterns: Vec<PatternEntry> = util::parse_json_response(&response)
         .map_err(|e| SkillMinerError::Parse(e.to_string()))?;
 
-    let source_ids: Vec<String> = conversations.iter().map(|c| c.summary.id.clone()).collect();
     let knowledge_patterns: Vec<KnowledgePattern> = patterns
         .into_iter()
-        .map(|p| p.into_knowledge_pattern(source_ids.clone()))
+        .map(|p| p.into_knowledge_pattern(conversations))
         .collect();
 
     Ok(DomainCluster {
@@ -248,18 +251,41 @@ struct PatternEntry {
     code_examples: Vec<String>,
     #[serde(default = "default_freq")]
     frequency: usize,
+    /// Indices of the conversations (in context order) this pattern came from
+    #[serde(default)]
+    conversations: Vec<usize>,
+    /// Whether the knowledge only applies to the repository it was learned in
+    #[serde(default)]
+    project_specific: bool,
 }
 
 impl PatternEntry {
-    fn into_knowledge_pattern(self, source_ids: Vec<String>) -> KnowledgePattern {
+    /// Resolve source conversations and project scope.
+    /// Without valid indices, all conversations in the group are treated as sources.
+    fn into_knowledge_pattern(self, conversations: &[&ClassifiedConversation]) -> KnowledgePattern {
+        let mut sources: Vec<&ClassifiedConversation> = self
+            .conversations
+            .iter()
+            .filter_map(|&i| conversations.get(i).copied())
+            .collect();
+        if sources.is_empty() {
+            sources = conversations.to_vec();
+        }
+        let project = if self.project_specific {
+            project::infer_project(&sources)
+        } else {
+            None
+        };
+
         KnowledgePattern {
             title: self.title,
             description: self.description,
             steps: self.steps,
             code_examples: self.code_examples,
-            source_ids,
+            source_ids: sources.iter().map(|c| c.summary.id.clone()).collect(),
             frequency: self.frequency,
             skill_slug: self.skill_slug,
+            project,
         }
     }
 }
@@ -0,0 +1,293 @@
+// Forget module: remove a conversation (or every conversation run in a project) from mining
+// The target is deny-listed in the manifest, dropped from the pattern store, and the drafts
+// built from its patterns are regenerated from what remains.
+
+use crate::error::SkillMinerError;
+use crate::project::{self, ProjectDraft};
+use crate::types::{DomainCluster, DraftEntry, Manifest, PatternStore, SkillDraft};
+use crate::{generator, layout, manifest, patterns};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// What to forget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgetTarget {
+    Conversation(String),
+    /// Normalized project path
+    Project(String),
+}
+
+impl ForgetTarget {
+    /// Conversation IDs are bare session IDs; anything that looks like a path is a project.
+    pub fn parse(arg: &str) -> Self {
+        if arg.contains('/') || arg.contains('\\') || Path::new(arg).is_dir() {
+            ForgetTarget::Project(project::normalize_project(arg))
+        } else {
+            ForgetTarget::Conversation(arg.to_string())
+        }
+    }
+}
+
+impl fmt::Display for ForgetTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForgetTarget::Conversation(id) => write!(f, "conversation {}", id),
+            ForgetTarget::Project(path) => write!(f, "project {}", path),
+        }
+    }
+}
+
+/// Outcome of a forget.
+#[derive(Debug, Default)]
+pub struct ForgetReport {
+    /// Conversations removed from `mined_ids`, `pending_extracts` and the pattern store
+    pub conversations: Vec<String>,
+    /// Patterns whose only sources were forgotten
+    pub dropped_patterns: usize,
+    /// Patterns that lost some sources (and frequency)
+    pub trimmed_patterns: usize,
+    /// Drafts rewritten from the remaining patterns
+    pub regenerated: Vec<String>,
+    /// Drafts left without patterns, removed from the drafts dir and manifest
+    pub removed: Vec<DraftEntry>,
+    /// Project drafts (`<project-key>/<name>`) rewritten or removed
+    pub project_regenerated: Vec<String>,
+    pub project_removed: Vec<String>,
+}
+
+/// Drafts generated from a pattern store, split like a mining run.
+struct Generated {
+    clusters: Vec<DomainCluster>,
+    drafts: Vec<SkillDraft>,
+    project_clusters: BTreeMap<String, Vec<DomainCluster>>,
+    project_drafts: Vec<ProjectDraft>,
+}
+
+fn generate(store: &PatternStore) -> Generated {
+    let (clusters, project_clusters) = project::split_clusters(&patterns::clusters(store));
+    Generated {
+        drafts: generator::generate_skills(&clusters),
+        project_drafts: project::generate_project_drafts(&project_clusters),
+        clusters,
+        project_clusters,
+    }
+}
+
+/// Forget a target: deny-list it, drop its conversations and patterns, and regenerate the
+/// affected drafts in `drafts_dir`. The caller writes the manifest (and redeploys).
+pub fn forget(
+    drafts_dir: &Path,
+    manifest: &mut Manifest,
+    target: &ForgetTarget,
+) -> Result<ForgetReport, SkillMinerError> {
+    match target {
+        ForgetTarget::Conversation(id) => manifest.forgotten.ids.insert(id.clone()),
+        ForgetTarget::Project(path) => manifest.forgotten.projects.insert(path.clone()),
+    };
+    let reason = format!("forget {}", target);
+
+    let mut store = patterns::load(drafts_dir)?;
+    let mut ids: HashSet<String> = manifest.forgotten.ids.iter().cloned().collect();
+    ids.extend(
+        store
+            .conversations
+            .iter()
+            .chain(&manifest.pending_extracts)
+            .filter(|c| manifest.forgotten.contains(&c.summary.id, c.summary.cwd.as_deref()))
+            .map(|c| c.summary.id.clone()),
+    );
+
+    let mut report = ForgetReport::default();
+    let mut found: Vec<String> = ids
+        .iter()
+        .filter(|id| {
+            manifest.mined_ids.contains(*id)
+                || manifest.pending_extracts.iter().any(|c| c.summary.id == **id)
+                || store.conversations.iter().any(|c| c.summary.id == **id)
+        })
+        .cloned()
+        .collect();
+    found.sort();
+    report.conversations = found;
+    manifest.mined_ids.retain(|id| !ids.contains(id));
+    manifest.pending_extracts.retain(|c| !ids.contains(&c.summary.id));
+
+    let before = generate(&store);
+    (report.dropped_patterns, report.trimmed_patterns) = patterns::forget(&mut store, &ids);
+    if report.dropped_patterns + report.trimmed_patterns == 0 {
+        if drafts_dir.join(patterns::PATTERNS_FILE).exists() {
+            patterns::save(drafts_dir, &store)?;
+        }
+        return Ok(report);
+    }
+    patterns::save(drafts_dir, &store)?;
+    let after = generate(&store);
+
+    // Global drafts: rewrite the changed ones (dropping stale supporting files), remove the emptied
+    let mut changed = Vec::new();
+    for old in &before.drafts {
+        match after.drafts.iter().find(|d| d.name == old.name) {
+            Some(new) if new.format_md() == old.format_md() && new.files == old.files => {}
+            Some(new) => changed.push(new.clone()),
+            None => {
+                layout::remove_skill(drafts_dir, &old.name)?;
+                if let Some(pos) = manifest.entries.iter().position(|e| e.slug == old.name) {
+                    report.removed.push(manifest.entries.remove(pos));
+                    manifest::annotate(manifest, &old.name, None, &reason);
+                }
+            }
+        }
+    }
+    for draft in &changed {
+        layout::remove_skill(drafts_dir, &draft.name)?;
+        layout::write_skill(drafts_dir, &draft.name, draft)?;
+        manifest::annotate(manifest, &draft.name, None, &reason);
+    }
+    manifest::merge_drafts(manifest, &changed, &after.clusters);
+    report.regenerated = changed.into_iter().map(|d| d.name).collect();
+
+    // Project drafts live in their own manifest under `projects/`
+    let mut changed = Vec::new();
+    let mut removed = Vec::new();
+    for old in &before.project_drafts {
+        match after.project_drafts.iter().find(|d| d.slug == old.slug) {
+            Some(new) if new.draft.format_md() == old.draft.format_md() => {}
+            Some(new) => changed.push(new.clone()),
+            None => removed.push(old.slug.clone()),
+        }
+    }
+    let project_dir = drafts_dir.join(project::PROJECTS_SUBDIR);
+    for pd in &changed {
+        layout::remove_skill(&project_dir, &pd.slug)?;
+    }
+    if !changed.is_empty() {
+        project::write_project_drafts(drafts_dir, &changed, &after.project_clusters)?;
+    }
+    if !removed.is_empty() {
+        let mut project_mf = project::load_manifest(drafts_dir)?;
+        for slug in &removed {
+            layout::remove_skill(&project_dir, slug)?;
+            project_mf.entries.retain(|e| e.slug != *slug);
+            manifest::annotate(&mut project_mf, slug, None, &reason);
+        }
+        manifest::write_manifest(&project_dir, &project_mf)?;
+    }
+    report.project_regenerated = changed.into_iter().map(|pd| pd.slug).collect();
+    report.project_removed = removed;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        ClassifiedConversation, ConversationSummary, DraftStatus, ForgetList, KnowledgePattern,
+    };
+    use std::path::PathBuf;
+
+    fn conv(id: &str, cwd: &str) -> ClassifiedConversation {
+        ClassifiedConversation {
+            summary: ConversationSummary {
+                id: id.to_string(),
+                source_path: PathBuf::from(format!("{}.jsonl", id)),
+                first_message: "hello".to_string(),
+                message_count: 4,
+                start_time: None,
+                cwd: Some(cwd.to_string()),
+                topics: vec![],
+                tools_used: vec![],
+                files_touched: vec![],
+                commands_used: vec![],
+            },
+            domain: "Rust".to_string(),
+            slug: "rust".to_string(),
+            tags: vec![],
+            confidence: 0.9,
+        }
+    }
+
+    fn pattern(title: &str, slug: &str, sources: &[&str]) -> KnowledgePattern {
+        KnowledgePattern {
+            title: title.to_string(),
+            description: format!("{} description", title),
+            steps: vec!["step".to_string()],
+            code_examples: vec![],
+            source_ids: sources.iter().map(|s| s.to_string()).collect(),
+            frequency: sources.len(),
+            skill_slug: Some(slug.to_string()),
+            project: None,
+        }
+    }
+
+    /// Store with two conversations, one draft built from both and one from c1 alone.
+    fn setup(dir: &Path) -> Manifest {
+        let cluster = DomainCluster {
+            domain: "Rust".to_string(),
+            conversations: vec![conv("c1", "/work/client"), conv("c2", "/work/oss")],
+            patterns: vec![
+                pattern("Shared", "rust-shared", &["c1", "c2"]),
+                pattern("Secret", "rust-secret", &["c1"]),
+            ],
+        };
+        patterns::record(dir, std::slice::from_ref(&cluster)).unwrap();
+        let drafts = generator::generate_skills(std::slice::from_ref(&cluster));
+        for draft in &drafts {
+            layout::write_skill(dir, &draft.name, draft).unwrap();
+        }
+        let mut mf = manifest::create_from_drafts(&drafts, &[cluster], dir);
+        mf.mined_ids = ["c1".to_string(), "c2".to_string()].into();
+        mf.entries.iter_mut().for_each(|e| e.status = DraftStatus::Deployed);
+        mf
+    }
+
+    #[test]
+    fn parse_target() {
+        assert_eq!(
+            ForgetTarget::parse("3f2a-11"),
+            ForgetTarget::Conversation("3f2a-11".to_string())
+        );
+        assert_eq!(
+            ForgetTarget::parse("/work/client/"),
+            ForgetTarget::Project("/work/client".to_string())
+        );
+    }
+
+    #[test]
+    fn forget_conversation_regenerates_and_removes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mf = setup(dir.path());
+        let report =
+            forget(dir.path(), &mut mf, &ForgetTarget::Conversation("c1".to_string())).unwrap();
+
+        assert_eq!(report.conversations, vec!["c1"]);
+        assert_eq!((report.dropped_patterns, report.trimmed_patterns), (1, 1));
+        assert_eq!(report.regenerated, vec!["rust-shared"]);
+        assert_eq!(report.removed[0].slug, "rust-secret");
+        assert!(!layout::exists(dir.path(), "rust-secret"));
+        assert_eq!(mf.entries.len(), 1);
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+        assert!(!mf.mined_ids.contains("c1"));
+        assert!(mf.forgotten.ids.contains("c1"));
+
+        let store = patterns::load(dir.path()).unwrap();
+        assert_eq!(store.conversations.len(), 1);
+        assert_eq!(store.patterns[0].pattern.source_ids, vec!["c2"]);
+    }
+
+    #[test]
+    fn forget_project_matches_by_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mf = setup(dir.path());
+        let target = ForgetTarget::parse("/work/client");
+        let report = forget(dir.path(), &mut mf, &target).unwrap();
+        assert_eq!(report.conversations, vec!["c1"]);
+        assert!(mf.mined_ids.contains("c2"));
+
+        let list = &mf.forgotten;
+        assert!(list.contains("new", Some("/work/client/sub")));
+        assert!(!list.contains("new", Some("/work/client-other")));
+        assert!(ForgetList::default().is_empty());
+    }
+}
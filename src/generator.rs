@@ -1,8 +1,26 @@
 use crate::domains;
 use crate::error::SkillMinerError;
-use crate::types::{DomainCluster, SkillDraft};
+use crate::template::{self, Value};
+use crate::types::{ConversationSummary, DomainCluster, SkillDraft, SkillFile};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
+/// Code examples longer than this many lines move into supporting files.
+const LONG_EXAMPLE_LINES: usize = 20;
+
+/// Orchestration tools that never go into `allowed-tools`.
+const IGNORED_TOOLS: &[&str] = &["AskUserQuestion", "ExitPlanMode", "Skill", "Task", "TodoWrite"];
+
+/// Programs whose first argument is a subcommand, so the Bash rule keeps both words.
+const SUBCOMMAND_PROGRAMS: &[&str] = &[
+    "bun", "cargo", "deno", "docker", "dotnet", "gh", "git", "go", "gradle", "helm", "just",
+    "kubectl", "make", "mvn", "npm", "pip", "pnpm", "poetry", "rustup", "terraform", "uv",
+    "yarn",
+];
+
+/// Scored patterns below this score are left out of rebuilt descriptions and bodies.
+pub const MIN_PATTERN_SCORE: f64 = 0.05;
+
 /// Generate skill drafts from domain clusters
 pub fn generate_skills(clusters: &[DomainCluster]) -> Vec<SkillDraft> {
     clusters.iter().flat_map(generate_from_cluster).collect()
@@ -32,13 +50,19 @@ fn generate_from_cluster(cluster: &DomainCluster) -> Vec<SkillDraft> {
         .map(|(slug, patterns)| {
             let domain_name = &cluster.domain;
             let description = build_description_for_group(domain_name, &patterns);
-            let body = build_body_for_group(domain_name, &patterns);
+            let (body, files) = externalize_examples(&build_body_for_group(domain_name, &patterns));
             let sources: Vec<String> = patterns
                 .iter()
                 .flat_map(|p| p.source_ids.clone())
                 .collect::<std::collections::HashSet<_>>()
                 .into_iter()
                 .collect();
+            let summaries: Vec<&ConversationSummary> = cluster
+                .conversations
+                .iter()
+                .map(|c| &c.summary)
+                .filter(|s| sources.contains(&s.id))
+                .collect();
 
             SkillDraft {
                 name: slug,
@@ -47,6 +71,8 @@ fn generate_from_cluster(cluster: &DomainCluster) -> Vec<SkillDraft> {
                 sources,
                 existing_skill: None,
                 diff: None,
+                files,
+                allowed_tools: allowed_tools(&summaries),
             }
         })
         .collect()
@@ -87,58 +113,69 @@ fn build_body_for_group(
     domain_name: &str,
     patterns: &[&crate::types::KnowledgePattern],
 ) -> String {
-    let mut body = format!("# {}\n\n", domain_name);
-
-    body.push_str(&format!("Patterns: {}\n\n", patterns.len()));
-
-    for (i, pattern) in patterns.iter().enumerate() {
-        render_pattern(&mut body, i + 1, pattern, None);
-    }
-
-    body
+    let mut context = body_context(domain_name, patterns.len(), None);
+    let items = patterns.iter().map(|p| pattern_value(p, None)).collect();
+    context.insert("patterns".to_string(), Value::List(items));
+    template::body_template().render(&context)
 }
 
-/// Render a single pattern entry into a body string.
-/// If `score` is provided, it's appended alongside frequency.
-fn render_pattern(
-    body: &mut String,
-    number: usize,
-    pattern: &crate::types::KnowledgePattern,
-    score: Option<f64>,
-) {
-    body.push_str(&format!("## {}. {}\n\n", number, pattern.title));
-    body.push_str(&format!("{}\n\n", pattern.description));
-
-    if !pattern.steps.is_empty() {
-        body.push_str("### Steps\n\n");
-        for (j, step) in pattern.steps.iter().enumerate() {
-            body.push_str(&format!("{}. {}\n", j + 1, step));
-        }
-        body.push_str("\n");
-    }
+/// Top-level template variables. `conversation_count` is only set for scored bodies.
+fn body_context(
+    domain_name: &str,
+    pattern_count: usize,
+    conversation_count: Option<usize>,
+) -> BTreeMap<String, Value> {
+    let mut context = BTreeMap::new();
+    context.insert("domain".to_string(), domain_name.into());
+    context.insert("pattern_count".to_string(), pattern_count.into());
+    context.insert("scored".to_string(), conversation_count.is_some().into());
+    context.insert(
+        "conversation_count".to_string(),
+        conversation_count.map(Value::from).unwrap_or_else(|| "".into()),
+    );
+    context
+}
 
-    if !pattern.code_examples.is_empty() {
-        body.push_str("### Examples\n\n");
-        for example in &pattern.code_examples {
-            // If the example already has fences, use as-is; otherwise wrap it
+/// Template variables for a single pattern.
+/// If `score` is provided, it's rendered alongside frequency by the default template.
+fn pattern_value(pattern: &crate::types::KnowledgePattern, score: Option<f64>) -> Value {
+    // Examples are always fenced; the trailing newline is left to the template
+    let examples = pattern
+        .code_examples
+        .iter()
+        .map(|example| {
             if example.starts_with("```") {
-                body.push_str(example);
-                if !example.ends_with('\n') {
-                    body.push('\n');
-                }
+                example.strip_suffix('\n').unwrap_or(example).to_string()
             } else {
-                body.push_str(&format!("```\n{}\n```\n", example));
+                format!("```\n{}\n```", example)
             }
-            body.push('\n');
-        }
-    }
+        })
+        .map(Value::Str)
+        .collect();
 
-    match (pattern.frequency > 1, score) {
-        (true, Some(s)) => body.push_str(&format!("Frequency: {} | Score: {:.2}\n\n", pattern.frequency, s)),
-        (true, None) => body.push_str(&format!("Frequency: {}\n\n", pattern.frequency)),
-        (false, Some(s)) => body.push_str(&format!("Score: {:.2}\n\n", s)),
-        (false, None) => body.push('\n'),
-    }
+    let mut map = BTreeMap::new();
+    map.insert("title".to_string(), pattern.title.as_str().into());
+    map.insert("description".to_string(), pattern.description.as_str().into());
+    map.insert(
+        "steps".to_string(),
+        Value::List(pattern.steps.iter().map(|s| s.as_str().into()).collect()),
+    );
+    map.insert("examples".to_string(), Value::List(examples));
+    map.insert(
+        "sources".to_string(),
+        Value::List(pattern.source_ids.iter().map(|s| s.as_str().into()).collect()),
+    );
+    map.insert("frequency".to_string(), pattern.frequency.into());
+    map.insert("repeated".to_string(), (pattern.frequency > 1).into());
+    map.insert(
+        "score".to_string(),
+        score.map(|s| format!("{:.2}", s)).unwrap_or_default().into(),
+    );
+    map.insert(
+        "project".to_string(),
+        pattern.project.clone().unwrap_or_default().into(),
+    );
+    Value::Map(map)
 }
 
 /// Rebuild description using scored patterns (sorted by score desc).
@@ -154,7 +191,7 @@ pub fn rebuild_description_scored(
 
     let pattern_summaries: Vec<&str> = scored_patterns
         .iter()
-        .filter(|(_, score)| *score >= 0.05)
+        .filter(|(_, score)| *score >= MIN_PATTERN_SCORE)
         .take(max_patterns)
         .filter_map(|(idx, _)| cluster.patterns.get(*idx).map(|p| p.title.as_str()))
         .collect();
@@ -175,7 +212,8 @@ pub fn rebuild_description_scored(
 }
 
 /// Rebuild body using scored patterns (sorted by score desc).
-/// Includes score display alongside frequency. Filters out patterns with score < 0.05.
+/// Includes score display alongside frequency. Filters out patterns with score < 0.05
+/// (`scorer::score_patterns` gives 0 to sections nobody followed).
 /// Falls back to `build_body` if empty.
 pub fn rebuild_body_scored(
     cluster: &DomainCluster,
@@ -187,7 +225,7 @@ pub fn rebuild_body_scored(
 
     let filtered: Vec<(usize, f64)> = scored_patterns
         .iter()
-        .filter(|(_, score)| *score >= 0.05)
+        .filter(|(_, score)| *score >= MIN_PATTERN_SCORE)
         .copied()
         .collect();
 
@@ -195,24 +233,188 @@ pub fn rebuild_body_scored(
         return build_body(cluster);
     }
 
-    let mut body = format!("# {}\n\n", cluster.domain);
+    let mut context = body_context(&cluster.domain, filtered.len(), Some(cluster.conversations.len()));
+    let items = filtered
+        .iter()
+        .filter_map(|(idx, score)| cluster.patterns.get(*idx).map(|p| pattern_value(p, Some(*score))))
+        .collect();
+    context.insert("patterns".to_string(), Value::List(items));
+    template::body_template().render(&context)
+}
+
+/// Move long fenced code blocks into `examples/<pattern>-<n>.<ext>` supporting files
+/// and replace them with a link line in the body.
+pub fn externalize_examples(body: &str) -> (String, Vec<SkillFile>) {
+    let lines: Vec<&str> = body.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut files = Vec::new();
+    let mut pattern = 0;
+    let mut counter = 0;
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(n) = pattern_number(line) {
+            pattern = n;
+            counter = 0;
+        }
+        if let Some(lang) = line.trim_start().strip_prefix("```") {
+            let close = lines[i + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with("```"));
+            if let Some(close) = close {
+                let code = &lines[i + 1..i + 1 + close];
+                if code.len() > LONG_EXAMPLE_LINES {
+                    counter += 1;
+                    let path = format!("examples/{}-{}.{}", pattern, counter, extension_for(lang.trim()));
+                    out.push(format!("See [{}]({}) ({} lines)", path, path, code.len()));
+                    files.push(SkillFile {
+                        path,
+                        content: format!("{}\n", code.join("\n")),
+                    });
+                } else {
+                    out.extend(lines[i..=i + 1 + close].iter().map(|l| l.to_string()));
+                }
+                i += close + 2;
+                continue;
+            }
+        }
+        out.push(line.to_string());
+        i += 1;
+    }
 
-    body.push_str(&format!(
-        "Conversations: {} | Patterns: {}\n\n",
-        cluster.conversations.len(),
-        filtered.len()
-    ));
+    let mut result = out.join("\n");
+    if body.ends_with('\n') {
+        result.push('\n');
+    }
+    (result, files)
+}
 
-    for (i, (idx, score)) in filtered.iter().enumerate() {
-        if let Some(pattern) = cluster.patterns.get(*idx) {
-            render_pattern(&mut body, i + 1, pattern, Some(*score));
+/// Reverse of `externalize_examples`: put supporting file contents back inline as fenced blocks.
+/// Used where a single markdown document is needed (e.g. a CLAUDE.md section).
+pub fn inline_examples(body: &str, files: &[SkillFile]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for line in body.lines() {
+        let file = files.iter().find(|f| {
+            line.starts_with(&format!("See [{}]({})", f.path, f.path))
+        });
+        match file {
+            Some(f) => {
+                let ext = f.path.rsplit('.').next().unwrap_or_default();
+                out.push(format!("```{}", language_for(ext)));
+                out.push(f.content.trim_end_matches('\n').to_string());
+                out.push("```".to_string());
+            }
+            None => out.push(line.to_string()),
         }
     }
+    let mut result = out.join("\n");
+    if body.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Parse the number from a `## N. Title` pattern heading.
+fn pattern_number(line: &str) -> Option<usize> {
+    line.strip_prefix("## ")?.split('.').next()?.trim().parse().ok()
+}
 
-    body
+const FENCE_EXTENSIONS: &[(&str, &str)] = &[
+    ("rust", "rs"),
+    ("bash", "sh"),
+    ("sh", "sh"),
+    ("shell", "sh"),
+    ("python", "py"),
+    ("py", "py"),
+    ("javascript", "js"),
+    ("js", "js"),
+    ("typescript", "ts"),
+    ("ts", "ts"),
+    ("json", "json"),
+    ("toml", "toml"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("powershell", "ps1"),
+    ("sql", "sql"),
+    ("go", "go"),
+];
+
+pub(crate) fn extension_for(lang: &str) -> &'static str {
+    let lang = lang.to_lowercase();
+    FENCE_EXTENSIONS
+        .iter()
+        .find(|(l, _)| *l == lang)
+        .map(|(_, ext)| *ext)
+        .unwrap_or("txt")
+}
+
+fn language_for(ext: &str) -> &'static str {
+    FENCE_EXTENSIONS
+        .iter()
+        .find(|(_, e)| *e == ext)
+        .map(|(l, _)| *l)
+        .unwrap_or("")
 }
 
 /// Format a skill draft as a complete .md file.
+/// Least-privilege `allowed-tools` rules for the tools used in the given conversations.
+/// Bash is narrowed to the command prefixes actually run (`Bash(cargo test:*)`); it stays
+/// unrestricted only when no command was recorded. Tool names come first, then Bash rules.
+pub fn allowed_tools(summaries: &[&ConversationSummary]) -> Vec<String> {
+    let mut tools: BTreeSet<String> = BTreeSet::new();
+    let mut bash: BTreeSet<String> = BTreeSet::new();
+    let mut uses_bash = false;
+    for summary in summaries {
+        for tool in &summary.tools_used {
+            if tool == "Bash" {
+                uses_bash = true;
+            } else if !IGNORED_TOOLS.contains(&tool.as_str()) {
+                tools.insert(tool.clone());
+            }
+        }
+        for cmd in &summary.commands_used {
+            uses_bash = true;
+            bash.extend(bash_prefixes(cmd).into_iter().map(|p| format!("Bash({}:*)", p)));
+        }
+    }
+    if uses_bash && bash.is_empty() {
+        tools.insert("Bash".to_string());
+    }
+    tools.into_iter().chain(bash).collect()
+}
+
+/// Permission prefixes of a Bash command line, one per `&&`/`||`/`;`/`|` segment:
+/// the program, plus its subcommand for tools like git and cargo. `cd` is skipped.
+pub fn bash_prefixes(cmd: &str) -> Vec<String> {
+    let line = cmd.lines().next().unwrap_or_default();
+    let plain = |w: &str| {
+        !w.is_empty()
+            && !w.starts_with('-')
+            && w.chars().all(|c| c.is_ascii_alphanumeric() || "._-/:".contains(c))
+    };
+    let mut prefixes: Vec<String> = Vec::new();
+    for segment in line.replace("&&", ";").replace("||", ";").replace('|', ";").split(';') {
+        let mut words = segment.split_whitespace().skip_while(|w| w.contains('='));
+        let Some(program) = words.next().filter(|w| plain(w)) else {
+            continue;
+        };
+        if program == "cd" {
+            continue;
+        }
+        let prefix = match words.next() {
+            Some(sub) if SUBCOMMAND_PROGRAMS.contains(&program) && plain(sub) && !sub.contains(['/', '.']) => {
+                format!("{} {}", program, sub)
+            }
+            _ => program.to_string(),
+        };
+        if !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    prefixes
+}
+
 /// Delegates to `SkillDraft::format_md()`.
 pub fn format_skill_md(draft: &SkillDraft) -> String {
     draft.format_md()
@@ -252,6 +454,7 @@ mod tests {
                     frequency: 10,
                     code_examples: vec![],
                     skill_slug: None,
+                    project: None,
                 },
                 KnowledgePattern {
                     title: "Low score".to_string(),
@@ -261,6 +464,7 @@ mod tests {
                     frequency: 1,
                     code_examples: vec![],
                     skill_slug: None,
+                    project: None,
                 },
                 KnowledgePattern {
                     title: "Zero score".to_string(),
@@ -270,6 +474,7 @@ mod tests {
                     frequency: 0,
                     code_examples: vec![],
                     skill_slug: None,
+                    project: None,
                 },
             ],
         };
@@ -294,6 +499,7 @@ mod tests {
                     frequency: 2,
                     code_examples: vec![],
                     skill_slug: None,
+                    project: None,
                 },
                 KnowledgePattern {
                     title: "First".to_string(),
@@ -303,6 +509,7 @@ mod tests {
                     frequency: 5,
                     code_examples: vec![],
                     skill_slug: None,
+                    project: None,
                 },
             ],
         };
@@ -328,9 +535,112 @@ mod tests {
                 source_ids: vec![],
                 frequency: 1,
                 skill_slug: None,
+                project: None,
             }],
         };
         let desc = rebuild_description_scored(&cluster, &[], 5);
         assert!(desc.contains("Pattern")); // fallback to build_description
     }
+
+    #[test]
+    fn test_externalize_examples_moves_long_blocks() {
+        let long: Vec<String> = (0..25).map(|i| format!("let x{} = {};", i, i)).collect();
+        let body = format!(
+            "# Domain\n\n## 2. Pattern\n\n```rust\n{}\n```\n\n```bash\necho short\n```\n",
+            long.join("\n")
+        );
+        let (out, files) = externalize_examples(&body);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "examples/2-1.rs");
+        assert!(files[0].content.starts_with("let x0 = 0;"));
+        assert!(out.contains("See [examples/2-1.rs](examples/2-1.rs) (25 lines)"));
+        assert!(out.contains("echo short"));
+        assert!(!out.contains("let x0"));
+    }
+
+    #[test]
+    fn test_inline_examples_roundtrip() {
+        let long: Vec<String> = (0..30).map(|i| format!("echo {}", i)).collect();
+        let body = format!("## 1. P\n\n```bash\n{}\n```\n", long.join("\n"));
+        let (out, files) = externalize_examples(&body);
+        assert_eq!(files[0].path, "examples/1-1.sh");
+        assert_eq!(inline_examples(&out, &files), body);
+    }
+
+    #[test]
+    fn test_default_template_layout() {
+        let cluster = DomainCluster {
+            domain: "Rust".to_string(),
+            conversations: vec![],
+            patterns: vec![
+                KnowledgePattern {
+                    title: "First".to_string(),
+                    description: "desc one".to_string(),
+                    steps: vec!["a".to_string(), "b".to_string()],
+                    code_examples: vec!["let x = 1;".to_string(), "```rust\nfn f() {}\n```\n".to_string()],
+                    source_ids: vec![],
+                    frequency: 3,
+                    skill_slug: None,
+                    project: None,
+                },
+                KnowledgePattern {
+                    title: "Second".to_string(),
+                    description: "desc two".to_string(),
+                    steps: vec![],
+                    code_examples: vec![],
+                    source_ids: vec![],
+                    frequency: 1,
+                    skill_slug: None,
+                    project: None,
+                },
+            ],
+        };
+        let expected = "# Rust\n\nPatterns: 2\n\n\
+## 1. First\n\ndesc one\n\n### Steps\n\n1. a\n2. b\n\n\
+### Examples\n\n```\nlet x = 1;\n```\n\n```rust\nfn f() {}\n```\n\nFrequency: 3\n\n\
+## 2. Second\n\ndesc two\n\n\n";
+        assert_eq!(build_body(&cluster), expected);
+
+        let scored = rebuild_body_scored(&cluster, &[(1, 0.5), (0, 0.25)]);
+        assert!(scored.starts_with("# Rust\n\nConversations: 0 | Patterns: 2\n\n## 1. Second\n\ndesc two\n\nScore: 0.50\n\n"));
+        assert!(scored.ends_with("Frequency: 3 | Score: 0.25\n\n"));
+    }
+
+    #[test]
+    fn test_bash_prefixes() {
+        assert_eq!(bash_prefixes("cd /repo && cargo test --lib"), vec!["cargo test"]);
+        assert_eq!(
+            bash_prefixes("RUST_LOG=debug cargo run | grep foo; git -C x status"),
+            vec!["cargo run", "grep", "git"]
+        );
+        assert_eq!(bash_prefixes("python ./scripts/gen.py"), vec!["python"]);
+        assert!(bash_prefixes("$(date)").is_empty());
+    }
+
+    #[test]
+    fn test_allowed_tools_least_privilege() {
+        let summary = |id: &str, tools: &[&str], commands: &[&str]| ConversationSummary {
+            id: id.to_string(),
+            source_path: std::path::PathBuf::from("/tmp/c.jsonl"),
+            first_message: String::new(),
+            message_count: 4,
+            start_time: None,
+            cwd: None,
+            topics: vec![],
+            tools_used: tools.iter().map(|t| t.to_string()).collect(),
+            files_touched: vec![],
+            commands_used: commands.iter().map(|c| c.to_string()).collect(),
+        };
+        let a = summary("a", &["Read", "Bash", "TodoWrite"], &["cargo test", "git status"]);
+        let b = summary("b", &["Edit", "Read", "Bash"], &["cargo test --release"]);
+        assert_eq!(
+            allowed_tools(&[&a, &b]),
+            vec!["Edit", "Read", "Bash(cargo test:*)", "Bash(git status:*)"]
+        );
+
+        // Bash without recorded commands stays unrestricted
+        let c = summary("c", &["Bash"], &[]);
+        assert_eq!(allowed_tools(&[&c]), vec!["Bash"]);
+        assert!(allowed_tools(&[]).is_empty());
+    }
 }
@@ -325,7 +325,12 @@ pub fn build_graph_from_contents(
         let raw_refs = extract_refs(content);
 
         for raw in &raw_refs {
-            let resolved = resolve_ref(base_dir, Some(skills_dir), &raw);
+            let mut resolved = resolve_ref(base_dir, Some(skills_dir), &raw);
+            if raw.ref_type != DepType::ProjectPath && !known_files.contains(&resolved) {
+                if let Some(folder) = folder_skill_key(&resolved, &known_files) {
+                    resolved = folder;
+                }
+            }
 
             all_deps.push(SkillDependency {
                 from: file_key.clone(),
@@ -398,6 +403,13 @@ pub fn build_graph_from_contents(
     }
 }
 
+/// Map `name` / `name.md` to a known `name/SKILL.md` (folder-based skill), if any.
+fn folder_skill_key(resolved: &str, known_files: &HashSet<String>) -> Option<String> {
+    let stem = resolved.strip_suffix(".md").unwrap_or(resolved);
+    let candidate = format!("{}/{}", stem.trim_end_matches('/'), crate::layout::SKILL_FILE);
+    known_files.contains(&candidate).then_some(candidate)
+}
+
 /// Recursively collect .md files from a directory.
 fn collect_md_files_recursive(dir: &Path, out: &mut Vec<PathBuf>) {
     let entries = match std::fs::read_dir(dir) {
@@ -714,4 +726,34 @@ Project at ~/tonsuu-checker/ for reference.
         // orphan detected
         assert!(graph.orphans.iter().any(|o| o.contains("orphan")));
     }
+
+    #[test]
+    fn test_build_graph_resolves_folder_skills() {
+        let skills_dir = Path::new("/test/skills");
+
+        let mut contents = HashMap::new();
+        contents.insert(
+            PathBuf::from("/test/skills/skill-a/SKILL.md"),
+            "# Skill A\nSee [reference](references/api.md)\n".to_string(),
+        );
+        contents.insert(
+            PathBuf::from("/test/skills/skill-a/references/api.md"),
+            "# API\nUsed by skill `skill-b`\n".to_string(),
+        );
+        contents.insert(
+            PathBuf::from("/test/skills/skill-b/SKILL.md"),
+            "# Skill B\nBuilds on skill `skill-a`\n".to_string(),
+        );
+
+        let graph = build_graph_from_contents(&contents, skills_dir);
+
+        assert!(graph.broken_links.is_empty(), "unexpected broken links: {:?}", graph.broken_links);
+        let skill_b = graph
+            .nodes
+            .iter()
+            .find(|n| n.path.ends_with("skill-b/SKILL.md"))
+            .unwrap();
+        assert_eq!(skill_b.incoming.len(), 1);
+        assert!(skill_b.outgoing.iter().any(|d| d.to.ends_with("skill-a/SKILL.md")));
+    }
 }
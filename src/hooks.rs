@@ -0,0 +1,644 @@
+// Hooks module: PostToolUse hook suggestions from tool-then-command habits
+// Finds Bash commands that are run by hand after Edit/Write and proposes `settings.json` hooks.
+// Suggestions are drafts under `<drafts>/hooks/` with their own manifest; deploying merges them
+// into `~/.claude/settings.json` (or `<repo>/.claude/settings.json`) without touching other keys.
+
+use crate::commands;
+use crate::error::SkillMinerError;
+use crate::manifest;
+use crate::project;
+use crate::transaction::{self, Transaction};
+use crate::types::{Conversation, DeployResult, DraftEntry, DraftStatus, Manifest};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Subdirectory of the drafts dir holding hook drafts and their manifest.
+pub const HOOKS_SUBDIR: &str = "hooks";
+
+/// Domain recorded in manifest entries for hook drafts.
+pub const HOOK_DOMAIN: &str = "hook";
+
+/// Hook event used for post-edit commands.
+pub const POST_TOOL_USE: &str = "PostToolUse";
+
+/// Tools that modify files, in matcher order.
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// A proposed hook: run `command` after `matcher` tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookSuggestion {
+    pub event: String,
+    /// Tool matcher, e.g. `Edit|Write`
+    pub matcher: String,
+    pub command: String,
+    /// Conversations in which the command followed an edit
+    pub support: usize,
+    /// Edit runs followed by the command
+    pub occurrences: usize,
+    /// Share of edit runs (in supporting conversations) followed by the command
+    pub ratio: f64,
+    /// Project root for project hooks (None = global settings)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl HookSuggestion {
+    /// The `settings.json` entry this suggestion adds under `hooks.<event>`.
+    pub fn settings_entry(&self) -> Value {
+        json!({
+            "matcher": self.matcher,
+            "hooks": [{ "type": "command", "command": self.command }]
+        })
+    }
+}
+
+/// A hook suggestion with its manifest slug.
+#[derive(Debug, Clone)]
+pub struct HookDraft {
+    /// `post-edit-<command>` for global hooks, `<project-key>/post-edit-<command>` for project hooks
+    pub slug: String,
+    pub suggestion: HookSuggestion,
+}
+
+#[derive(Default)]
+struct Habit {
+    conversations: BTreeSet<String>,
+    occurrences: usize,
+    tools: BTreeSet<String>,
+}
+
+/// Edit runs of a conversation, each with its edit tools and the distinct commands
+/// run after it (before the next edit).
+fn edit_runs(conv: &Conversation) -> Vec<(BTreeSet<String>, Vec<String>)> {
+    let mut runs: Vec<(BTreeSet<String>, Vec<String>)> = Vec::new();
+    let mut in_edit = false;
+    for tu in conv.messages.iter().flat_map(|m| &m.tool_uses) {
+        if EDIT_TOOLS.contains(&tu.name.as_str()) {
+            if !in_edit {
+                runs.push((BTreeSet::new(), Vec::new()));
+                in_edit = true;
+            }
+            if let Some(run) = runs.last_mut() {
+                run.0.insert(tu.name.clone());
+            }
+            continue;
+        }
+        in_edit = false;
+        if tu.name != "Bash" {
+            continue;
+        }
+        let cmd = tu.command.as_deref().and_then(commands::normalize_command);
+        if let (Some(cmd), Some(run)) = (cmd, runs.last_mut()) {
+            if !run.1.contains(&cmd) {
+                run.1.push(cmd);
+            }
+        }
+    }
+    runs
+}
+
+fn matcher_for(tools: &BTreeSet<String>) -> String {
+    EDIT_TOOLS
+        .iter()
+        .filter(|t| tools.contains(**t))
+        .copied()
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Find commands that follow edits in at least `min_support` conversations and after
+/// at least `min_ratio` of the edit runs there. Commands seen in two or more projects
+/// become global hooks; others are scoped to their project.
+pub fn mine_hooks(
+    conversations: &[Conversation],
+    min_support: usize,
+    min_ratio: f64,
+) -> Vec<HookSuggestion> {
+    // command -> project ("" = unknown cwd) -> habit
+    let mut habits: BTreeMap<String, BTreeMap<String, Habit>> = BTreeMap::new();
+    let mut runs_per_conv: BTreeMap<String, usize> = BTreeMap::new();
+
+    for conv in conversations {
+        let project = conv.cwd.as_deref().map(project::normalize_project).unwrap_or_default();
+        let runs = edit_runs(conv);
+        runs_per_conv.insert(conv.id.clone(), runs.len());
+        for (tools, cmds) in runs {
+            for cmd in cmds {
+                let habit = habits.entry(cmd).or_default().entry(project.clone()).or_default();
+                habit.conversations.insert(conv.id.clone());
+                habit.occurrences += 1;
+                habit.tools.extend(tools.iter().cloned());
+            }
+        }
+    }
+
+    let mut suggestions = Vec::new();
+    for (command, by_project) in habits {
+        let global = by_project.len() >= 2 || by_project.contains_key("");
+        let scopes: Vec<(Option<String>, Vec<&Habit>)> = if global {
+            vec![(None, by_project.values().collect())]
+        } else {
+            by_project
+                .iter()
+                .map(|(p, h)| (Some(p.clone()), vec![h]))
+                .collect()
+        };
+        for (project, group) in scopes {
+            let convs: BTreeSet<&String> = group.iter().flat_map(|h| &h.conversations).collect();
+            let occurrences: usize = group.iter().map(|h| h.occurrences).sum();
+            let runs: usize = convs.iter().filter_map(|c| runs_per_conv.get(*c)).sum();
+            let ratio = if runs == 0 { 0.0 } else { occurrences as f64 / runs as f64 };
+            if convs.len() < min_support || ratio < min_ratio {
+                continue;
+            }
+            let tools: BTreeSet<String> = group.iter().flat_map(|h| h.tools.iter().cloned()).collect();
+            suggestions.push(HookSuggestion {
+                event: POST_TOOL_USE.to_string(),
+                matcher: matcher_for(&tools),
+                command: command.clone(),
+                support: convs.len(),
+                occurrences,
+                ratio,
+                project,
+            });
+        }
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.support
+            .cmp(&a.support)
+            .then(b.ratio.total_cmp(&a.ratio))
+            .then(a.command.cmp(&b.command))
+    });
+    suggestions
+}
+
+/// Give suggestions unique slugs.
+pub fn generate_drafts(suggestions: &[HookSuggestion]) -> Vec<HookDraft> {
+    let mut used: HashSet<String> = HashSet::new();
+    suggestions
+        .iter()
+        .map(|s| {
+            let prefix = s
+                .project
+                .as_deref()
+                .map(|p| format!("{}/", project::project_key(p)))
+                .unwrap_or_default();
+            let base = format!("post-edit-{}", commands::command_name(std::slice::from_ref(&s.command)));
+            let mut slug = format!("{}{}", prefix, base);
+            let mut n = 2;
+            while !used.insert(slug.clone()) {
+                slug = format!("{}{}-{}", prefix, base, n);
+                n += 1;
+            }
+            HookDraft {
+                slug,
+                suggestion: s.clone(),
+            }
+        })
+        .collect()
+}
+
+fn draft_path(drafts_dir: &Path, slug: &str) -> PathBuf {
+    drafts_dir.join(HOOKS_SUBDIR).join(format!("{}.json", slug))
+}
+
+/// Load the hook manifest, or an empty one if none exists yet.
+pub fn load_manifest(drafts_dir: &Path) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(HOOKS_SUBDIR);
+    if dir.join("manifest.toml").exists() {
+        manifest::read_manifest(&dir)
+    } else {
+        Ok(Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: Vec::new(),
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        })
+    }
+}
+
+/// Write drafts as `<drafts_dir>/hooks/<slug>.json` and merge them into its manifest.
+pub fn write_drafts(drafts_dir: &Path, drafts: &[HookDraft]) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(HOOKS_SUBDIR);
+    std::fs::create_dir_all(&dir)?;
+    let mut mf = load_manifest(drafts_dir)?;
+
+    for draft in drafts {
+        let path = draft_path(drafts_dir, &draft.slug);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&draft.suggestion)
+            .map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+        crate::util::write_atomic(&path, content.as_bytes())?;
+
+        manifest::merge_entry(
+            &mut mf,
+            DraftEntry {
+                slug: draft.slug.clone(),
+                domain: HOOK_DOMAIN.to_string(),
+                status: DraftStatus::Draft,
+                pattern_count: draft.suggestion.occurrences,
+                conversation_count: draft.suggestion.support,
+                generated_at: chrono::Utc::now(),
+                content_hash: manifest::compute_hash(&content),
+                score: Some(draft.suggestion.ratio),
+                project: draft.suggestion.project.clone(),
+                ..Default::default()
+            },
+        );
+    }
+
+    mf.generated_at = chrono::Utc::now();
+    manifest::write_manifest(&dir, &mf)?;
+    Ok(mf)
+}
+
+/// Read a hook draft by slug.
+pub fn read_draft(drafts_dir: &Path, slug: &str) -> Result<HookSuggestion, SkillMinerError> {
+    let path = draft_path(drafts_dir, slug);
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        SkillMinerError::Config(format!("draft file not found: {} ({})", path.display(), e))
+    })?;
+    serde_json::from_str(&content).map_err(|e| SkillMinerError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Settings file a hook is merged into.
+pub fn settings_path(global_settings: &Path, hook: &HookSuggestion) -> PathBuf {
+    match hook.project {
+        Some(ref p) => Path::new(p).join(".claude").join("settings.json"),
+        None => global_settings.to_path_buf(),
+    }
+}
+
+/// Merge hooks into settings JSON. Existing keys and hooks are kept; a hook whose command
+/// already runs for the same event and matcher is skipped. Returns (new content, hooks added).
+/// Fails rather than overwrite settings that are not a JSON object.
+pub fn merge_settings(existing: &str, hooks: &[HookSuggestion]) -> Result<(String, usize), SkillMinerError> {
+    let mut root: Value = if existing.trim().is_empty() {
+        Value::Object(Map::new())
+    } else {
+        serde_json::from_str(existing)
+            .map_err(|e| SkillMinerError::Parse(format!("settings.json: {}", e)))?
+    };
+    let not_object = |what: &str| SkillMinerError::Config(format!("settings.json: {} is not an object", what));
+
+    let mut added = 0;
+    for hook in hooks {
+        let root = root.as_object_mut().ok_or_else(|| not_object("root"))?;
+        let all_hooks = root
+            .entry("hooks")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| not_object("hooks"))?;
+        let event = all_hooks
+            .entry(hook.event.clone())
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .ok_or_else(|| SkillMinerError::Config(format!("settings.json: hooks.{} is not an array", hook.event)))?;
+
+        let existing_entry = event
+            .iter_mut()
+            .find(|e| e.get("matcher").and_then(Value::as_str) == Some(hook.matcher.as_str()));
+        match existing_entry {
+            Some(entry) => {
+                let commands = entry
+                    .as_object_mut()
+                    .ok_or_else(|| not_object("hook entry"))?
+                    .entry("hooks")
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                    .as_array_mut()
+                    .ok_or_else(|| SkillMinerError::Config("settings.json: hook entry hooks is not an array".to_string()))?;
+                let present = commands
+                    .iter()
+                    .any(|c| c.get("command").and_then(Value::as_str) == Some(hook.command.as_str()));
+                if !present {
+                    commands.push(json!({ "type": "command", "command": hook.command }));
+                    added += 1;
+                }
+            }
+            None => {
+                event.push(hook.settings_entry());
+                added += 1;
+            }
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&root).map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+    Ok((format!("{}\n", content), added))
+}
+
+/// Merged content of one settings file.
+#[derive(Debug, Clone)]
+pub struct SettingsPreview {
+    pub path: PathBuf,
+    pub content: String,
+    /// Hooks that are not in the file yet
+    pub added: usize,
+    /// Hook drafts targeting this file
+    pub slugs: Vec<String>,
+}
+
+/// Merged settings per target file for the given hook drafts, without writing anything.
+pub fn preview(
+    drafts_dir: &Path,
+    global_settings: &Path,
+    slugs: &[String],
+) -> Result<Vec<SettingsPreview>, SkillMinerError> {
+    let mut by_target: BTreeMap<PathBuf, Vec<(String, HookSuggestion)>> = BTreeMap::new();
+    for slug in slugs {
+        let hook = read_draft(drafts_dir, slug)?;
+        by_target
+            .entry(settings_path(global_settings, &hook))
+            .or_default()
+            .push((slug.clone(), hook));
+    }
+
+    let mut previews = Vec::new();
+    for (path, hooks) in by_target {
+        let existing = if path.exists() {
+            std::fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+        let suggestions: Vec<HookSuggestion> = hooks.iter().map(|(_, h)| h.clone()).collect();
+        let (content, added) = merge_settings(&existing, &suggestions)?;
+        let slugs = hooks.into_iter().map(|(s, _)| s).collect();
+        previews.push(SettingsPreview {
+            path,
+            content,
+            added,
+            slugs,
+        });
+    }
+    Ok(previews)
+}
+
+/// Merge hook drafts into their settings files and mark them deployed, as one transaction
+/// of the hooks dir that `deploy --hooks --undo` can revert, and write its manifest.
+/// The previous settings file is also kept as `settings.json.bak`.
+pub fn deploy_hooks(
+    drafts_dir: &Path,
+    global_settings: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    for slug in slugs {
+        if manifest::find_entry(manifest, slug).is_none() {
+            return Err(SkillMinerError::Config(format!("draft not found: {}", slug)));
+        }
+    }
+
+    transaction::run(&drafts_dir.join(HOOKS_SUBDIR), manifest, |tx, manifest| {
+        apply_hooks(tx, drafts_dir, global_settings, manifest, slugs)
+    })
+}
+
+fn apply_hooks(
+    tx: &mut Transaction,
+    drafts_dir: &Path,
+    global_settings: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    let mut results = Vec::new();
+    for SettingsPreview {
+        path,
+        content,
+        added,
+        slugs: target_slugs,
+    } in preview(drafts_dir, global_settings, slugs)?
+    {
+        let was_update = path.exists();
+        for slug in &target_slugs {
+            if let Some(entry) = manifest::find_entry(manifest, slug) {
+                tx.remember(entry)?;
+            }
+        }
+        if added > 0 {
+            tx.snapshot(&path)?;
+            tx.snapshot(&path.with_extension("json.bak"))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            if was_update {
+                std::fs::copy(&path, path.with_extension("json.bak"))?;
+            }
+            crate::util::write_atomic(&path, content.as_bytes())?;
+        }
+        for slug in target_slugs {
+            if let Some(e) = manifest::find_entry_mut(manifest, &slug) {
+                e.status = DraftStatus::Deployed;
+                e.deployed_at = Some(chrono::Utc::now());
+            }
+            results.push(DeployResult {
+                slug,
+                target_path: path.clone(),
+                was_update,
+                merged: false,
+                conflicts: Vec::new(),
+            });
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Message, Role, ToolUse};
+
+    fn tool(name: &str, command: Option<&str>) -> ToolUse {
+        ToolUse {
+            name: name.to_string(),
+            input_summary: String::new(),
+            file_path: None,
+            command: command.map(|c| c.to_string()),
+            task: None,
+            failed: false,
+        }
+    }
+
+    fn conv(id: &str, cwd: &str, tools: Vec<ToolUse>) -> Conversation {
+        Conversation {
+            id: id.to_string(),
+            source_path: PathBuf::from(format!("{}.jsonl", id)),
+            messages: vec![Message {
+                role: Role::Assistant,
+                content: String::new(),
+                timestamp: None,
+                tool_uses: tools,
+                sidechain: false,
+            }],
+            start_time: None,
+            end_time: None,
+            cwd: Some(cwd.to_string()),
+            git_branch: None,
+        }
+    }
+
+    fn edit_then_fmt(id: &str, cwd: &str) -> Conversation {
+        conv(
+            id,
+            cwd,
+            vec![
+                tool("Edit", None),
+                tool("Write", None),
+                tool("Bash", Some("cargo fmt")),
+                tool("Read", None),
+                tool("Edit", None),
+                tool("Bash", Some("cargo fmt")),
+                tool("Bash", Some("git status")),
+            ],
+        )
+    }
+
+    #[test]
+    fn detects_post_edit_habit() {
+        let convs = vec![
+            edit_then_fmt("1", "/r/app"),
+            edit_then_fmt("2", "/r/app"),
+            edit_then_fmt("3", "/r/app"),
+        ];
+        let hooks = mine_hooks(&convs, 3, 0.6);
+        assert_eq!(hooks.len(), 1);
+        let h = &hooks[0];
+        assert_eq!(h.command, "cargo fmt");
+        assert_eq!(h.matcher, "Edit|Write");
+        assert_eq!(h.support, 3);
+        assert_eq!(h.occurrences, 6);
+        assert!((h.ratio - 1.0).abs() < 1e-9);
+        assert_eq!(h.project.as_deref(), Some("/r/app"));
+        // git status only followed half of the edit runs
+        assert!(mine_hooks(&convs, 3, 0.4).iter().any(|h| h.command == "git status"));
+    }
+
+    #[test]
+    fn multi_project_habit_is_global() {
+        let convs = vec![
+            edit_then_fmt("1", "/r/a"),
+            edit_then_fmt("2", "/r/b"),
+            edit_then_fmt("3", "/r/b"),
+        ];
+        let hooks = mine_hooks(&convs, 3, 0.9);
+        assert_eq!(hooks[0].project, None);
+        let drafts = generate_drafts(&hooks);
+        assert_eq!(drafts[0].slug, "post-edit-cargo-fmt");
+    }
+
+    #[test]
+    fn merge_settings_keeps_existing_and_is_idempotent() {
+        let existing = r#"{
+  "model": "opus",
+  "hooks": {
+    "PostToolUse": [
+      { "matcher": "Edit|Write", "hooks": [{ "type": "command", "command": "cargo fmt" }] }
+    ],
+    "Stop": [{ "hooks": [{ "type": "command", "command": "notify" }] }]
+  }
+}"#;
+        let hook = |cmd: &str, matcher: &str| HookSuggestion {
+            event: POST_TOOL_USE.to_string(),
+            matcher: matcher.to_string(),
+            command: cmd.to_string(),
+            support: 3,
+            occurrences: 3,
+            ratio: 1.0,
+            project: None,
+        };
+        let hooks = vec![
+            hook("cargo fmt", "Edit|Write"),
+            hook("cargo clippy", "Edit|Write"),
+            hook("npm run lint", "Write"),
+        ];
+        let (merged, added) = merge_settings(existing, &hooks).unwrap();
+        assert_eq!(added, 2);
+        let v: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(v["model"], "opus");
+        assert_eq!(v["hooks"]["Stop"][0]["hooks"][0]["command"], "notify");
+        assert_eq!(v["hooks"]["PostToolUse"][0]["hooks"].as_array().unwrap().len(), 2);
+        assert_eq!(v["hooks"]["PostToolUse"][1]["matcher"], "Write");
+
+        let (again, added) = merge_settings(&merged, &hooks).unwrap();
+        assert_eq!(added, 0);
+        assert_eq!(again, merged);
+    }
+
+    #[test]
+    fn merge_settings_keeps_key_order() {
+        let existing = r#"{"theme": "dark", "permissions": {"deny": [], "allow": []}, "env": {}}"#;
+        let hook = HookSuggestion {
+            event: POST_TOOL_USE.to_string(),
+            matcher: "Edit".to_string(),
+            command: "cargo fmt".to_string(),
+            support: 3,
+            occurrences: 3,
+            ratio: 1.0,
+            project: None,
+        };
+        let (merged, _) = merge_settings(existing, &[hook]).unwrap();
+        let at = |key: &str| merged.find(key).unwrap();
+        assert!(at("\"theme\"") < at("\"permissions\""));
+        assert!(at("\"deny\"") < at("\"allow\""));
+        assert!(at("\"allow\"") < at("\"env\""));
+        assert!(at("\"env\"") < at("\"hooks\""));
+    }
+
+    #[test]
+    fn merge_settings_refuses_invalid_json() {
+        assert!(merge_settings("{ not json", &[]).is_err());
+        assert!(merge_settings("[1, 2]", &[HookSuggestion {
+            event: POST_TOOL_USE.to_string(),
+            matcher: "Edit".to_string(),
+            command: "x".to_string(),
+            support: 1,
+            occurrences: 1,
+            ratio: 1.0,
+            project: None,
+        }])
+        .is_err());
+    }
+
+    #[test]
+    fn deploy_hooks_writes_settings_with_backup() {
+        let drafts_dir = tempfile::tempdir().unwrap();
+        let home = tempfile::tempdir().unwrap();
+        let settings = home.path().join("settings.json");
+        std::fs::write(&settings, "{\"theme\": \"dark\"}").unwrap();
+
+        let convs = vec![
+            edit_then_fmt("1", "/r/a"),
+            edit_then_fmt("2", "/r/b"),
+            edit_then_fmt("3", "/r/c"),
+        ];
+        let drafts = generate_drafts(&mine_hooks(&convs, 3, 0.9));
+        let mut mf = write_drafts(drafts_dir.path(), &drafts).unwrap();
+        let slugs: Vec<String> = drafts.iter().map(|d| d.slug.clone()).collect();
+
+        let previews = preview(drafts_dir.path(), &settings, &slugs).unwrap();
+        assert_eq!(previews.len(), 1);
+        assert!(previews[0].content.contains("cargo fmt"));
+        // Preview does not write
+        assert_eq!(std::fs::read_to_string(&settings).unwrap(), "{\"theme\": \"dark\"}");
+
+        let results = deploy_hooks(drafts_dir.path(), &settings, &mut mf, &slugs).unwrap();
+        assert_eq!(results.len(), 1);
+        let written = std::fs::read_to_string(&settings).unwrap();
+        assert!(written.contains("\"theme\": \"dark\""));
+        assert!(written.contains("cargo fmt"));
+        assert!(home.path().join("settings.json.bak").exists());
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+
+        // The deploy is a transaction of the hooks dir
+        transaction::undo(&drafts_dir.path().join(HOOKS_SUBDIR), &mut mf).unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(&settings).unwrap(), "{\"theme\": \"dark\"}");
+        assert!(!home.path().join("settings.json.bak").exists());
+        assert_eq!(mf.entries[0].status, DraftStatus::Draft);
+    }
+}
@@ -0,0 +1,429 @@
+// Layout module: folder-based skills (<slug>/SKILL.md + supporting files)
+// Legacy single-file <slug>.md skills are still read everywhere and replaced on write.
+
+use crate::error::SkillMinerError;
+use crate::types::{SkillDraft, SkillFile};
+use std::path::{Component, Path, PathBuf};
+
+/// File name of the skill definition inside a skill folder.
+pub const SKILL_FILE: &str = "SKILL.md";
+
+fn folder_md_path(dir: &Path, slug: &str) -> PathBuf {
+    dir.join(slug).join(SKILL_FILE)
+}
+
+fn legacy_path(dir: &Path, slug: &str) -> PathBuf {
+    dir.join(format!("{}.md", slug))
+}
+
+/// Resolve a skill's markdown file: `<slug>/SKILL.md` first, then legacy `<slug>.md`.
+/// Returns the folder path when neither exists (the layout new skills are written in).
+pub fn skill_md_path(dir: &Path, slug: &str) -> PathBuf {
+    let folder = folder_md_path(dir, slug);
+    if folder.exists() {
+        return folder;
+    }
+    let legacy = legacy_path(dir, slug);
+    if legacy.exists() {
+        legacy
+    } else {
+        folder
+    }
+}
+
+/// Whether a skill exists in either layout.
+pub fn exists(dir: &Path, slug: &str) -> bool {
+    folder_md_path(dir, slug).exists() || legacy_path(dir, slug).exists()
+}
+
+/// Whether a skill is stored in folder layout.
+pub fn is_folder(dir: &Path, slug: &str) -> bool {
+    folder_md_path(dir, slug).exists()
+}
+
+/// Read a skill's SKILL.md (or legacy file) content.
+pub fn read_skill_md(dir: &Path, slug: &str) -> Result<String, SkillMinerError> {
+    Ok(std::fs::read_to_string(skill_md_path(dir, slug))?)
+}
+
+/// Relative paths (forward slashes, sorted) of supporting files in a folder skill.
+pub fn supporting_files(dir: &Path, slug: &str) -> Result<Vec<String>, SkillMinerError> {
+    let mut files = Vec::new();
+    if is_folder(dir, slug) {
+        let root = dir.join(slug);
+        collect_files(&root, &root, &mut files)?;
+        files.retain(|f| f != SKILL_FILE);
+        files.sort();
+    }
+    Ok(files)
+}
+
+fn collect_files(root: &Path, current: &Path, out: &mut Vec<String>) -> Result<(), SkillMinerError> {
+    for entry in std::fs::read_dir(current)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if file_type.is_file() {
+            if let Ok(rel) = path.strip_prefix(root) {
+                out.push(crate::util::normalize_path(rel));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read a skill's supporting files.
+pub fn read_supporting_files(dir: &Path, slug: &str) -> Result<Vec<SkillFile>, SkillMinerError> {
+    supporting_files(dir, slug)?
+        .into_iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(dir.join(slug).join(&path))?;
+            Ok(SkillFile { path, content })
+        })
+        .collect()
+}
+
+/// Hash of a skill's content. A skill without supporting files hashes like its SKILL.md
+/// alone (`manifest::compute_hash`); otherwise each supporting file's path and content is
+/// hashed after it, in path order.
+pub fn hash_content(skill_md: &str, files: &[SkillFile]) -> String {
+    if files.is_empty() {
+        return crate::manifest::compute_hash(skill_md);
+    }
+    let mut sorted: Vec<&SkillFile> = files.iter().collect();
+    sorted.sort_by(|a, b| a.path.cmp(&b.path));
+    let mut content = skill_md.to_string();
+    for file in sorted {
+        content.push_str(&format!("\n\0{}\n{}", file.path, file.content));
+    }
+    crate::manifest::compute_hash(&content)
+}
+
+/// Hash of a draft as `write_skill` writes it.
+pub fn draft_hash(draft: &SkillDraft) -> String {
+    hash_content(&draft.format_md(), &draft.files)
+}
+
+/// Hash of a skill on disk: SKILL.md (or legacy file) plus its supporting files.
+pub fn hash_skill(dir: &Path, slug: &str) -> Result<String, SkillMinerError> {
+    Ok(hash_content(&read_skill_md(dir, slug)?, &read_supporting_files(dir, slug)?))
+}
+
+/// Remove files of a skill folder that are not SKILL.md or in `keep`, then the
+/// directories they leave empty.
+fn remove_stale(root: &Path, keep: &[String]) -> Result<(), SkillMinerError> {
+    let mut files = Vec::new();
+    collect_files(root, root, &mut files)?;
+    for rel in files {
+        if rel != SKILL_FILE && !keep.contains(&rel) {
+            std::fs::remove_file(root.join(&rel))?;
+        }
+    }
+    remove_empty_dirs(root)?;
+    Ok(())
+}
+
+fn remove_empty_dirs(dir: &Path) -> Result<(), SkillMinerError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            let path = entry.path();
+            remove_empty_dirs(&path)?;
+            if std::fs::read_dir(&path)?.next().is_none() {
+                std::fs::remove_dir(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reject absolute paths and `..` so supporting files stay inside the skill folder.
+fn check_relative(path: &str) -> Result<(), SkillMinerError> {
+    let p = Path::new(path);
+    if p.components().all(|c| matches!(c, Component::Normal(_))) {
+        Ok(())
+    } else {
+        Err(SkillMinerError::Config(format!(
+            "supporting file escapes skill folder: {}",
+            path
+        )))
+    }
+}
+
+/// Write a draft as `<dir>/<slug>/SKILL.md` plus its supporting files.
+/// Files the draft no longer has and a legacy `<slug>.md` for the same skill are removed.
+/// Returns the SKILL.md path.
+pub fn write_skill(dir: &Path, slug: &str, draft: &SkillDraft) -> Result<PathBuf, SkillMinerError> {
+    for file in &draft.files {
+        check_relative(&file.path)?;
+    }
+
+    let root = dir.join(slug);
+    std::fs::create_dir_all(&root)?;
+    let skill_md = root.join(SKILL_FILE);
+    std::fs::write(&skill_md, draft.format_md())?;
+
+    for file in &draft.files {
+        let path = root.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.content)?;
+    }
+    let keep: Vec<String> = draft.files.iter().map(|f| f.path.clone()).collect();
+    remove_stale(&root, &keep)?;
+
+    let legacy = legacy_path(dir, slug);
+    if legacy.exists() {
+        std::fs::remove_file(&legacy)?;
+    }
+
+    Ok(skill_md)
+}
+
+/// Copy a skill in whichever layout it is stored from `src_dir/src_slug` to `dst_dir/dst_slug`.
+/// The destination ends up with exactly the source's files: folder skills replace a legacy
+/// file at the destination and drop supporting files the source no longer has, a legacy
+/// source replaces a destination folder. Returns the destination markdown path.
+pub fn copy_skill(
+    src_dir: &Path,
+    src_slug: &str,
+    dst_dir: &Path,
+    dst_slug: &str,
+) -> Result<PathBuf, SkillMinerError> {
+    if is_folder(src_dir, src_slug) {
+        let src_root = src_dir.join(src_slug);
+        let dst_root = dst_dir.join(dst_slug);
+        std::fs::create_dir_all(&dst_root)?;
+        std::fs::copy(src_root.join(SKILL_FILE), dst_root.join(SKILL_FILE))?;
+        let files = supporting_files(src_dir, src_slug)?;
+        for rel in &files {
+            let target = dst_root.join(rel);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(src_root.join(rel), &target)?;
+        }
+        remove_stale(&dst_root, &files)?;
+        let legacy = legacy_path(dst_dir, dst_slug);
+        if legacy.exists() {
+            std::fs::remove_file(&legacy)?;
+        }
+        Ok(dst_root.join(SKILL_FILE))
+    } else {
+        let source = legacy_path(src_dir, src_slug);
+        if !source.exists() {
+            return Err(SkillMinerError::Config(format!(
+                "skill not found: {}",
+                src_dir.join(src_slug).display()
+            )));
+        }
+        let target = legacy_path(dst_dir, dst_slug);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&source, &target)?;
+        if is_folder(dst_dir, dst_slug) {
+            std::fs::remove_dir_all(dst_dir.join(dst_slug))?;
+        }
+        Ok(target)
+    }
+}
+
+/// Remove a skill in either layout. Missing skills are not an error.
+pub fn remove_skill(dir: &Path, slug: &str) -> Result<(), SkillMinerError> {
+    let root = dir.join(slug);
+    if root.join(SKILL_FILE).exists() {
+        std::fs::remove_dir_all(&root)?;
+    }
+    let legacy = legacy_path(dir, slug);
+    if legacy.exists() {
+        std::fs::remove_file(&legacy)?;
+    }
+    Ok(())
+}
+
+/// List skills directly under `dir` as (slug, markdown path), in both layouts.
+/// A folder skill wins over a legacy file with the same slug.
+pub fn list_skills(dir: &Path) -> Result<Vec<(String, PathBuf)>, SkillMinerError> {
+    let mut skills: std::collections::BTreeMap<String, PathBuf> = std::collections::BTreeMap::new();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            let skill_md = path.join(SKILL_FILE);
+            if skill_md.exists() {
+                let slug = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                skills.insert(slug, skill_md);
+            }
+        } else if path.extension().map(|e| e == "md").unwrap_or(false) {
+            let slug = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            skills.entry(slug).or_insert(path);
+        }
+    }
+
+    Ok(skills.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_draft(name: &str, files: Vec<SkillFile>) -> SkillDraft {
+        SkillDraft {
+            name: name.to_string(),
+            description: "desc".to_string(),
+            body: "# Body\n\nSee [examples/1-1.rs](examples/1-1.rs) (30 lines)".to_string(),
+            sources: vec![],
+            existing_skill: None,
+            diff: None,
+            files,
+            allowed_tools: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn write_skill_creates_folder_and_removes_legacy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("my-skill.md"), "legacy").unwrap();
+        let draft = make_draft(
+            "my-skill",
+            vec![SkillFile {
+                path: "examples/1-1.rs".to_string(),
+                content: "fn main() {}\n".to_string(),
+            }],
+        );
+
+        let path = write_skill(dir.path(), "my-skill", &draft).unwrap();
+        assert_eq!(path, dir.path().join("my-skill").join("SKILL.md"));
+        assert!(!dir.path().join("my-skill.md").exists());
+        assert_eq!(
+            supporting_files(dir.path(), "my-skill").unwrap(),
+            vec!["examples/1-1.rs".to_string()]
+        );
+        assert_eq!(skill_md_path(dir.path(), "my-skill"), path);
+    }
+
+    #[test]
+    fn write_skill_rejects_escaping_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let draft = make_draft(
+            "bad",
+            vec![SkillFile {
+                path: "../outside.txt".to_string(),
+                content: String::new(),
+            }],
+        );
+        assert!(write_skill(dir.path(), "bad", &draft).is_err());
+        assert!(!dir.path().join("bad").exists());
+    }
+
+    #[test]
+    fn skill_md_path_falls_back_to_legacy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("old.md"), "legacy").unwrap();
+        assert_eq!(skill_md_path(dir.path(), "old"), dir.path().join("old.md"));
+        assert!(exists(dir.path(), "old"));
+        assert!(!is_folder(dir.path(), "old"));
+        assert_eq!(
+            skill_md_path(dir.path(), "new"),
+            dir.path().join("new").join("SKILL.md")
+        );
+    }
+
+    #[test]
+    fn copy_and_remove_folder_skill() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        let draft = make_draft(
+            "s",
+            vec![SkillFile {
+                path: "scripts/run.sh".to_string(),
+                content: "echo hi\n".to_string(),
+            }],
+        );
+        write_skill(src.path(), "s", &draft).unwrap();
+        std::fs::write(dst.path().join("s.md"), "legacy deployed").unwrap();
+
+        let target = copy_skill(src.path(), "s", dst.path(), "s").unwrap();
+        assert_eq!(target, dst.path().join("s").join("SKILL.md"));
+        assert!(dst.path().join("s").join("scripts").join("run.sh").exists());
+        assert!(!dst.path().join("s.md").exists());
+
+        remove_skill(dst.path(), "s").unwrap();
+        assert!(!exists(dst.path(), "s"));
+    }
+
+    #[test]
+    fn write_and_copy_drop_obsolete_files() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        let file = |path: &str| SkillFile {
+            path: path.to_string(),
+            content: "x\n".to_string(),
+        };
+        write_skill(src.path(), "s", &make_draft("s", vec![file("examples/1-1.rs"), file("a.md")]))
+            .unwrap();
+        copy_skill(src.path(), "s", dst.path(), "s").unwrap();
+
+        write_skill(src.path(), "s", &make_draft("s", vec![file("a.md")])).unwrap();
+        assert_eq!(supporting_files(src.path(), "s").unwrap(), vec!["a.md".to_string()]);
+        assert!(!src.path().join("s").join("examples").exists());
+
+        copy_skill(src.path(), "s", dst.path(), "s").unwrap();
+        assert_eq!(supporting_files(dst.path(), "s").unwrap(), vec!["a.md".to_string()]);
+        assert!(!dst.path().join("s").join("examples").exists());
+    }
+
+    #[test]
+    fn hash_covers_supporting_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = make_draft("p", vec![]);
+        write_skill(dir.path(), "p", &plain).unwrap();
+        assert_eq!(
+            hash_skill(dir.path(), "p").unwrap(),
+            crate::manifest::compute_hash(&plain.format_md())
+        );
+
+        let mut draft = make_draft(
+            "s",
+            vec![SkillFile {
+                path: "examples/1-1.rs".to_string(),
+                content: "fn main() {}\n".to_string(),
+            }],
+        );
+        write_skill(dir.path(), "s", &draft).unwrap();
+        let hash = hash_skill(dir.path(), "s").unwrap();
+        assert_eq!(hash, draft_hash(&draft));
+
+        draft.files[0].content = "fn main() { run() }\n".to_string();
+        write_skill(dir.path(), "s", &draft).unwrap();
+        assert_ne!(hash_skill(dir.path(), "s").unwrap(), hash);
+    }
+
+    #[test]
+    fn list_skills_both_layouts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("legacy.md"), "x").unwrap();
+        write_skill(dir.path(), "folder", &make_draft("folder", vec![])).unwrap();
+        std::fs::create_dir_all(dir.path().join("not-a-skill")).unwrap();
+
+        let skills = list_skills(dir.path()).unwrap();
+        let slugs: Vec<&str> = skills.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(slugs, vec!["folder", "legacy"]);
+    }
+}
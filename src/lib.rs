@@ -1,29 +1,50 @@
+pub mod agents;
+pub mod attribution;
+pub mod audit;
 pub mod bundle;
 pub mod classifier;
+pub mod commands;
 pub mod compressor;
 pub mod deployer;
+pub mod diff;
 pub mod domains;
+pub mod drift;
 pub mod error;
 pub mod extractor;
+pub mod forget;
 pub mod generator;
 pub mod graph;
 pub mod history;
+pub mod hooks;
+pub mod layout;
+pub mod lock;
 pub mod manifest;
+pub mod merge;
 pub mod miner;
+pub mod overlap;
 pub mod parser;
+pub mod patterns;
+pub mod project;
 pub mod refiner;
+pub mod rules;
 pub mod scorer;
 pub mod sync;
+pub mod template;
+pub mod targets;
 pub mod today;
 pub mod tool_coverage;
+pub mod transaction;
 pub mod types;
-
 pub mod util;
+pub mod versions;
 
 pub use error::SkillMinerError;
 pub use types::{
-    BundleSkill, BundleStats, ClassifiedConversation, Conversation, ConversationSummary,
-    DepType, DependencyGraph, DeployResult, DomainCluster, DraftEntry, DraftStatus, GraphNode,
-    ImportResult, KnowledgePattern, Manifest, Message, MineConfig, PipelineStats, PruneOptions,
-    RawRef, Role, SkillBundle, SkillDependency, SkillDraft, SkillInvocation, ToolUse,
+    AgentFormat, AuditAction, AuditEvent, BundleSkill, BundleStats, ClassifiedConversation,
+    Conversation, ConversationSummary, DepType, DependencyGraph, DeployResult, DeployTarget,
+    DomainCluster, DraftEntry, DraftMeta, DraftStatus, DriftKind, ForgetList, GraphNode,
+    ImportResult, InvocationOutcome, KnowledgePattern, Manifest, Message, MineConfig, OverlapPair,
+    OverlapSuggestion, PatternStore, PipelineStats, PruneOptions, RawRef, ReviewComment, Role,
+    RuleExport, RuleExportResult, ScoreBreakdown, SkillBundle, SkillDependency, SkillDraft,
+    SkillDrift, SkillFile, SkillInvocation, ToolUse, UndeployOptions, UndeployResult,
 };
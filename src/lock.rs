@@ -0,0 +1,344 @@
+// Lock module: advisory lock on a drafts directory
+// Read-modify-write commands hold `<drafts>/.skill-miner.lock` for their whole run so a
+// cron `mine` and an interactive `approve` cannot interleave manifest updates.
+
+use crate::error::SkillMinerError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lock file name inside the drafts directory.
+pub const LOCK_FILE: &str = ".skill-miner.lock";
+
+/// How long to wait for a live lock holder before giving up.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Locks older than this are treated as stale even if the holder cannot be checked.
+const STALE_AFTER_HOURS: i64 = 12;
+
+/// Unreadable lock files (left by a crash while one was written) are taken over once
+/// they were last modified this long ago.
+const UNREADABLE_STALE_AFTER: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Who holds a lock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    #[serde(default)]
+    pub host: String,
+    pub command: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+impl LockInfo {
+    fn current(command: &str) -> Self {
+        Self {
+            pid: std::process::id(),
+            host: hostname(),
+            command: command.to_string(),
+            acquired_at: Utc::now(),
+        }
+    }
+
+    /// Whether the holder has crashed: its process is gone (same host) or the lock is too old.
+    pub fn is_stale(&self) -> bool {
+        if Utc::now() - self.acquired_at > chrono::Duration::hours(STALE_AFTER_HOURS) {
+            return true;
+        }
+        self.host == hostname() && process_alive(self.pid) == Some(false)
+    }
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Whether a process exists. None when it cannot be checked on this platform.
+fn process_alive(pid: u32) -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        Some(Path::new("/proc").join(pid.to_string()).exists())
+    } else {
+        None
+    }
+}
+
+/// Held lock; released when dropped.
+#[derive(Debug)]
+pub struct DirLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+impl DirLock {
+    /// Lock a drafts directory, waiting up to [`LOCK_TIMEOUT`] for another holder.
+    pub fn acquire(dir: &Path, command: &str) -> Result<Self, SkillMinerError> {
+        Self::acquire_timeout(dir, command, LOCK_TIMEOUT)
+    }
+
+    /// Lock a drafts directory, waiting up to `timeout`. Stale locks are taken over.
+    pub fn acquire_timeout(
+        dir: &Path,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<Self, SkillMinerError> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        let info = LockInfo::current(command);
+        let content =
+            serde_json::to_string(&info).map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+        let start = Instant::now();
+
+        loop {
+            match create_lock(&path, &content) {
+                Ok(()) => return Ok(Self { path, info }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            let holder = read_lock(dir);
+            match holder {
+                Some(ref holder) if holder.is_stale() => {
+                    if take_over(&path, Some(holder))? {
+                        eprintln!(
+                            "Removed stale lock of pid {} ({}) from {}",
+                            holder.pid,
+                            holder.command,
+                            holder.acquired_at.format("%Y-%m-%d %H:%M:%S")
+                        );
+                    }
+                    continue;
+                }
+                None => match lock_age(&path) {
+                    // Released in the meantime
+                    None => continue,
+                    Some(age) if age >= UNREADABLE_STALE_AFTER => {
+                        take_over(&path, None)?;
+                        continue;
+                    }
+                    Some(_) => {}
+                },
+                Some(_) => {}
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(SkillMinerError::Config(match holder {
+                    Some(holder) => format!(
+                        "{} is locked by pid {} ({}) since {}; if that process is gone, run: skill-miner unlock -d {}",
+                        dir.display(),
+                        holder.pid,
+                        holder.command,
+                        holder.acquired_at.format("%Y-%m-%d %H:%M:%S"),
+                        dir.display()
+                    ),
+                    None => format!(
+                        "{} is locked (unreadable lock file); if no skill-miner is running, run: skill-miner unlock -d {}",
+                        dir.display(),
+                        dir.display()
+                    ),
+                }));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Unique suffix for the temp files of this process.
+fn unique_suffix() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    format!("{}-{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Create the lock file with its content in one step: the content is written to a temp
+/// file, which is hard-linked to the lock path. Linking fails if a lock already exists, and
+/// no other process ever sees the lock without its holder.
+fn create_lock(path: &Path, content: &str) -> std::io::Result<()> {
+    let tmp = path.with_file_name(format!("{}.{}.tmp", LOCK_FILE, unique_suffix()));
+    std::fs::write(&tmp, content)?;
+    let linked = std::fs::hard_link(&tmp, path);
+    let _ = std::fs::remove_file(&tmp);
+    linked
+}
+
+/// Time since the lock file was last modified (None if there is no lock file).
+fn lock_age(path: &Path) -> Option<Duration> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.elapsed().unwrap_or_default())
+}
+
+/// Take over a stale lock: move it aside, then delete it. The rename is atomic, so of
+/// several processes taking over the same lock only one moves it. If the moved lock is not
+/// the one found stale (another process took over first and now holds it), it is put back;
+/// if that fails, it is left next to the lock and an error is returned.
+/// Returns whether the stale lock was removed.
+fn take_over(path: &Path, stale: Option<&LockInfo>) -> Result<bool, SkillMinerError> {
+    let aside = path.with_file_name(format!("{}.{}.stale", LOCK_FILE, unique_suffix()));
+    match std::fs::rename(path, &aside) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    }
+    let moved: Option<LockInfo> = std::fs::read_to_string(&aside)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    let removed = moved.as_ref() == stale;
+    if !removed {
+        if let Err(e) = std::fs::hard_link(&aside, path) {
+            let dir = path.parent().unwrap_or(Path::new("."));
+            return Err(SkillMinerError::Config(format!(
+                "could not put back the lock of {} (kept as {}): {}; run: skill-miner unlock -d {}",
+                dir.display(),
+                aside.display(),
+                e,
+                dir.display()
+            )));
+        }
+    }
+    std::fs::remove_file(&aside)?;
+    Ok(removed)
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        // Only remove the file if it is still ours (it may have been broken by `unlock`)
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        if read_lock(dir).as_ref() == Some(&self.info) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Current holder of a directory's lock, if any.
+pub fn read_lock(dir: &Path) -> Option<LockInfo> {
+    let content = std::fs::read_to_string(dir.join(LOCK_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Remove a directory's lock regardless of its holder. Returns the removed holder.
+pub fn break_lock(dir: &Path) -> Result<Option<LockInfo>, SkillMinerError> {
+    let path = dir.join(LOCK_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let holder = read_lock(dir);
+    std::fs::remove_file(&path)?;
+    Ok(holder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acquire_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let _lock = DirLock::acquire(dir.path(), "approve").unwrap();
+            let holder = read_lock(dir.path()).unwrap();
+            assert_eq!(holder.pid, std::process::id());
+            assert_eq!(holder.command, "approve");
+        }
+        assert!(!dir.path().join(LOCK_FILE).exists());
+    }
+
+    #[test]
+    fn live_lock_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let _lock = DirLock::acquire(dir.path(), "mine").unwrap();
+        let err = DirLock::acquire_timeout(dir.path(), "approve", Duration::from_millis(300))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("locked by pid"), "{}", err);
+        assert!(err.contains("(mine)"));
+    }
+
+    #[test]
+    fn stale_locks_are_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = LockInfo {
+            pid: std::process::id(),
+            host: hostname(),
+            command: "mine".to_string(),
+            acquired_at: Utc::now() - chrono::Duration::hours(STALE_AFTER_HOURS + 1),
+        };
+        assert!(old.is_stale());
+        std::fs::write(dir.path().join(LOCK_FILE), serde_json::to_string(&old).unwrap()).unwrap();
+        let lock = DirLock::acquire_timeout(dir.path(), "approve", Duration::ZERO).unwrap();
+        assert_eq!(read_lock(dir.path()).unwrap().command, "approve");
+        drop(lock);
+
+        // Garbage left by a crash mid-write: kept while recent, taken over once old
+        let path = dir.path().join(LOCK_FILE);
+        std::fs::write(&path, "{").unwrap();
+        let err = DirLock::acquire_timeout(dir.path(), "reject", Duration::from_millis(300))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unreadable lock file"), "{}", err);
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() - UNREADABLE_STALE_AFTER * 2).unwrap();
+        drop(file);
+        let _lock = DirLock::acquire_timeout(dir.path(), "reject", Duration::ZERO).unwrap();
+        assert_eq!(read_lock(dir.path()).unwrap().command, "reject");
+        let leftovers = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+
+    #[test]
+    fn take_over_puts_back_a_replaced_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let stale = LockInfo {
+            pid: std::process::id(),
+            host: hostname(),
+            command: "mine".to_string(),
+            acquired_at: Utc::now() - chrono::Duration::hours(STALE_AFTER_HOURS + 1),
+        };
+        // Another process already replaced the stale lock with its own
+        let _live = DirLock::acquire(dir.path(), "approve").unwrap();
+        assert!(!take_over(&path, Some(&stale)).unwrap());
+        assert_eq!(read_lock(dir.path()).unwrap().command, "approve");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn dead_holder_is_stale() {
+        let dead = LockInfo {
+            pid: u32::MAX - 1,
+            host: hostname(),
+            command: "mine".to_string(),
+            acquired_at: Utc::now(),
+        };
+        assert!(dead.is_stale());
+        let live = LockInfo {
+            pid: std::process::id(),
+            ..dead.clone()
+        };
+        assert!(!live.is_stale());
+        let remote = LockInfo {
+            host: "elsewhere".to_string(),
+            ..dead
+        };
+        assert!(!remote.is_stale());
+    }
+
+    #[test]
+    fn break_lock_removes_any_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(break_lock(dir.path()).unwrap().is_none());
+        let lock = DirLock::acquire(dir.path(), "mine").unwrap();
+        let holder = break_lock(dir.path()).unwrap().unwrap();
+        assert_eq!(holder.command, "mine");
+        // The broken lock's guard must not remove a newer holder's file
+        let _newer = DirLock::acquire(dir.path(), "approve").unwrap();
+        drop(lock);
+        assert_eq!(read_lock(dir.path()).unwrap().command, "approve");
+    }
+}
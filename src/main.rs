@@ -1,8 +1,11 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use skill_miner::{
-    bundle, classifier, compressor, deployer, extractor, generator, graph, history, manifest, miner,
-    parser, refiner, scorer, today, util, DraftStatus, MineConfig, PruneOptions,
+    agents, attribution, audit, bundle, classifier, commands, compressor, deployer, diff, drift, extractor, forget, generator, graph, history,
+    hooks, layout, lock, manifest, miner, overlap, parser, patterns, project, refiner, rules, scorer, targets, template, today,
+    transaction, util, versions,
+    AgentFormat, AuditAction, DeployResult, DraftStatus, DriftKind, Manifest, MineConfig, OverlapSuggestion,
+    PruneOptions, SkillDrift,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -13,6 +16,23 @@ enum TodayFormat {
     Summary,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum RuleFormat {
+    Cursor,
+    Copilot,
+    AgentsMd,
+}
+
+impl From<RuleFormat> for AgentFormat {
+    fn from(format: RuleFormat) -> Self {
+        match format {
+            RuleFormat::Cursor => AgentFormat::Cursor,
+            RuleFormat::Copilot => AgentFormat::Copilot,
+            RuleFormat::AgentsMd => AgentFormat::AgentsMd,
+        }
+    }
+}
+
 #[derive(clap::ValueEnum, Clone, Copy, Debug)]
 enum SummaryGranularity {
     Coarse,
@@ -89,6 +109,16 @@ enum Command {
         output: Option<PathBuf>,
     },
 
+    /// Rebuild drafts from the stored patterns (no AI calls)
+    Regenerate {
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Show what would change without writing drafts
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Run progressive mining: auto-expand time window until no new conversations
     Mine {
         /// Output directory for generated skills
@@ -130,11 +160,83 @@ enum Command {
         summarize_model: String,
     },
 
+    /// Mine recurring Bash command sequences into slash-command drafts (no AI)
+    MineCommands {
+        /// How many days back to scan
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Minimum messages per conversation
+        #[arg(short, long, default_value = "4")]
+        min_messages: usize,
+        /// Minimum number of conversations a sequence must appear in
+        #[arg(long, default_value = "3")]
+        min_support: usize,
+        /// Longest command sequence to consider
+        #[arg(long, default_value = "4")]
+        max_len: usize,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Suggest PostToolUse hooks from commands run by hand after edits (no AI)
+    MineHooks {
+        /// How many days back to scan
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Minimum messages per conversation
+        #[arg(short, long, default_value = "4")]
+        min_messages: usize,
+        /// Minimum number of conversations showing the habit
+        #[arg(long, default_value = "3")]
+        min_support: usize,
+        /// Minimum share of edit runs followed by the command (0.0-1.0)
+        #[arg(long, default_value = "0.5")]
+        min_ratio: f64,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Turn repeated Task delegations into subagent drafts (no AI)
+    MineAgents {
+        /// How many days back to scan
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Minimum messages per conversation
+        #[arg(short, long, default_value = "4")]
+        min_messages: usize,
+        /// Minimum number of conversations delegating the same kind of work
+        #[arg(long, default_value = "3")]
+        min_support: usize,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
     /// List skill drafts with their status
     List {
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
+        /// Only drafts with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only drafts assigned to this owner
+        #[arg(long)]
+        owner: Option<String>,
+        /// List project-scoped drafts instead of global skills
+        #[arg(long)]
+        project: bool,
+        /// List slash-command drafts instead of skills
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// List hook drafts instead of skills
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// List subagent drafts instead of skills
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
     },
 
     /// Show diff between draft and deployed skill
@@ -144,6 +246,65 @@ enum Command {
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
+        /// Compare stored versions instead: from this version (v3, 3 or a hash prefix)
+        #[arg(long, requires = "name")]
+        from: Option<String>,
+        /// Compare stored versions up to this version (default: latest)
+        #[arg(long, requires = "name")]
+        to: Option<String>,
+        /// Summarize changes per pattern section instead of printing hunks
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        sections: bool,
+        /// Also write the unified diff to this file (apply with `patch -p1` in the skills dir)
+        #[arg(long, value_name = "FILE")]
+        patch: Option<PathBuf>,
+        /// Only compare against this deploy target ("global" or a project path)
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        target: Option<String>,
+    },
+
+    /// Reconcile deployed skills with the manifest (modified, missing, unknown)
+    Status {
+        /// Only these skill slugs (omit for all)
+        names: Vec<String>,
+        /// Copy modified and unknown deployed skills back into the drafts
+        #[arg(long)]
+        adopt: bool,
+        /// Only check this deploy target ("global" or a project path)
+        #[arg(long)]
+        target: Option<String>,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// List the stored versions of a draft
+    Versions {
+        /// Draft slug
+        slug: String,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Restore a stored version of a draft (redeploys it if it is deployed)
+    Rollback {
+        /// Draft slug
+        slug: String,
+        /// Version to restore (v3, 3 or a hash prefix)
+        version: String,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Forget a conversation or every conversation of a project, and never mine it again
+    Forget {
+        /// Conversation ID or project path
+        target: String,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
     },
 
     /// Approve skill drafts for deployment
@@ -156,18 +317,182 @@ enum Command {
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
+        /// Approve project-scoped drafts (slugs are <project>/<skill>)
+        #[arg(long)]
+        project: bool,
+        /// Approve slash-command drafts
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Approve hook drafts
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Approve subagent drafts
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
     },
 
     /// Reject skill drafts
     Reject {
         /// Skill slugs to reject
         names: Vec<String>,
+        /// Why the drafts are rejected (recorded in the audit log)
+        #[arg(long)]
+        reason: Option<String>,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Reject project-scoped drafts (slugs are <project>/<skill>)
+        #[arg(long)]
+        project: bool,
+        /// Reject slash-command drafts
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Reject hook drafts
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Reject subagent drafts
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Show the audit timeline of a draft (generated, approved, rejected, deployed, ...)
+    History {
+        /// Draft slug
+        slug: String,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Show a project-scoped draft
+        #[arg(long)]
+        project: bool,
+        /// Show a slash-command draft
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Show a hook draft
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Show a subagent draft
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Add or remove review tags on a draft
+    Tag {
+        /// Draft slug
+        slug: String,
+        /// Tags to add (or remove with --remove)
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Remove the tags instead
+        #[arg(long)]
+        remove: bool,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Tag a project-scoped draft
+        #[arg(long)]
+        project: bool,
+        /// Tag a slash-command draft
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Tag a hook draft
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Tag a subagent draft
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Assign a reviewer to a draft (omit OWNER to unassign)
+    Assign {
+        /// Draft slug
+        slug: String,
+        /// Owner name
+        owner: Option<String>,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Assign a project-scoped draft
+        #[arg(long)]
+        project: bool,
+        /// Assign a slash-command draft
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Assign a hook draft
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Assign a subagent draft
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Set the notes of a draft (omit TEXT to show them)
+    Note {
+        /// Draft slug
+        slug: String,
+        /// Notes text (replaces the current notes)
+        text: Option<String>,
+        /// Clear the notes
+        #[arg(long, conflicts_with = "text")]
+        clear: bool,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Annotate a project-scoped draft
+        #[arg(long)]
+        project: bool,
+        /// Annotate a slash-command draft
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Annotate a hook draft
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Annotate a subagent draft
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Add a review comment to a draft (omit TEXT to show the thread)
+    Comment {
+        /// Draft slug
+        slug: String,
+        /// Comment text
+        text: Option<String>,
+        /// Reply to comment #N
+        #[arg(long, requires = "text")]
+        reply_to: Option<usize>,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Comment on a project-scoped draft
+        #[arg(long)]
+        project: bool,
+        /// Comment on a slash-command draft
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Comment on a hook draft
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// Comment on a subagent draft
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+    },
+
+    /// Show or set where a skill deploys: "global" (~/.claude/skills) and/or project paths
+    Target {
+        /// Skill slug
+        slug: String,
+        /// New targets ("global" or a repository path)
+        targets: Vec<String>,
+        /// Go back to the default target: global
+        #[arg(long, conflicts_with = "targets")]
+        auto: bool,
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
     },
 
-    /// Deploy approved skills to ~/.claude/skills/
+    /// Deploy approved skills to their targets (~/.claude/skills/ or <repo>/.claude/skills/)
     Deploy {
         /// Deploy specific skills by name (or use --approved)
         names: Vec<String>,
@@ -177,6 +502,68 @@ enum Command {
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
+        /// Deploy project-scoped drafts into their repositories (CLAUDE.md sections by default)
+        #[arg(long)]
+        project: bool,
+        /// With --project: write <repo>/.claude/skills/<name>/SKILL.md instead of a CLAUDE.md section
+        #[arg(long, requires = "project")]
+        as_skill: bool,
+        /// Deploy slash-command drafts to ~/.claude/commands/ (or <repo>/.claude/commands/)
+        #[arg(long, conflicts_with = "project")]
+        commands: bool,
+        /// Merge hook drafts into ~/.claude/settings.json (or <repo>/.claude/settings.json)
+        #[arg(long, conflicts_with_all = ["project", "commands"])]
+        hooks: bool,
+        /// With --hooks: print the merged settings files without writing them
+        #[arg(long, requires = "hooks")]
+        dry_run: bool,
+        /// Deploy subagent drafts to ~/.claude/agents/
+        #[arg(long, conflicts_with_all = ["project", "commands", "hooks"])]
+        agents: bool,
+        /// Export to another agent's rule format instead (Cursor rule, Copilot or AGENTS.md section)
+        #[arg(long, value_enum, conflicts_with_all = ["project", "commands", "hooks", "agents"])]
+        format: Option<RuleFormat>,
+        /// With --format: write into this repository instead of each draft's project targets
+        #[arg(long, requires = "format")]
+        repo: Option<String>,
+        /// Restore the skills (and deploy state) from before the last deploy of the scope
+        #[arg(long, conflicts_with_all = ["names", "approved", "format"])]
+        undo: bool,
+    },
+
+    /// Remove deployed skills from ~/.claude/skills/ (and project targets)
+    Undeploy {
+        /// Skill slugs
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Only undeploy from this target ("global" or a project path)
+        #[arg(long)]
+        target: Option<String>,
+        /// Mark the drafts rejected (default: approved, so they can be redeployed)
+        #[arg(long)]
+        reject: bool,
+        /// Reason recorded in the audit log
+        #[arg(long)]
+        reason: Option<String>,
+        /// Move the deployed copies to <drafts>/.archive/ instead of deleting them
+        #[arg(long)]
+        archive: bool,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Inspect or upgrade manifest.toml files
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestCommand,
+    },
+
+    /// Remove the lock on a drafts directory left by a crashed run
+    Unlock {
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
     },
 
     /// Remove low-quality or duplicate drafts
@@ -262,6 +649,34 @@ enum Command {
         dir: Option<PathBuf>,
     },
 
+    /// Find skills whose descriptions overlap and collide on the same prompts
+    Overlap {
+        /// How many days of invocation logs to replay as test prompts
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Report pairs at or above this similarity (0..1), besides pairs with collisions
+        #[arg(long, default_value = "0.35")]
+        threshold: f64,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Score skills from invocation logs, or explain one skill's score
+    Score {
+        /// Skill slugs to show (default: all)
+        names: Vec<String>,
+        /// Print each factor's contribution to this skill's score
+        #[arg(long, value_name = "SLUG", conflicts_with = "names")]
+        explain: Option<String>,
+        /// How many days of invocation logs to scan
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Drafts directory
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
     /// Consolidate: score skills from invocation logs and rebuild descriptions
     Consolidate {
         /// Skill slugs to consolidate (or use --all)
@@ -281,6 +696,12 @@ enum Command {
         /// Use AI to refine descriptions based on actual trigger phrases
         #[arg(long)]
         refine: bool,
+        /// Rebuild drafts from stored patterns, keeping only the sections that get followed
+        #[arg(long)]
+        sections: bool,
+        /// Also undeploy deployed skills that are rejected for their score
+        #[arg(long)]
+        undeploy: bool,
         /// Drafts directory
         #[arg(short, long)]
         dir: Option<PathBuf>,
@@ -306,10 +727,29 @@ enum Command {
     },
 }
 
+#[derive(Subcommand)]
+enum ManifestCommand {
+    /// Upgrade manifests to the current schema (the originals are kept as manifest.toml.v<N>.bak)
+    Migrate {
+        /// Drafts directory (its projects/, commands/, hooks/ and agents/ manifests are included)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let config = MineConfig::default();
 
+    // Read-modify-write commands hold the drafts dir lock until they return
+    let _lock = match lock_target(&config, &cli.command) {
+        Some((dir, name)) => Some(lock::DirLock::acquire(&dir, name)?),
+        None => None,
+    };
+
     match cli.command {
         Command::Scan { days, min_messages, fast, project } => {
             if fast {
@@ -328,6 +768,7 @@ fn main() -> Result<()> {
             cmd_extract(&config, input, output, parallel, sum_opts.as_ref())
         }
         Command::Generate { input, output } => cmd_generate(&config, input, output),
+        Command::Regenerate { dir, dry_run } => cmd_regenerate(&config, dir, dry_run),
         Command::Mine {
             output,
             deploy,
@@ -345,15 +786,109 @@ fn main() -> Result<()> {
             let sum_opts = build_summarize_options(no_summarize, &summarize_backend, &summarize_model);
             cmd_mine(&config, output, !deploy, parallel, max_windows, max_days, min_messages, min_significance, dir, sync, sum_opts)
         }
-        Command::List { dir } => cmd_list(&config, dir),
-        Command::Diff { name, dir } => cmd_diff(&config, name, dir),
-        Command::Approve { names, all, dir } => cmd_approve(&config, names, all, dir),
-        Command::Reject { names, dir } => cmd_reject(&config, names, dir),
+        Command::MineCommands {
+            days,
+            min_messages,
+            min_support,
+            max_len,
+            dir,
+        } => cmd_mine_commands(&config, days, min_messages, min_support, max_len, dir),
+        Command::MineHooks {
+            days,
+            min_messages,
+            min_support,
+            min_ratio,
+            dir,
+        } => cmd_mine_hooks(&config, days, min_messages, min_support, min_ratio, dir),
+        Command::MineAgents {
+            days,
+            min_messages,
+            min_support,
+            dir,
+        } => cmd_mine_agents(&config, days, min_messages, min_support, dir),
+        Command::List { dir, tag, owner, project, commands, hooks, agents } => cmd_list(
+            &config,
+            dir,
+            tag.as_deref(),
+            owner.as_deref(),
+            DraftScope::from_flags(project, commands, hooks, agents),
+        ),
+        Command::Diff { name, dir, from, to, sections, patch, target } => match (name, from, to) {
+            (Some(slug), from, to) if from.is_some() || to.is_some() => {
+                cmd_diff_versions(&config, &slug, dir, from, to, patch)
+            }
+            (name, _, _) => cmd_diff(&config, name, dir, sections, patch, target),
+        },
+        Command::Status { names, adopt, target, dir } => {
+            cmd_status(&config, &names, adopt, target, dir)
+        }
+        Command::Versions { slug, dir } => cmd_versions(&config, &slug, dir),
+        Command::Rollback { slug, version, dir } => cmd_rollback(&config, &slug, &version, dir),
+        Command::Forget { target, dir } => cmd_forget(&config, &target, dir),
+        Command::Approve { names, all, dir, project, commands, hooks, agents } => {
+            cmd_approve(&config, names, all, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Reject { names, reason, dir, project, commands, hooks, agents } => {
+            cmd_reject(&config, names, reason, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::History { slug, dir, project, commands, hooks, agents } => {
+            cmd_history(&config, &slug, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Tag { slug, tags, remove, dir, project, commands, hooks, agents } => {
+            cmd_tag(&config, &slug, &tags, remove, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Assign { slug, owner, dir, project, commands, hooks, agents } => {
+            cmd_assign(&config, &slug, owner, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Note { slug, text, clear, dir, project, commands, hooks, agents } => {
+            cmd_note(&config, &slug, text, clear, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Comment { slug, text, reply_to, dir, project, commands, hooks, agents } => cmd_comment(
+            &config,
+            &slug,
+            text,
+            reply_to,
+            dir,
+            DraftScope::from_flags(project, commands, hooks, agents),
+        ),
+        Command::Deploy { dir, undo: true, project, commands, hooks, agents, .. } => {
+            cmd_deploy_undo(&config, dir, DraftScope::from_flags(project, commands, hooks, agents))
+        }
+        Command::Deploy { names, approved, dir, format: Some(format), repo, .. } => {
+            cmd_deploy_rules(&config, names, approved, dir, format.into(), repo)
+        }
         Command::Deploy {
             names,
             approved,
             dir,
-        } => cmd_deploy(&config, names, approved, dir),
+            project,
+            as_skill,
+            commands,
+            hooks,
+            dry_run,
+            agents,
+            undo: false,
+            ..
+        } => match DraftScope::from_flags(project, commands, hooks, agents) {
+            DraftScope::Projects => cmd_deploy_project(&config, names, approved, dir, as_skill),
+            DraftScope::Commands => cmd_deploy_commands(&config, names, approved, dir),
+            DraftScope::Hooks => cmd_deploy_hooks(&config, names, approved, dir, dry_run),
+            DraftScope::Agents => cmd_deploy_agents(&config, names, approved, dir),
+            DraftScope::Skills => cmd_deploy(&config, names, approved, dir),
+        },
+        Command::Undeploy { names, target, reject, reason, archive, dir } => {
+            let opts = skill_miner::UndeployOptions {
+                target: target.map(|t| targets::parse(&t)),
+                reject,
+                archive,
+            };
+            cmd_undeploy(&config, &names, &opts, reason, dir)
+        }
+        Command::Target { slug, targets, auto, dir } => cmd_target(&config, &slug, &targets, auto, dir),
+        Command::Manifest {
+            action: ManifestCommand::Migrate { dir, dry_run },
+        } => cmd_manifest_migrate(&config, dir, dry_run),
+        Command::Unlock { dir } => cmd_unlock(&config, dir),
         Command::Prune {
             misc,
             rejected,
@@ -385,6 +920,13 @@ fn main() -> Result<()> {
             dir,
         ),
         Command::Graph { dir } => cmd_graph(&config, dir),
+        Command::Overlap { days, threshold, dir } => cmd_overlap(&config, days, threshold, dir),
+        Command::Score {
+            names,
+            explain,
+            days,
+            dir,
+        } => cmd_score(&config, names, explain, days, dir),
         Command::Import { bundle_path, dir } => cmd_import(&config, bundle_path, dir),
         Command::Verify { bundle_path } => cmd_verify(bundle_path),
         Command::Validate {
@@ -399,8 +941,12 @@ fn main() -> Result<()> {
             min_score,
             dry_run,
             refine,
+            sections,
+            undeploy,
             dir,
-        } => cmd_consolidate(&config, names, all, days, min_score, dry_run, refine, dir),
+        } => cmd_consolidate(
+            &config, names, all, days, min_score, dry_run, refine, sections, undeploy, dir,
+        ),
         Command::Today {
             days,
             project,
@@ -700,7 +1246,8 @@ fn cmd_extract(
 
 fn cmd_generate(config: &MineConfig, input: PathBuf, output: Option<PathBuf>) -> Result<()> {
     let json = std::fs::read_to_string(&input)?;
-    let clusters: Vec<skill_miner::DomainCluster> = serde_json::from_str(&json)?;
+    let all_clusters: Vec<skill_miner::DomainCluster> = serde_json::from_str(&json)?;
+    let (clusters, project_clusters) = project::split_clusters(&all_clusters);
 
     let mut drafts = generator::generate_skills(&clusters);
 
@@ -730,13 +1277,79 @@ fn cmd_generate(config: &MineConfig, input: PathBuf, output: Option<PathBuf>) ->
             println!("[{}] {}: {}", status, draft.name, util::truncate(&draft.description, 80));
         }
 
-        let content = generator::format_skill_md(draft);
-        let path = out_dir.join(format!("{}.md", draft.name));
-        std::fs::write(&path, content)?;
+        layout::write_skill(&out_dir, &draft.name, draft)?;
     }
 
     eprintln!("\nGenerated {} skill drafts in {}", drafts.len(), out_dir.display());
 
+    let project_drafts = project::generate_project_drafts(&project_clusters);
+    if !project_drafts.is_empty() {
+        project::write_project_drafts(&out_dir, &project_drafts, &project_clusters)?;
+        for pd in &project_drafts {
+            println!("[PROJECT] {} ({})", pd.slug, pd.project);
+        }
+        eprintln!(
+            "Generated {} project drafts in {}",
+            project_drafts.len(),
+            out_dir.join(project::PROJECTS_SUBDIR).display()
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_regenerate(config: &MineConfig, dir: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let store = patterns::load(&drafts_dir)?;
+    if store.patterns.is_empty() {
+        anyhow::bail!(
+            "no stored patterns in {} (run mine first)",
+            drafts_dir.join(patterns::PATTERNS_FILE).display()
+        );
+    }
+
+    let all_clusters = patterns::clusters(&store);
+    let (clusters, project_clusters) = project::split_clusters(&all_clusters);
+    let drafts = generator::generate_skills(&clusters);
+    let project_drafts = project::generate_project_drafts(&project_clusters);
+
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    for draft in &drafts {
+        let status = match manifest::find_entry(&mf, &draft.name) {
+            Some(entry) if entry.content_hash == layout::draft_hash(draft) => "UNCHANGED",
+            Some(_) => "UPDATE",
+            None => "NEW",
+        };
+        println!("[{}] {}", status, draft.name);
+    }
+    for pd in &project_drafts {
+        println!("[PROJECT] {} ({})", pd.slug, pd.project);
+    }
+
+    if dry_run {
+        eprintln!(
+            "\nDry run: {} drafts would be regenerated from {} stored patterns",
+            drafts.len() + project_drafts.len(),
+            store.patterns.len()
+        );
+        return Ok(());
+    }
+
+    for draft in &drafts {
+        layout::write_skill(&drafts_dir, &draft.name, draft)?;
+    }
+    manifest::merge_drafts(&mut mf, &drafts, &clusters);
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    if !project_drafts.is_empty() {
+        project::write_project_drafts(&drafts_dir, &project_drafts, &project_clusters)?;
+    }
+
+    eprintln!(
+        "\nRegenerated {} drafts from {} stored patterns in {} (no AI calls)",
+        drafts.len() + project_drafts.len(),
+        store.patterns.len(),
+        drafts_dir.display()
+    );
     Ok(())
 }
 
@@ -789,32 +1402,42 @@ fn cmd_mine(
             println!("[{}] {}", status, draft.name);
         }
     }
+    for pd in &result.project_drafts {
+        println!("[PROJECT] {} ({})", pd.slug, pd.project);
+    }
 
     if !dry_run {
-        // Deploy directly to skills dir (no draft stage)
-        std::fs::create_dir_all(&config.skills_dir)?;
+        // Deploy straight away (no review stage), through the drafts dir like `deploy`
         for draft in &result.drafts {
-            let content = generator::format_skill_md(draft);
-            let path = config.skills_dir.join(format!("{}.md", draft.name));
-            std::fs::write(&path, content)?;
+            layout::write_skill(&drafts_dir, &draft.name, draft)?;
+        }
+        for entry in mf.entries.iter_mut().filter(|e| e.deployed_hash.is_none()) {
+            // Earlier mine runs wrote skills without recording a deploy hash: the content
+            // they deployed is the one last hashed
+            if entry.status == DraftStatus::Deployed {
+                entry.deployed_hash = Some(entry.content_hash.clone());
+            }
         }
-
-        // Merge into manifest and mark as deployed
         miner::merge_into_manifest(&mut mf, &result.drafts, &result.clusters);
-        let now = chrono::Utc::now();
-        for draft in &result.drafts {
-            if let Some(entry) = mf.entries.iter_mut().find(|e| e.slug == draft.name) {
-                entry.status = skill_miner::DraftStatus::Deployed;
-                entry.deployed_at = Some(now);
+        let names: Vec<String> = result.drafts.iter().map(|d| d.name.clone()).collect();
+        let results = deployer::deploy_by_names(&drafts_dir, &config.skills_dir, &mut mf, &names)?;
+        for r in results.iter().filter(|r| !r.conflicts.is_empty()) {
+            println!("[merged] {} → {}", r.slug, r.target_path.display());
+            for section in &r.conflicts {
+                println!("  CONFLICT: {} (resolve the markers in the deployed file)", section);
             }
         }
-        manifest::write_manifest(&drafts_dir, &mf)?;
-
-        eprintln!(
-            "\nDeployed {} skills to {}",
-            result.drafts.len(),
-            config.skills_dir.display()
-        );
+        eprintln!("\n{}", deploy_summary(&mf, &config.skills_dir, &results));
+
+        // Project-scoped drafts are never auto-deployed: they await review
+        if !result.project_drafts.is_empty() {
+            project::write_project_drafts(&drafts_dir, &result.project_drafts, &result.project_clusters)?;
+            eprintln!(
+                "{} project drafts awaiting review (list --project --dir {})",
+                result.project_drafts.len(),
+                drafts_dir.display()
+            );
+        }
 
         // Auto-sync if requested
         if sync {
@@ -893,16 +1516,329 @@ fn cmd_mine(
     Ok(())
 }
 
-// ── State management commands ──
+fn cmd_mine_commands(
+    config: &MineConfig,
+    days: u32,
+    min_messages: usize,
+    min_support: usize,
+    max_len: usize,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    eprintln!("Scanning conversations (last {} days)...", days);
+    let conversations = parser::parse_all(&config.projects_dir, min_messages, days)?;
+    eprintln!("Found {} conversations", conversations.len());
 
-fn resolve_drafts_dir(_config: &MineConfig, dir: Option<PathBuf>) -> PathBuf {
-    dir.unwrap_or_else(|| PathBuf::from("./skill-drafts"))
-}
+    let sequences = commands::mine_sequences(&conversations, min_support, max_len);
+    if sequences.is_empty() {
+        eprintln!("No command sequence seen in >= {} conversations", min_support);
+        return Ok(());
+    }
 
-fn load_or_create_manifest(dir: &std::path::Path) -> Result<skill_miner::Manifest> {
-    match manifest::read_manifest(dir) {
-        Ok(m) => Ok(m),
-        Err(_) => {
+    let drafts = commands::generate_drafts(&sequences);
+    for d in &drafts {
+        let scope = d.sequence.project.as_deref().unwrap_or("global");
+        println!(
+            "[COMMAND] /{} ({} convs, {})  {}",
+            d.name,
+            d.sequence.support,
+            scope,
+            d.sequence.commands.join(" → ")
+        );
+    }
+
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = commands::write_drafts(&drafts_dir, &drafts)?;
+    eprintln!(
+        "\n{} slash-command drafts ({} in manifest), review with: list --commands --dir {}",
+        drafts.len(),
+        mf.entries.len(),
+        drafts_dir.display()
+    );
+
+    Ok(())
+}
+
+fn cmd_mine_hooks(
+    config: &MineConfig,
+    days: u32,
+    min_messages: usize,
+    min_support: usize,
+    min_ratio: f64,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    eprintln!("Scanning conversations (last {} days)...", days);
+    let conversations = parser::parse_all(&config.projects_dir, min_messages, days)?;
+    eprintln!("Found {} conversations", conversations.len());
+
+    let suggestions = hooks::mine_hooks(&conversations, min_support, min_ratio);
+    if suggestions.is_empty() {
+        eprintln!("No post-edit command habit found");
+        return Ok(());
+    }
+
+    let drafts = hooks::generate_drafts(&suggestions);
+    for d in &drafts {
+        let h = &d.suggestion;
+        let target = hooks::settings_path(&config.settings_path, h);
+        println!(
+            "[HOOK] {}  after {}: `{}` ({} convs, {:.0}% of edits) → {}",
+            d.slug,
+            h.matcher,
+            h.command,
+            h.support,
+            h.ratio * 100.0,
+            target.display()
+        );
+        let entry = serde_json::to_string_pretty(&h.settings_entry())?;
+        println!("  {}: {}", h.event, entry.replace('\n', "\n  "));
+    }
+
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = hooks::write_drafts(&drafts_dir, &drafts)?;
+    eprintln!(
+        "\n{} hook drafts ({} in manifest), review with: list --hooks --dir {}",
+        drafts.len(),
+        mf.entries.len(),
+        drafts_dir.display()
+    );
+
+    Ok(())
+}
+
+fn cmd_mine_agents(
+    config: &MineConfig,
+    days: u32,
+    min_messages: usize,
+    min_support: usize,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    eprintln!("Scanning conversations (last {} days)...", days);
+    let conversations = parser::parse_all(&config.projects_dir, min_messages, days)?;
+    eprintln!("Found {} conversations", conversations.len());
+
+    let patterns = agents::mine_agents(&conversations, min_support);
+    if patterns.is_empty() {
+        eprintln!("No kind of delegated work seen in >= {} conversations", min_support);
+        return Ok(());
+    }
+
+    let drafts = agents::generate_drafts(&patterns);
+    for d in &drafts {
+        let p = &d.pattern;
+        let tools = if p.tools.is_empty() {
+            "all tools".to_string()
+        } else {
+            p.tools.join(", ")
+        };
+        println!(
+            "[AGENT] {} ({} calls, {} convs)  tools: {}",
+            d.slug,
+            p.calls,
+            p.sources.len(),
+            tools
+        );
+    }
+
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = agents::write_drafts(&drafts_dir, &drafts)?;
+    eprintln!(
+        "\n{} subagent drafts ({} in manifest), review with: list --agents --dir {}",
+        drafts.len(),
+        mf.entries.len(),
+        drafts_dir.display()
+    );
+
+    Ok(())
+}
+
+// ── State management commands ──
+
+fn resolve_drafts_dir(_config: &MineConfig, dir: Option<PathBuf>) -> PathBuf {
+    dir.unwrap_or_else(|| PathBuf::from("./skill-drafts"))
+}
+
+/// Drafts directory a command modifies, and the name recorded in its lock.
+/// None for read-only commands.
+fn lock_target(config: &MineConfig, command: &Command) -> Option<(PathBuf, &'static str)> {
+    let drafts = |dir: &Option<PathBuf>| resolve_drafts_dir(config, dir.clone());
+    match command {
+        Command::Mine { output, dir, .. } => Some((
+            output
+                .clone()
+                .or_else(|| dir.clone())
+                .unwrap_or_else(|| config.skills_dir.join("drafts")),
+            "mine",
+        )),
+        Command::Generate { output, .. } => Some((drafts(output), "generate")),
+        Command::Regenerate { dir, dry_run: false } => Some((drafts(dir), "regenerate")),
+        Command::MineCommands { dir, .. } => Some((drafts(dir), "mine-commands")),
+        Command::MineHooks { dir, .. } => Some((drafts(dir), "mine-hooks")),
+        Command::MineAgents { dir, .. } => Some((drafts(dir), "mine-agents")),
+        Command::Approve { dir, .. } => Some((drafts(dir), "approve")),
+        Command::Reject { dir, .. } => Some((drafts(dir), "reject")),
+        Command::Deploy { dir, .. } => Some((drafts(dir), "deploy")),
+        Command::Undeploy { dir, .. } => Some((drafts(dir), "undeploy")),
+        Command::Status { dir, adopt: true, .. } => Some((drafts(dir), "status --adopt")),
+        Command::Target { targets, auto, dir, .. } if !targets.is_empty() || *auto => {
+            Some((drafts(dir), "target"))
+        }
+        Command::Rollback { dir, .. } => Some((drafts(dir), "rollback")),
+        Command::Tag { dir, .. } => Some((drafts(dir), "tag")),
+        Command::Assign { dir, .. } => Some((drafts(dir), "assign")),
+        Command::Note { text: Some(_), dir, .. } | Command::Note { clear: true, dir, .. } => {
+            Some((drafts(dir), "note"))
+        }
+        Command::Comment { text: Some(_), dir, .. } => Some((drafts(dir), "comment")),
+        Command::Forget { dir, .. } => Some((drafts(dir), "forget")),
+        Command::Prune { dir, .. } => Some((drafts(dir), "prune")),
+        Command::Import { dir, .. } => Some((drafts(dir), "import")),
+        Command::Consolidate { dir, .. } => Some((drafts(dir), "consolidate")),
+        Command::Manifest {
+            action: ManifestCommand::Migrate { dir, dry_run: false },
+        } => Some((drafts(dir), "manifest migrate")),
+        _ => None,
+    }
+}
+
+fn cmd_manifest_migrate(config: &MineConfig, dir: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let dirs = [
+        drafts_dir.clone(),
+        drafts_dir.join(project::PROJECTS_SUBDIR),
+        drafts_dir.join(commands::COMMANDS_SUBDIR),
+        drafts_dir.join(hooks::HOOKS_SUBDIR),
+        drafts_dir.join(agents::AGENTS_SUBDIR),
+    ];
+    let mut found = 0;
+    let mut pending = 0;
+    for dir in dirs.iter().filter(|d| d.join("manifest.toml").exists()) {
+        found += 1;
+        let path = dir.join("manifest.toml");
+        match manifest::migrate(dir, dry_run)? {
+            None => println!(
+                "{}: up to date (schema v{})",
+                path.display(),
+                manifest::SCHEMA_VERSION
+            ),
+            Some(report) => {
+                pending += 1;
+                let verb = if dry_run { "would migrate" } else { "migrated" };
+                println!("{}: {} v{} → v{}", path.display(), verb, report.from, report.to);
+                for change in &report.changes {
+                    println!("  {}", change);
+                }
+                if let Some(ref backup) = report.backup {
+                    println!("  backup: {}", backup.display());
+                }
+            }
+        }
+    }
+    if found == 0 {
+        println!("No manifests in {}", drafts_dir.display());
+    } else if dry_run && pending > 0 {
+        println!("\nDry run: nothing written. Re-run without --dry-run to migrate.");
+    }
+    Ok(())
+}
+
+fn cmd_unlock(config: &MineConfig, dir: Option<PathBuf>) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let existed = drafts_dir.join(lock::LOCK_FILE).exists();
+    match lock::break_lock(&drafts_dir)? {
+        Some(holder) => println!(
+            "Removed lock of pid {} ({}) from {}",
+            holder.pid,
+            holder.command,
+            holder.acquired_at.format("%Y-%m-%d %H:%M:%S")
+        ),
+        None if existed => println!("Removed unreadable lock from {}", drafts_dir.display()),
+        None => println!("{} is not locked", drafts_dir.display()),
+    }
+    Ok(())
+}
+
+/// Which set of drafts a lifecycle command works on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DraftScope {
+    /// Global skills (`<drafts>/manifest.toml`)
+    Skills,
+    /// Project-scoped drafts (`<drafts>/projects/`)
+    Projects,
+    /// Slash-command drafts (`<drafts>/commands/`)
+    Commands,
+    /// Hook suggestions (`<drafts>/hooks/`)
+    Hooks,
+    /// Subagent drafts (`<drafts>/agents/`)
+    Agents,
+}
+
+impl DraftScope {
+    fn from_flags(project: bool, commands: bool, hooks: bool, agents: bool) -> Self {
+        if project {
+            DraftScope::Projects
+        } else if commands {
+            DraftScope::Commands
+        } else if hooks {
+            DraftScope::Hooks
+        } else if agents {
+            DraftScope::Agents
+        } else {
+            DraftScope::Skills
+        }
+    }
+}
+
+/// Drafts directory and manifest for the given scope.
+fn open_manifest(
+    config: &MineConfig,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<(PathBuf, skill_miner::Manifest)> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    match scope {
+        DraftScope::Projects => {
+            let projects_dir = drafts_dir.join(project::PROJECTS_SUBDIR);
+            if !projects_dir.exists() {
+                anyhow::bail!("no project drafts in {}", projects_dir.display());
+            }
+            let mf = project::load_manifest(&drafts_dir)?;
+            Ok((projects_dir, mf))
+        }
+        DraftScope::Commands => {
+            let commands_dir = drafts_dir.join(commands::COMMANDS_SUBDIR);
+            if !commands_dir.exists() {
+                anyhow::bail!("no slash-command drafts in {}", commands_dir.display());
+            }
+            let mf = commands::load_manifest(&drafts_dir)?;
+            Ok((commands_dir, mf))
+        }
+        DraftScope::Hooks => {
+            let hooks_dir = drafts_dir.join(hooks::HOOKS_SUBDIR);
+            if !hooks_dir.exists() {
+                anyhow::bail!("no hook drafts in {}", hooks_dir.display());
+            }
+            let mf = hooks::load_manifest(&drafts_dir)?;
+            Ok((hooks_dir, mf))
+        }
+        DraftScope::Agents => {
+            let agents_dir = drafts_dir.join(agents::AGENTS_SUBDIR);
+            if !agents_dir.exists() {
+                anyhow::bail!("no subagent drafts in {}", agents_dir.display());
+            }
+            let mf = agents::load_manifest(&drafts_dir)?;
+            Ok((agents_dir, mf))
+        }
+        DraftScope::Skills => {
+            let mf = load_or_create_manifest(&drafts_dir)?;
+            Ok((drafts_dir, mf))
+        }
+    }
+}
+
+fn load_or_create_manifest(dir: &std::path::Path) -> Result<skill_miner::Manifest> {
+    match manifest::read_manifest(dir) {
+        Ok(m) => Ok(m),
+        Err(_) => {
             eprintln!("No manifest.toml found, scanning .md files...");
             let m = manifest::create_from_directory(dir)?;
             manifest::write_manifest(dir, &m)?;
@@ -911,14 +1847,27 @@ fn load_or_create_manifest(dir: &std::path::Path) -> Result<skill_miner::Manifes
     }
 }
 
-fn cmd_list(config: &MineConfig, dir: Option<PathBuf>) -> Result<()> {
-    let drafts_dir = resolve_drafts_dir(config, dir);
-    let mf = load_or_create_manifest(&drafts_dir)?;
+fn cmd_list(
+    config: &MineConfig,
+    dir: Option<PathBuf>,
+    tag: Option<&str>,
+    owner: Option<&str>,
+    scope: DraftScope,
+) -> Result<()> {
+    let (_, mut mf) = open_manifest(config, dir, scope)?;
+    mf.entries.retain(|e| {
+        tag.is_none_or(|t| e.meta.tags.iter().any(|x| x == t))
+            && owner.is_none_or(|o| e.meta.owner.as_deref() == Some(o))
+    });
 
-    println!(
-        "=== Skill Drafts ({} total) ===\n",
-        mf.entries.len()
-    );
+    let title = match scope {
+        DraftScope::Skills => "Skill Drafts",
+        DraftScope::Projects => "Project Drafts",
+        DraftScope::Commands => "Slash-Command Drafts",
+        DraftScope::Hooks => "Hook Drafts",
+        DraftScope::Agents => "Subagent Drafts",
+    };
+    println!("=== {} ({} total) ===\n", title, mf.entries.len());
 
     // Sort: deployed first, then approved, then draft, then rejected
     let mut entries: Vec<_> = mf.entries.iter().collect();
@@ -930,7 +1879,8 @@ fn cmd_list(config: &MineConfig, dir: Option<PathBuf>) -> Result<()> {
     });
 
     for e in &entries {
-        let deployed_info = if let Some(dt) = e.deployed_at {
+        let last_deploy = targets::of(e).iter().filter_map(|t| targets::deployed_at(e, t)).max();
+        let deployed_info = if let Some(dt) = last_deploy {
             format!("  deployed: {}", dt.format("%Y-%m-%d"))
         } else {
             String::new()
@@ -943,112 +1893,899 @@ fn cmd_list(config: &MineConfig, dir: Option<PathBuf>) -> Result<()> {
             Some(f) => format!("  fires: {}", f),
             None => String::new(),
         };
-        println!(
-            "[{:9}] {:<20} {:<12} {} patterns{}{}{}",
-            e.status.to_string(),
-            e.slug,
-            e.domain,
-            e.pattern_count,
-            score_info,
-            fire_info,
-            deployed_info
-        );
+        let project_info = match e.project {
+            Some(ref p) => format!("  project: {}", p),
+            None => String::new(),
+        };
+        let mut review_info = String::new();
+        if let Some(ref owner) = e.meta.owner {
+            review_info.push_str(&format!("  owner: {}", owner));
+        }
+        if !e.meta.tags.is_empty() {
+            review_info.push_str(&format!("  tags: {}", e.meta.tags.join(",")));
+        }
+        if !e.meta.comments.is_empty() {
+            review_info.push_str(&format!("  comments: {}", e.meta.comments.len()));
+        }
+        println!(
+            "[{:9}] {:<20} {:<12} {} patterns{}{}{}{}{}",
+            e.status.to_string(),
+            e.slug,
+            e.domain,
+            e.pattern_count,
+            score_info,
+            fire_info,
+            deployed_info,
+            project_info,
+            review_info
+        );
+        if let Some(ref notes) = e.meta.notes {
+            println!("            note: {}", util::truncate(notes, 100));
+        }
+        if !e.targets.is_empty() {
+            let states: Vec<String> = targets::of(e)
+                .iter()
+                .map(|t| match targets::deployed_at(e, t) {
+                    Some(dt) => format!("{} (deployed {})", t, dt.format("%Y-%m-%d")),
+                    None => format!("{} (not deployed)", t),
+                })
+                .collect();
+            let pinned = if e.targets_pinned { " [pinned]" } else { "" };
+            println!("            targets{}: {}", pinned, states.join(", "));
+        }
+        for x in &e.exports {
+            println!(
+                "            {}: {} (exported {})",
+                x.format,
+                x.repo,
+                x.deployed_at.format("%Y-%m-%d")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_diff(
+    config: &MineConfig,
+    name: Option<String>,
+    dir: Option<PathBuf>,
+    sections: bool,
+    patch: Option<PathBuf>,
+    target: Option<String>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = load_or_create_manifest(&drafts_dir)?;
+    let target = target.map(|t| targets::parse(&t));
+
+    let slugs = match name {
+        Some(slug) => vec![slug],
+        // Diff all
+        None => mf.entries.iter().map(|e| e.slug.clone()).collect(),
+    };
+
+    let mut unified = String::new();
+    for slug in &slugs {
+        let entry_targets = match manifest::find_entry(&mf, slug) {
+            Some(entry) => targets::of(entry),
+            None => vec![targets::GLOBAL.to_string()],
+        };
+        let selected: Vec<&String> = entry_targets
+            .iter()
+            .filter(|t| target.as_ref().is_none_or(|want| *want == **t))
+            .collect();
+        for (i, t) in selected.iter().enumerate() {
+            let skills_dir = targets::dir(&config.skills_dir, t);
+            if entry_targets.len() > 1 || **t != targets::GLOBAL {
+                println!("@ {}", t);
+            }
+            let result = deployer::diff_skill(&drafts_dir, &skills_dir, slug, sections)?;
+            println!("{}", result);
+            // A patch applies to one skills dir: the first selected target
+            if patch.is_some() && i == 0 {
+                unified.push_str(&deployer::skill_patch(&drafts_dir, &skills_dir, slug)?);
+            }
+        }
+    }
+
+    if let Some(path) = patch {
+        util::write_atomic(&path, unified.as_bytes())?;
+        eprintln!("Wrote patch to {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn cmd_status(
+    config: &MineConfig,
+    names: &[String],
+    adopt: bool,
+    target: Option<String>,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let target = target.map(|t| targets::parse(&t));
+
+    let drifts = drift::detect(&drafts_dir, &config.skills_dir, &config.commands_dir, &mf)?;
+    let drifts: Vec<SkillDrift> = drifts
+        .into_iter()
+        .filter(|d| names.is_empty() || names.contains(&d.slug))
+        .filter(|d| target.as_ref().is_none_or(|t| *t == d.target))
+        .collect();
+    if drifts.is_empty() {
+        println!("Deployed skills match the manifest");
+        return Ok(());
+    }
+
+    let mut adopted: Vec<&str> = Vec::new();
+    for d in &drifts {
+        println!("[{:<8}] {:<20} {}", d.kind, d.slug, d.path.display());
+        if !adopt || d.kind == DriftKind::Missing {
+            continue;
+        }
+        if d.command {
+            eprintln!("  skip: slash command (edit its draft under commands/ and deploy --commands)");
+            continue;
+        }
+        // A skill modified in several targets is adopted from the first one
+        if adopted.contains(&d.slug.as_str()) {
+            eprintln!("  skip: already adopted from another target");
+            continue;
+        }
+        drift::adopt(&drafts_dir, &config.skills_dir, &mut mf, d)?;
+        adopted.push(&d.slug);
+        println!("  adopted into {}", layout::skill_md_path(&drafts_dir, &d.slug).display());
+    }
+
+    if adopt {
+        if !adopted.is_empty() {
+            manifest::write_manifest(&drafts_dir, &mf)?;
+        }
+        println!("\nAdopted {} skills", adopted.len());
+    } else {
+        let count = |kind| drifts.iter().filter(|d| d.kind == kind).count();
+        println!(
+            "\n{} modified, {} missing, {} unknown",
+            count(DriftKind::Modified),
+            count(DriftKind::Missing),
+            count(DriftKind::Unknown)
+        );
+        if count(DriftKind::Modified) + count(DriftKind::Unknown) > 0 {
+            println!("Use `status --adopt [NAMES...]` to copy them back into the drafts.");
+        }
+        if count(DriftKind::Missing) > 0 {
+            println!("Use `deploy` to restore missing skills or `undeploy` to forget them.");
+        }
+    }
+    Ok(())
+}
+
+fn cmd_diff_versions(
+    config: &MineConfig,
+    slug: &str,
+    dir: Option<PathBuf>,
+    from: Option<String>,
+    to: Option<String>,
+    patch: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = load_or_create_manifest(&drafts_dir)?;
+    let entry = manifest::find_entry(&mf, slug)
+        .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+    let latest = entry.versions.len();
+    if latest == 0 {
+        anyhow::bail!("no stored versions of {}", slug);
+    }
+    let to = to.unwrap_or_else(|| format!("v{}", latest));
+    let from = from.unwrap_or_else(|| format!("v{}", latest.saturating_sub(1).max(1)));
+    let unified = versions::diff(&drafts_dir, entry, &from, &to)?;
+    print!("{}", unified);
+    if let Some(path) = patch {
+        util::write_atomic(&path, unified.as_bytes())?;
+        eprintln!("Wrote patch to {}", path.display());
+    }
+    Ok(())
+}
+
+fn cmd_versions(config: &MineConfig, slug: &str, dir: Option<PathBuf>) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = load_or_create_manifest(&drafts_dir)?;
+    let entry = manifest::find_entry(&mf, slug)
+        .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+    let current = if layout::exists(&drafts_dir, slug) {
+        Some(layout::hash_skill(&drafts_dir, slug)?)
+    } else {
+        None
+    };
+
+    println!("=== Versions: {} ({}) ===\n", slug, entry.versions.len());
+    for (i, version) in entry.versions.iter().enumerate() {
+        let mut marks = Vec::new();
+        if i + 1 == entry.versions.len() && current.as_deref() == Some(version.hash.as_str()) {
+            marks.push("current");
+        }
+        if entry.status == DraftStatus::Deployed
+            && targets::of(entry)
+                .iter()
+                .any(|t| targets::deployed_hash(entry, t) == Some(version.hash.as_str()))
+        {
+            marks.push("deployed");
+        }
+        let marks = if marks.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", marks.join(", "))
+        };
+        println!(
+            "v{:<3} {}  {}{}",
+            i + 1,
+            version.at.format("%Y-%m-%d %H:%M:%S"),
+            versions::short(&version.hash),
+            marks
+        );
+    }
+    let recorded = entry.versions.last().map(|v| v.hash.as_str());
+    if current.is_some() && recorded != current.as_deref() {
+        println!("\n(the draft has unrecorded edits; they are stored on the next manifest update)");
+    }
+    Ok(())
+}
+
+fn cmd_rollback(config: &MineConfig, slug: &str, version: &str, dir: Option<PathBuf>) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let number = versions::rollback(&drafts_dir, &mut mf, slug, version)?;
+    println!("Restored {} to v{}", slug, number);
+
+    let deployed = manifest::find_entry(&mf, slug).is_some_and(|e| e.status == DraftStatus::Deployed);
+    if deployed {
+        manifest::annotate(
+            &mut mf,
+            slug,
+            Some(AuditAction::RolledBack),
+            &format!("rollback to v{}, redeployed", number),
+        );
+        // The restored version replaces deployed copies as is, hand edits included.
+        // The deploy writes the manifest.
+        let names = [slug.to_string()];
+        let results = deployer::deploy_replacing(&drafts_dir, &config.skills_dir, &mut mf, &names)?;
+        for r in &results {
+            println!("[redeployed] {} → {}", r.slug, r.target_path.display());
+        }
+    } else {
+        manifest::write_manifest(&drafts_dir, &mf)?;
+    }
+    Ok(())
+}
+
+fn cmd_forget(config: &MineConfig, target: &str, dir: Option<PathBuf>) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let target = forget::ForgetTarget::parse(target);
+    let report = forget::forget(&drafts_dir, &mut mf, &target)?;
+
+    println!("Forgot {} ({} conversations)", target, report.conversations.len());
+    for id in &report.conversations {
+        println!("  {}", id);
+    }
+    println!(
+        "Patterns: {} removed, {} trimmed",
+        report.dropped_patterns, report.trimmed_patterns
+    );
+
+    // Deployed copies must not keep the forgotten content either
+    let mut redeploy = Vec::new();
+    for slug in &report.regenerated {
+        println!("[regenerated] {}", slug);
+        let Some(entry) = manifest::find_entry(&mf, slug) else { continue };
+        // Redeploying syncs the folder, so dropped supporting files go too
+        if entry.status == DraftStatus::Deployed {
+            redeploy.push(slug.clone());
+        }
+    }
+    // One transaction for redeploys and removed copies; it writes the manifest
+    let results = deployer::deploy_and_remove(
+        &drafts_dir,
+        &config.skills_dir,
+        &mut mf,
+        &redeploy,
+        &report.removed,
+    )?;
+    for r in &results {
+        println!("[redeployed] {} → {}", r.slug, r.target_path.display());
+        for section in &r.conflicts {
+            println!("  CONFLICT: {} (resolve the markers in the deployed file)", section);
+        }
+    }
+    for entry in &report.removed {
+        if entry.status == DraftStatus::Deployed {
+            println!("[removed] {} (deployed copy deleted)", entry.slug);
+        } else {
+            println!("[removed] {}", entry.slug);
+        }
+    }
+    for slug in &report.project_regenerated {
+        println!("[regenerated] {} (project)", slug);
+    }
+    for slug in &report.project_removed {
+        println!("[removed] {} (project)", slug);
+    }
+
+    if !report.project_regenerated.is_empty() {
+        eprintln!("Project drafts already applied to a repository need deploy --project again.");
+    }
+    Ok(())
+}
+
+fn cmd_approve(
+    config: &MineConfig,
+    names: Vec<String>,
+    all: bool,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    let (drafts_dir, mut mf) = open_manifest(config, dir, scope)?;
+
+    let slugs: Vec<String> = if all {
+        mf.entries
+            .iter()
+            .filter(|e| e.status == DraftStatus::Draft)
+            .map(|e| e.slug.clone())
+            .collect()
+    } else {
+        names
+    };
+
+    for slug in &slugs {
+        match manifest::update_status(&mut mf, slug, DraftStatus::Approved) {
+            Ok(()) => println!("[approved] {}", slug),
+            Err(e) => eprintln!("  skip {}: {}", slug, e),
+        }
+    }
+
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    eprintln!("\nApproved {} drafts", slugs.len());
+
+    Ok(())
+}
+
+fn cmd_reject(
+    config: &MineConfig,
+    names: Vec<String>,
+    reason: Option<String>,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    let (drafts_dir, mut mf) = open_manifest(config, dir, scope)?;
+
+    for slug in &names {
+        if manifest::find_entry(&mf, slug).is_some_and(|e| e.status == DraftStatus::Deployed) {
+            eprintln!("  skip {}: deployed (use `undeploy --reject` to remove it)", slug);
+            continue;
+        }
+        match manifest::update_status(&mut mf, slug, DraftStatus::Rejected) {
+            Ok(()) => {
+                if let Some(ref reason) = reason {
+                    manifest::annotate(&mut mf, slug, None, reason);
+                }
+                println!("[rejected] {}", slug)
+            }
+            Err(e) => eprintln!("  skip {}: {}", slug, e),
+        }
+    }
+
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    Ok(())
+}
+
+fn cmd_history(
+    config: &MineConfig,
+    slug: &str,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    let (drafts_dir, mf) = open_manifest(config, dir, scope)?;
+    let events = audit::history(&drafts_dir, slug)?;
+    if events.is_empty() && manifest::find_entry(&mf, slug).is_none() {
+        anyhow::bail!("no draft or history for {}", slug);
+    }
+
+    println!("=== History: {} ({} events) ===\n", slug, events.len());
+    for event in &events {
+        println!("{}", audit::format_event(event));
+    }
+    if let Some(entry) = manifest::find_entry(&mf, slug) {
+        println!("\ncurrent status: {}", entry.status);
+    }
+
+    Ok(())
+}
+
+/// Load the manifest of a scope, edit one entry's review metadata, and write it back.
+fn edit_meta(
+    config: &MineConfig,
+    slug: &str,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+    edit: impl FnOnce(&mut skill_miner::DraftMeta) -> Result<String>,
+) -> Result<()> {
+    let (drafts_dir, mut mf) = open_manifest(config, dir, scope)?;
+    let entry = manifest::find_entry_mut(&mut mf, slug)
+        .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+    let message = edit(&mut entry.meta)?;
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    println!("{}", message);
+    Ok(())
+}
+
+fn cmd_tag(
+    config: &MineConfig,
+    slug: &str,
+    tags: &[String],
+    remove: bool,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    edit_meta(config, slug, dir, scope, |meta| {
+        if remove {
+            meta.tags.retain(|t| !tags.contains(t));
+        } else {
+            meta.add_tags(tags);
+        }
+        Ok(format!("[tags] {}: {}", slug, meta.tags.join(", ")))
+    })
+}
+
+fn cmd_assign(
+    config: &MineConfig,
+    slug: &str,
+    owner: Option<String>,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    edit_meta(config, slug, dir, scope, |meta| {
+        meta.owner = owner;
+        Ok(match meta.owner {
+            Some(ref o) => format!("[assigned] {} → {}", slug, o),
+            None => format!("[unassigned] {}", slug),
+        })
+    })
+}
+
+fn cmd_note(
+    config: &MineConfig,
+    slug: &str,
+    text: Option<String>,
+    clear: bool,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    if text.is_none() && !clear {
+        let (_, mf) = open_manifest(config, dir, scope)?;
+        let entry = manifest::find_entry(&mf, slug)
+            .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+        println!("{}", entry.meta.notes.as_deref().unwrap_or("(no notes)"));
+        return Ok(());
+    }
+    edit_meta(config, slug, dir, scope, |meta| {
+        meta.notes = text.filter(|t| !t.trim().is_empty());
+        Ok(match meta.notes {
+            Some(_) => format!("[noted] {}", slug),
+            None => format!("[notes cleared] {}", slug),
+        })
+    })
+}
+
+fn cmd_comment(
+    config: &MineConfig,
+    slug: &str,
+    text: Option<String>,
+    reply_to: Option<usize>,
+    dir: Option<PathBuf>,
+    scope: DraftScope,
+) -> Result<()> {
+    let Some(text) = text else {
+        let (_, mf) = open_manifest(config, dir, scope)?;
+        let entry = manifest::find_entry(&mf, slug)
+            .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+        println!("=== Comments: {} ({}) ===\n", slug, entry.meta.comments.len());
+        print_comments(&entry.meta.comments, None, 0);
+        return Ok(());
+    };
+    edit_meta(config, slug, dir, scope, |meta| {
+        let id = meta
+            .add_comment(&text, audit::actor(), reply_to)
+            .map_err(|e| anyhow::anyhow!("{}: {}", slug, e))?;
+        Ok(format!("[comment #{}] {}", id, slug))
+    })
+}
+
+/// Print the replies to `parent` (None = top-level comments) as an indented thread.
+fn print_comments(comments: &[skill_miner::ReviewComment], parent: Option<usize>, depth: usize) {
+    for c in comments.iter().filter(|c| c.reply_to == parent) {
+        println!(
+            "{}#{} {} {}: {}",
+            "  ".repeat(depth),
+            c.id,
+            c.at.format("%Y-%m-%d %H:%M"),
+            c.author.as_deref().unwrap_or("?"),
+            c.text
+        );
+        print_comments(comments, Some(c.id), depth + 1);
+    }
+}
+
+fn cmd_target(
+    config: &MineConfig,
+    slug: &str,
+    new_targets: &[String],
+    auto: bool,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let entry = manifest::find_entry_mut(&mut mf, slug)
+        .ok_or_else(|| anyhow::anyhow!("draft not found: {}", slug))?;
+
+    if new_targets.is_empty() && !auto {
+        let pinned = if entry.targets_pinned { " (pinned)" } else { " (inferred)" };
+        println!("=== Targets: {}{} ===\n", slug, pinned);
+        for t in targets::of(entry) {
+            let state = match targets::deployed_at(entry, &t) {
+                Some(dt) => format!("deployed {}", dt.format("%Y-%m-%d %H:%M")),
+                None => "not deployed".to_string(),
+            };
+            println!("{:<40} {}  → {}", t, state, targets::dir(&config.skills_dir, &t).display());
+        }
+        return Ok(());
+    }
+
+    let paths: Vec<String> = if auto {
+        vec![targets::GLOBAL.to_string()]
+    } else {
+        new_targets.iter().map(|t| targets::parse(t)).collect()
+    };
+    for t in targets::of(entry) {
+        if !paths.contains(&t) && targets::deployed_hash(entry, &t).is_some() {
+            eprintln!("warn: {} is still deployed to {}; remove that copy by hand", slug, t);
+        }
+    }
+    targets::set(entry, &paths);
+    entry.targets_pinned = !auto;
+    println!("[targets] {}: {}", slug, targets::of(entry).join(", "));
+    let reason = format!("targets: {}", targets::of(entry).join(", "));
+    manifest::annotate(&mut mf, slug, None, &reason);
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    Ok(())
+}
+
+fn cmd_undeploy(
+    config: &MineConfig,
+    names: &[String],
+    opts: &skill_miner::UndeployOptions,
+    reason: Option<String>,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    // With --reject the status change is recorded as "rejected"
+    let action = (!opts.reject).then_some(AuditAction::Undeployed);
+    let reason = reason.unwrap_or_else(|| "undeploy".to_string());
+    for slug in names {
+        manifest::annotate(&mut mf, slug, action, &reason);
+    }
+    let results = deployer::undeploy(&drafts_dir, &config.skills_dir, &mut mf, names, opts)?;
+
+    for r in &results {
+        if r.removed.is_empty() {
+            println!("[not deployed] {} ({})", r.slug, r.status);
+        }
+        for path in &r.removed {
+            println!("[removed] {} ← {}", r.slug, path.display());
+        }
+        for path in &r.archived {
+            println!("  archived to {}", path.display());
+        }
+        if !r.removed.is_empty() {
+            println!("  status: {}", r.status);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_deploy_rules(
+    config: &MineConfig,
+    names: Vec<String>,
+    approved: bool,
+    dir: Option<PathBuf>,
+    format: AgentFormat,
+    repo: Option<String>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+
+    let slugs: Vec<String> = if approved {
+        // Reviewed drafts: approved, or already deployed as Claude skills
+        mf.entries
+            .iter()
+            .filter(|e| matches!(e.status, DraftStatus::Approved | DraftStatus::Deployed))
+            .map(|e| e.slug.clone())
+            .collect()
+    } else if !names.is_empty() {
+        names
+    } else {
+        eprintln!("Specify skill names or use --approved");
+        return Ok(());
+    };
+    let repo = repo.map(|r| targets::parse(&r));
+    if repo.as_deref() == Some(targets::GLOBAL) {
+        anyhow::bail!("--repo needs a repository path; rule formats have no global location");
+    }
+
+    let results = rules::export(&drafts_dir, &mut mf, &slugs, format, repo.as_deref())?;
+    for r in &results {
+        let action = if r.was_update { "updated" } else { "created" };
+        println!("[{}] {} → {}", action, r.slug, r.path.display());
+        if r.overwrote_edits {
+            println!("  hand edits overwritten (`deploy --undo` restores them)");
+        }
+        manifest::annotate(
+            &mut mf,
+            &r.slug,
+            Some(AuditAction::Exported),
+            &format!("{} → {}", format, r.path.display()),
+        );
+    }
+
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    eprintln!("\nExported {} skills as {}", results.len(), format);
+    Ok(())
+}
+
+fn cmd_deploy_undo(config: &MineConfig, dir: Option<PathBuf>, scope: DraftScope) -> Result<()> {
+    // Each scope journals its deploys next to its own manifest
+    let (drafts_dir, mut mf) = open_manifest(config, dir, scope)?;
+    let Some(journal) = transaction::undo(&drafts_dir, &mut mf)? else {
+        println!("Nothing to undo");
+        return Ok(());
+    };
+    for entry in &journal.entries {
+        manifest::annotate(&mut mf, &entry.slug, None, &format!("deploy --undo {}", journal.id));
+        println!("[restored] {}", entry.slug);
+    }
+    manifest::write_manifest(&drafts_dir, &mf)?;
+    eprintln!(
+        "\nUndid deploy {} ({} paths restored)",
+        journal.started_at.format("%Y-%m-%d %H:%M:%S"),
+        journal.backups.len()
+    );
+    Ok(())
+}
+
+/// Deploy summary: how many skills were written to which skills dirs.
+fn deploy_summary(
+    mf: &Manifest,
+    skills_dir: &std::path::Path,
+    results: &[DeployResult],
+) -> String {
+    let dirs: std::collections::BTreeSet<PathBuf> = results
+        .iter()
+        .filter_map(|r| manifest::find_entry(mf, &r.slug))
+        .flat_map(targets::of)
+        .map(|t| targets::dir(skills_dir, &t))
+        .collect();
+    let dirs: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+    match dirs.as_slice() {
+        [] => format!("Deployed {} skills", results.len()),
+        [dir] => format!("Deployed {} skills to {}", results.len(), dir),
+        _ => format!(
+            "Deployed {} skill copies to {} locations: {}",
+            results.len(),
+            dirs.len(),
+            dirs.join(", ")
+        ),
+    }
+}
+
+fn cmd_deploy(
+    config: &MineConfig,
+    names: Vec<String>,
+    approved: bool,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+
+    let results = if approved {
+        deployer::deploy_approved(&drafts_dir, &config.skills_dir, &mut mf)?
+    } else if !names.is_empty() {
+        deployer::deploy_by_names(&drafts_dir, &config.skills_dir, &mut mf, &names)?
+    } else {
+        eprintln!("Specify skill names or use --approved");
+        return Ok(());
+    };
+
+    for r in &results {
+        let action = match (r.merged, r.was_update) {
+            (true, _) => "merged",
+            (false, true) => "updated",
+            (false, false) => "created",
+        };
+        println!("[{}] {} → {}", action, r.slug, r.target_path.display());
+        for section in &r.conflicts {
+            println!("  CONFLICT: {} (resolve the markers in the deployed file)", section);
+        }
+    }
+
+    eprintln!("\n{}", deploy_summary(&mf, &config.skills_dir, &results));
+    let conflicted = results.iter().filter(|r| !r.conflicts.is_empty()).count();
+    if conflicted > 0 {
+        eprintln!("{} skills have merge conflicts with hand edits", conflicted);
     }
 
     Ok(())
 }
 
-fn cmd_diff(config: &MineConfig, name: Option<String>, dir: Option<PathBuf>) -> Result<()> {
-    let drafts_dir = resolve_drafts_dir(config, dir);
+fn cmd_deploy_project(
+    config: &MineConfig,
+    names: Vec<String>,
+    approved: bool,
+    dir: Option<PathBuf>,
+    as_skill: bool,
+) -> Result<()> {
+    let (projects_dir, mut mf) = open_manifest(config, dir, DraftScope::Projects)?;
+
+    let slugs: Vec<String> = if approved {
+        mf.entries
+            .iter()
+            .filter(|e| e.status == DraftStatus::Approved)
+            .map(|e| e.slug.clone())
+            .collect()
+    } else if !names.is_empty() {
+        names
+    } else {
+        eprintln!("Specify project draft names or use --approved");
+        return Ok(());
+    };
 
-    if let Some(slug) = name {
-        let result = deployer::diff_skill(&drafts_dir, &config.skills_dir, &slug)?;
-        println!("{}", result);
+    let output = if as_skill {
+        project::ProjectOutput::Skill
     } else {
-        // Diff all
-        let mf = load_or_create_manifest(&drafts_dir)?;
-        for entry in &mf.entries {
-            let result =
-                deployer::diff_skill(&drafts_dir, &config.skills_dir, &entry.slug)?;
-            println!("{}", result);
-        }
+        project::ProjectOutput::ClaudeMd
+    };
+    let results = project::deploy_project_drafts(&projects_dir, &mut mf, &slugs, output)?;
+
+    for r in &results {
+        let action = if r.was_update { "updated" } else { "created" };
+        println!("[{}] {} → {}", action, r.slug, r.target_path.display());
+    }
+
+    eprintln!("\nDeployed {} project drafts", results.len());
+    if results.len() < slugs.len() {
+        eprintln!("Skipped {} drafts that are not approved", slugs.len() - results.len());
     }
 
     Ok(())
 }
 
-fn cmd_approve(
+fn cmd_deploy_commands(
     config: &MineConfig,
     names: Vec<String>,
-    all: bool,
+    approved: bool,
     dir: Option<PathBuf>,
 ) -> Result<()> {
-    let drafts_dir = resolve_drafts_dir(config, dir);
-    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let drafts_dir = resolve_drafts_dir(config, dir.clone());
+    let (_, mut mf) = open_manifest(config, dir, DraftScope::Commands)?;
 
-    let slugs: Vec<String> = if all {
+    let slugs: Vec<String> = if approved {
         mf.entries
             .iter()
-            .filter(|e| e.status == DraftStatus::Draft)
+            .filter(|e| e.status == DraftStatus::Approved)
             .map(|e| e.slug.clone())
             .collect()
-    } else {
+    } else if !names.is_empty() {
         names
+    } else {
+        eprintln!("Specify slash-command draft names or use --approved");
+        return Ok(());
     };
 
-    for slug in &slugs {
-        match manifest::update_status(&mut mf, slug, DraftStatus::Approved) {
-            Ok(()) => println!("[approved] {}", slug),
-            Err(e) => eprintln!("  skip {}: {}", slug, e),
+    let results = commands::deploy_commands(&drafts_dir, &config.commands_dir, &mut mf, &slugs)?;
+
+    for r in &results {
+        let action = match (r.merged, r.was_update) {
+            (true, _) => "merged",
+            (false, true) => "updated",
+            (false, false) => "created",
+        };
+        println!("[{}] {} → {}", action, r.slug, r.target_path.display());
+        for section in &r.conflicts {
+            println!("  CONFLICT: {} (resolve the markers in the deployed file)", section);
         }
     }
 
-    manifest::write_manifest(&drafts_dir, &mf)?;
-    eprintln!("\nApproved {} drafts", slugs.len());
+    eprintln!("\nDeployed {} slash commands", results.len());
+    if results.len() < slugs.len() {
+        eprintln!("Skipped {} drafts that are not approved", slugs.len() - results.len());
+    }
 
     Ok(())
 }
 
-fn cmd_reject(config: &MineConfig, names: Vec<String>, dir: Option<PathBuf>) -> Result<()> {
-    let drafts_dir = resolve_drafts_dir(config, dir);
-    let mut mf = load_or_create_manifest(&drafts_dir)?;
+fn cmd_deploy_hooks(
+    config: &MineConfig,
+    names: Vec<String>,
+    approved: bool,
+    dir: Option<PathBuf>,
+    dry_run: bool,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir.clone());
+    let (_, mut mf) = open_manifest(config, dir, DraftScope::Hooks)?;
 
-    for slug in &names {
-        match manifest::update_status(&mut mf, slug, DraftStatus::Rejected) {
-            Ok(()) => println!("[rejected] {}", slug),
-            Err(e) => eprintln!("  skip {}: {}", slug, e),
+    let slugs: Vec<String> = if approved {
+        mf.entries
+            .iter()
+            .filter(|e| e.status == DraftStatus::Approved)
+            .map(|e| e.slug.clone())
+            .collect()
+    } else if !names.is_empty() {
+        names
+    } else {
+        eprintln!("Specify hook draft names or use --approved");
+        return Ok(());
+    };
+
+    if dry_run {
+        for p in hooks::preview(&drafts_dir, &config.settings_path, &slugs)? {
+            println!("=== {} ({} hooks added) ===", p.path.display(), p.added);
+            println!("{}", p.content);
         }
+        return Ok(());
     }
 
-    manifest::write_manifest(&drafts_dir, &mf)?;
+    let results = hooks::deploy_hooks(&drafts_dir, &config.settings_path, &mut mf, &slugs)?;
+
+    for r in &results {
+        let action = if r.was_update { "merged" } else { "created" };
+        println!("[{}] {} → {}", action, r.slug, r.target_path.display());
+    }
+
+    eprintln!("\nDeployed {} hooks (previous settings kept as settings.json.bak)", results.len());
+
     Ok(())
 }
 
-fn cmd_deploy(
+fn cmd_deploy_agents(
     config: &MineConfig,
     names: Vec<String>,
     approved: bool,
     dir: Option<PathBuf>,
 ) -> Result<()> {
-    let drafts_dir = resolve_drafts_dir(config, dir);
-    let mut mf = load_or_create_manifest(&drafts_dir)?;
+    let (agents_drafts, mut mf) = open_manifest(config, dir, DraftScope::Agents)?;
 
     let results = if approved {
-        deployer::deploy_approved(&drafts_dir, &config.skills_dir, &mut mf)?
+        deployer::deploy_approved(&agents_drafts, &config.agents_dir, &mut mf)?
     } else if !names.is_empty() {
-        deployer::deploy_by_names(&drafts_dir, &config.skills_dir, &mut mf, &names)?
+        deployer::deploy_by_names(&agents_drafts, &config.agents_dir, &mut mf, &names)?
     } else {
-        eprintln!("Specify skill names or use --approved");
+        eprintln!("Specify subagent draft names or use --approved");
         return Ok(());
     };
 
     for r in &results {
-        let action = if r.was_update { "updated" } else { "created" };
+        let action = match (r.merged, r.was_update) {
+            (true, _) => "merged",
+            (false, true) => "updated",
+            (false, false) => "created",
+        };
         println!("[{}] {} → {}", action, r.slug, r.target_path.display());
+        for section in &r.conflicts {
+            println!("  CONFLICT: {} (resolve the markers in the deployed file)", section);
+        }
     }
 
-    manifest::write_manifest(&drafts_dir, &mf)?;
-    eprintln!("\nDeployed {} skills to {}", results.len(), config.skills_dir.display());
+    eprintln!("\nDeployed {} subagents to {}", results.len(), config.agents_dir.display());
 
     Ok(())
 }
@@ -1322,7 +3059,20 @@ fn cmd_import(config: &MineConfig, bundle_path: PathBuf, dir: Option<PathBuf>) -
     if !result.conflicted.is_empty() {
         println!("Conflicted (saved as .imported.md):");
         for slug in &result.conflicted {
-            println!("  ! {}", slug);
+            let imported = format!("{}.imported", slug);
+            match (
+                layout::read_skill_md(&drafts_dir, slug),
+                layout::read_skill_md(&drafts_dir, &imported),
+            ) {
+                (Ok(ours), Ok(theirs)) => {
+                    let d = diff::compute(&ours, &theirs);
+                    println!("  ! {}: +{} lines, -{} lines", slug, d.added_count(), d.removed_count());
+                    for change in diff::sections(&ours, &theirs) {
+                        println!("      {}", change);
+                    }
+                }
+                _ => println!("  ! {}", slug),
+            }
         }
     }
 
@@ -1366,36 +3116,55 @@ fn cmd_import(config: &MineConfig, bundle_path: PathBuf, dir: Option<PathBuf>) -
     Ok(())
 }
 
-fn cmd_consolidate(
-    config: &MineConfig,
-    names: Vec<String>,
-    all: bool,
-    days: u32,
-    min_score: f64,
-    dry_run: bool,
-    refine: bool,
-    dir: Option<PathBuf>,
-) -> Result<()> {
+fn cmd_overlap(config: &MineConfig, days: u32, threshold: f64, dir: Option<PathBuf>) -> Result<()> {
     let drafts_dir = resolve_drafts_dir(config, dir);
-    let mut mf = load_or_create_manifest(&drafts_dir)?;
-
-    if mf.entries.is_empty() {
-        eprintln!("No drafts found in {}", drafts_dir.display());
+    let mf = load_or_create_manifest(&drafts_dir)?;
+    let skills = overlap::load_skills(&drafts_dir, &config.skills_dir, &mf)?;
+    if skills.len() < 2 {
+        eprintln!("Need at least two skills to compare ({} found)", skills.len());
         return Ok(());
     }
 
-    // Step 1: Parse chat history and extract skill invocations
-    eprintln!("[1/3] Scanning chat history (last {} days) for skill invocations...", days);
+    eprintln!("Scanning chat history (last {} days) for trigger prompts...", days);
     let conversations = parser::parse_all(&config.projects_dir, 1, days)?;
     let invocations = parser::extract_skill_invocations(&conversations);
-    eprintln!("  → {} conversations, {} skill invocations", conversations.len(), invocations.len());
+    let prompts = invocations.iter().filter(|i| i.trigger_context.is_some()).count();
+    eprintln!("  → {} skills, {} trigger prompts", skills.len(), prompts);
 
-    // Step 2: Score skills
-    eprintln!("[2/3] Scoring skills...");
+    let pairs = overlap::analyze(&skills, &invocations, threshold);
+    if pairs.is_empty() {
+        println!("No overlapping skills");
+        return Ok(());
+    }
+
+    for p in &pairs {
+        println!(
+            "[{:<6}] {} ↔ {}  description {:.2}  keywords {:.2}  collisions {}",
+            p.suggestion, p.a, p.b, p.description_similarity, p.keyword_similarity, p.collisions
+        );
+        if !p.shared_terms.is_empty() {
+            println!("  shared terms: {}", p.shared_terms.join(", "));
+        }
+        for example in &p.examples {
+            println!("  e.g. \"{}\"", util::truncate(example, 80));
+        }
+        match p.suggestion {
+            OverlapSuggestion::Merge => println!(
+                "  suggestion: keep one skill (reject the other, or fold its patterns in and regenerate)"
+            ),
+            OverlapSuggestion::Reword => println!(
+                "  suggestion: reword the descriptions so each says when it applies and not the shared terms"
+            ),
+        }
+    }
+    eprintln!("\n{} overlapping pairs", pairs.len());
+    Ok(())
+}
 
-    // Build minimal clusters from manifest entries for scoring
-    let clusters: Vec<skill_miner::DomainCluster> = mf
-        .entries
+/// Minimal clusters built from manifest entries for scoring: one pattern of frequency 1
+/// per pattern the draft was generated from.
+fn manifest_clusters(mf: &skill_miner::Manifest) -> Vec<skill_miner::DomainCluster> {
+    mf.entries
         .iter()
         .map(|e| {
             let patterns: Vec<skill_miner::KnowledgePattern> = (0..e.pattern_count)
@@ -1407,6 +3176,7 @@ fn cmd_consolidate(
                     source_ids: vec![],
                     frequency: 1,
                     skill_slug: None,
+                    project: None,
                 })
                 .collect();
             skill_miner::DomainCluster {
@@ -1415,9 +3185,143 @@ fn cmd_consolidate(
                 patterns,
             }
         })
-        .collect();
+        .collect()
+}
+
+fn cmd_score(
+    config: &MineConfig,
+    names: Vec<String>,
+    explain: Option<String>,
+    days: u32,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mf = load_or_create_manifest(&drafts_dir)?;
+    if mf.entries.is_empty() {
+        eprintln!("No drafts found in {}", drafts_dir.display());
+        return Ok(());
+    }
+    if let Some(ref slug) = explain {
+        if manifest::find_entry(&mf, slug).is_none() {
+            anyhow::bail!("no draft named {}", slug);
+        }
+    }
+
+    eprintln!("Scanning chat history (last {} days) for skill invocations...", days);
+    let conversations = parser::parse_all(&config.projects_dir, 1, days)?;
+    let invocations = parser::extract_skill_invocations(&conversations);
+    eprintln!("  → {} conversations, {} skill invocations", conversations.len(), invocations.len());
+
+    let weights = scorer::config();
+    let breakdowns = scorer::explain_skills(&invocations, &mf, &manifest_clusters(&mf), weights);
+
+    let Some(slug) = explain else {
+        for b in breakdowns
+            .iter()
+            .filter(|b| names.is_empty() || names.contains(&b.slug))
+        {
+            let inactive = b
+                .days_inactive
+                .map(|d| format!("{:.1}d", d))
+                .unwrap_or_else(|| "-".to_string());
+            println!(
+                "{:.3}  {:<30} fires {:<4} decay {:.2} (inactive {})",
+                b.score, b.slug, b.fire_count, b.decay, inactive
+            );
+        }
+        return Ok(());
+    };
+
+    let Some(b) = breakdowns.iter().find(|b| b.slug == slug) else {
+        return Ok(());
+    };
+    println!("{}", b.slug);
+    println!(
+        "  fires        {}/{} = {:.3} × {} = {:.3}",
+        b.fire_count,
+        b.max_fire_count,
+        b.fire_score,
+        weights.fire_weight,
+        weights.fire_weight * b.fire_score
+    );
+    println!(
+        "  patterns     {}/{} = {:.3} × {} = {:.3}",
+        b.pattern_freq_sum,
+        b.max_pattern_freq_sum,
+        b.pattern_score,
+        weights.pattern_weight,
+        weights.pattern_weight * b.pattern_score
+    );
+    println!("  base         {:.3}", b.base_score);
+    println!(
+        "  productivity {}/{} productive → {} + {} × {:.3} = ×{:.3}",
+        b.productive_count,
+        b.fire_count,
+        weights.productive_base,
+        weights.productive_weight,
+        b.productive_rate,
+        b.productive_multiplier
+    );
+    println!(
+        "  outcome      {:.3} mean over invocations → {} + {} × {:.3} = ×{:.3}",
+        b.outcome_rate,
+        weights.outcome_base,
+        weights.outcome_weight,
+        b.outcome_rate,
+        b.outcome_multiplier
+    );
+    match b.days_inactive {
+        Some(d) => {
+            let since = if b.last_invoked.is_some() {
+                "last invocation"
+            } else {
+                "deploy"
+            };
+            println!(
+                "  decay        {:.1} days since {}, half-life {} days → ×{:.3}",
+                d, since, weights.half_life_days, b.decay
+            );
+        }
+        None => println!("  decay        never invoked nor deployed → ×1.000"),
+    }
+    println!(
+        "  score        {:.3} × {:.3} × {:.3} × {:.3} = {:.3}",
+        b.base_score, b.productive_multiplier, b.outcome_multiplier, b.decay, b.score
+    );
+    Ok(())
+}
+
+fn cmd_consolidate(
+    config: &MineConfig,
+    names: Vec<String>,
+    all: bool,
+    days: u32,
+    min_score: f64,
+    dry_run: bool,
+    refine: bool,
+    sections: bool,
+    undeploy: bool,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let drafts_dir = resolve_drafts_dir(config, dir);
+    let mut mf = load_or_create_manifest(&drafts_dir)?;
+
+    if mf.entries.is_empty() {
+        eprintln!("No drafts found in {}", drafts_dir.display());
+        return Ok(());
+    }
+
+    // Step 1: Parse chat history and extract skill invocations
+    eprintln!("[1/3] Scanning chat history (last {} days) for skill invocations...", days);
+    let conversations = parser::parse_all(&config.projects_dir, 1, days)?;
+    let invocations = parser::extract_skill_invocations(&conversations);
+    eprintln!("  → {} conversations, {} skill invocations", conversations.len(), invocations.len());
+
+    // Step 2: Score skills
+    eprintln!("[2/3] Scoring skills...");
 
-    let scores = scorer::score_skills(&invocations, &mf, &clusters);
+    let clusters = manifest_clusters(&mf);
+    let scores = scorer::score_skills(&invocations, &mf, &clusters, scorer::config());
 
     // Build score lookup
     let score_map: HashMap<String, f64> = scores.iter().cloned().collect();
@@ -1460,15 +3364,23 @@ fn cmd_consolidate(
 
     let mut rejected_count = 0;
     let mut updated_count = 0;
+    // Deployed skills rejected for their score; undeployed (and rejected) at the end
+    let mut to_undeploy = Vec::new();
+    // Without --undeploy, deployed skills below the score keep their status and files
+    let mut kept_deployed = Vec::new();
 
     for slug in &target_slugs {
         let score = score_map.get(slug).copied().unwrap_or(0.0);
         let fires = fire_map.get(slug.as_str()).copied().unwrap_or(0);
+        let deployed =
+            manifest::find_entry(&mf, slug).is_some_and(|e| e.status == DraftStatus::Deployed);
 
-        let status_change = if score < min_score {
-            "→ rejected"
-        } else {
+        let status_change = if score >= min_score {
             ""
+        } else if deployed && !undeploy {
+            "→ kept (deployed)"
+        } else {
+            "→ rejected"
         };
 
         println!(
@@ -1477,6 +3389,7 @@ fn cmd_consolidate(
         );
 
         if !dry_run {
+            let mut rejected = false;
             if let Some(entry) = manifest::find_entry_mut(&mut mf, slug) {
                 entry.score = Some(score);
                 entry.fire_count = Some(fires);
@@ -1484,18 +3397,24 @@ fn cmd_consolidate(
 
                 if score < min_score && entry.status != DraftStatus::Rejected {
                     match entry.status {
-                        DraftStatus::Draft => {
+                        DraftStatus::Draft | DraftStatus::Approved => {
                             entry.status = DraftStatus::Rejected;
-                            rejected_count += 1;
+                            rejected = true;
                         }
-                        DraftStatus::Approved | DraftStatus::Deployed => {
-                            entry.status = DraftStatus::Rejected;
-                            rejected_count += 1;
+                        DraftStatus::Deployed if undeploy => {
+                            to_undeploy.push(slug.clone());
+                            rejected = true;
                         }
+                        DraftStatus::Deployed => kept_deployed.push(slug.as_str()),
                         DraftStatus::Rejected => {}
                     }
                 }
             }
+            if rejected {
+                rejected_count += 1;
+                let reason = format!("consolidate: score {:.3} < min_score {}", score, min_score);
+                manifest::annotate(&mut mf, slug, None, &reason);
+            }
         }
     }
 
@@ -1591,21 +3510,14 @@ fn cmd_consolidate(
                     None => continue,
                 };
 
-                // Read current description from MD file
-                let md_path = drafts_dir.join(format!("{}.md", slug));
-                if !md_path.exists() {
-                    // Try skills_dir
-                    let skill_path = config.skills_dir.join(format!("{}.md", slug));
-                    if !skill_path.exists() {
-                        eprintln!("  {} -- MD file not found, skipping", slug);
-                        continue;
-                    }
-                }
-
-                let md_path = if drafts_dir.join(format!("{}.md", slug)).exists() {
-                    drafts_dir.join(format!("{}.md", slug))
+                // Read current description from MD file (draft first, then skills_dir)
+                let md_path = if layout::exists(&drafts_dir, slug) {
+                    layout::skill_md_path(&drafts_dir, slug)
+                } else if layout::exists(&config.skills_dir, slug) {
+                    layout::skill_md_path(&config.skills_dir, slug)
                 } else {
-                    config.skills_dir.join(format!("{}.md", slug))
+                    eprintln!("  {} -- MD file not found, skipping", slug);
+                    continue;
                 };
 
                 let content = std::fs::read_to_string(&md_path)?;
@@ -1645,6 +3557,73 @@ fn cmd_consolidate(
         }
     }
 
+    // === --sections: drop the sections nobody follows ===
+    let mut rebuilt_count = 0;
+    if sections {
+        eprintln!("=== Section Usage ===\n");
+        let clusters = patterns::clusters(&patterns::load(&drafts_dir)?);
+        let weights = scorer::config();
+
+        for slug in &target_slugs {
+            match manifest::find_entry(&mf, slug) {
+                Some(entry) if entry.status != DraftStatus::Rejected => {}
+                _ => continue,
+            }
+            let Some(cluster) = attribution::skill_cluster(&clusters, slug) else {
+                eprintln!("  {} -- no stored patterns, skipping", slug);
+                continue;
+            };
+            let usage = attribution::section_usage(&cluster, &invocations);
+            let fired = usage.first().map_or(0, |u| u.invocations);
+            if fired < weights.min_section_invocations {
+                eprintln!(
+                    "  {} -- {} invocations, too few to attribute (min {})",
+                    slug, fired, weights.min_section_invocations
+                );
+                continue;
+            }
+
+            let scored = scorer::score_patterns(&cluster, &invocations, weights);
+            let kept = |idx: usize| {
+                scored
+                    .iter()
+                    .any(|&(i, score)| i == idx && score >= generator::MIN_PATTERN_SCORE)
+            };
+            println!("  {} ({} invocations)", slug, fired);
+            for (idx, u) in usage.iter().enumerate() {
+                let status = if kept(idx) { "kept" } else { "dropped" };
+                println!("    [{:<7}] {}/{}  {}", status, u.used, u.invocations, u.title);
+            }
+            if !(0..usage.len()).any(kept) {
+                println!("    no section followed, left as is");
+                continue;
+            }
+            if dry_run {
+                continue;
+            }
+
+            let Some(mut draft) = generator::generate_skills(std::slice::from_ref(&cluster))
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            draft.description = generator::rebuild_description_scored(&cluster, &scored, 5);
+            let (body, files) =
+                generator::externalize_examples(&generator::rebuild_body_scored(&cluster, &scored));
+            draft.body = body;
+            draft.files = files;
+            layout::write_skill(&drafts_dir, slug, &draft)?;
+            manifest::merge_drafts(
+                &mut mf,
+                std::slice::from_ref(&draft),
+                std::slice::from_ref(&cluster),
+            );
+            rebuilt_count += 1;
+        }
+        println!();
+    }
+
     // Summary
     if dry_run {
         eprintln!("\nDry run: no changes written");
@@ -1656,11 +3635,30 @@ fn cmd_consolidate(
             eprintln!("{} skills would be rejected (score < {:.2})", below, min_score);
         }
     } else {
+        if !to_undeploy.is_empty() {
+            let opts = skill_miner::UndeployOptions {
+                reject: true,
+                ..Default::default()
+            };
+            let results =
+                deployer::undeploy(&drafts_dir, &config.skills_dir, &mut mf, &to_undeploy, &opts)?;
+            for r in &results {
+                println!("[undeployed] {} ({} copies removed)", r.slug, r.removed.len());
+            }
+        } else if !kept_deployed.is_empty() {
+            eprintln!(
+                "note: below min_score but still deployed: {} (use --undeploy or `undeploy --reject`)",
+                kept_deployed.join(", ")
+            );
+        }
         manifest::write_manifest(&drafts_dir, &mf)?;
         eprintln!(
             "\nUpdated {} skills, rejected {} (score < {:.2})",
             updated_count, rejected_count, min_score
         );
+        if rebuilt_count > 0 {
+            eprintln!("Rebuilt {} drafts from their followed sections", rebuilt_count);
+        }
         eprintln!("Manifest written to {}", drafts_dir.join("manifest.toml").display());
     }
 
@@ -1707,15 +3705,23 @@ fn cmd_validate(bundle_path: PathBuf, public: bool, fix: bool) -> Result<()> {
         println!();
     }
 
-    let report = bundle::validate_bundle(
+    let mut report = bundle::validate_bundle(
         &bundle_path,
         &bundle::ValidateOptions {
             public_profile: public,
         },
     )?;
 
+    // Body templates from the config dir must parse, or generation falls back to the default
+    let (checked_templates, template_errors) = match template::templates_dir() {
+        Some(dir) => template::check_templates(&dir)?,
+        None => (0, Vec::new()),
+    };
+    report.errors.extend(template_errors);
+
     println!("Bundle validation:");
     println!("  checked skills: {}", report.checked_skills);
+    println!("  checked templates: {}", checked_templates);
     println!("  errors: {}", report.errors.len());
     println!("  warnings: {}", report.warnings.len());
 
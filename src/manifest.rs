@@ -1,11 +1,12 @@
 // Manifest module: read/write/update manifest.toml, status transitions
 // Issue #21
 
+use crate::audit;
 use crate::error::SkillMinerError;
-use crate::types::{DraftEntry, DraftStatus, Manifest};
+use crate::types::{AuditAction, AuditEvent, AuditNote, DraftEntry, DraftStatus, Manifest};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Compute SHA256 hash of content, returned as hex string.
 pub fn compute_hash(content: &str) -> String {
@@ -14,20 +15,291 @@ pub fn compute_hash(content: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
-/// Read manifest.toml from a drafts directory.
+/// Schema version written by this build. Older manifests are migrated on read.
+pub const SCHEMA_VERSION: u32 = 8;
+
+/// `version` string for manifests in the current schema.
+pub fn current_version() -> String {
+    format!("{}.0", SCHEMA_VERSION)
+}
+
+/// Schema version of a `version` string: the major part ("1.0" → 1). Missing means 1.
+pub fn schema_version(version: Option<&str>) -> Result<u32, SkillMinerError> {
+    let Some(version) = version else {
+        return Ok(1);
+    };
+    version
+        .split('.')
+        .next()
+        .and_then(|major| major.trim().parse().ok())
+        .ok_or_else(|| SkillMinerError::Parse(format!("manifest.toml: bad version {:?}", version)))
+}
+
+/// One step of the migration chain, upgrading a manifest from `from` to `from + 1`.
+/// Steps get the manifest's directory to look at its drafts; they never write to it.
+struct Migration {
+    from: u32,
+    apply: fn(&Path, &mut toml::Table) -> Result<Vec<String>, SkillMinerError>,
+}
+
+/// Ordered migration chain. Each step returns a description of every change it made.
+/// Steps that only add fields have nothing to convert (the fields default to empty), but
+/// still bump the version so that older builds refuse the manifest instead of dropping
+/// the new fields when they rewrite it.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 1,
+        apply: migrate_v1_to_v2,
+    },
+    // v3: `entries.versions` (version history)
+    Migration {
+        from: 2,
+        apply: add_fields,
+    },
+    // v4: `forgotten` (mining deny-list)
+    Migration {
+        from: 3,
+        apply: add_fields,
+    },
+    // v5: `entries.meta` (review metadata)
+    Migration {
+        from: 4,
+        apply: add_fields,
+    },
+    // v6: `entries.targets` and `entries.targets_pinned` (deploy targets)
+    Migration {
+        from: 5,
+        apply: add_fields,
+    },
+    // v7: `entries.exports` (rule exports)
+    Migration {
+        from: 6,
+        apply: add_fields,
+    },
+    Migration {
+        from: 7,
+        apply: migrate_v7_to_v8,
+    },
+];
+
+/// v1 → v2: statuses are lowercase and slugs are unique (the first entry wins,
+/// matching what `find_entry` always returned).
+fn migrate_v1_to_v2(_dir: &Path, table: &mut toml::Table) -> Result<Vec<String>, SkillMinerError> {
+    let mut changes = Vec::new();
+    let Some(toml::Value::Array(entries)) = table.get_mut("entries") else {
+        return Ok(changes);
+    };
+    let mut seen = HashSet::new();
+    entries.retain(|entry| {
+        let slug = entry.get("slug").and_then(|s| s.as_str()).unwrap_or_default();
+        if seen.insert(slug.to_string()) {
+            true
+        } else {
+            changes.push(format!("{}: drop duplicate entry", slug));
+            false
+        }
+    });
+    for entry in entries.iter_mut() {
+        let Some(entry) = entry.as_table_mut() else {
+            continue;
+        };
+        let slug = entry.get("slug").and_then(|s| s.as_str()).unwrap_or_default().to_string();
+        if let Some(toml::Value::String(status)) = entry.get_mut("status") {
+            let lower = status.to_lowercase();
+            if *status != lower {
+                changes.push(format!("{}: status {} → {}", slug, status, lower));
+                *status = lower;
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Step for a schema change that only added fields.
+fn add_fields(_dir: &Path, _table: &mut toml::Table) -> Result<Vec<String>, SkillMinerError> {
+    Ok(Vec::new())
+}
+
+/// v7 → v8: content and deploy hashes cover supporting files (`layout::hash_skill`), not
+/// SKILL.md alone. Hashes of a draft's current SKILL.md are replaced by the hash of its
+/// whole folder; other hashes (older content) are left alone.
+fn migrate_v7_to_v8(dir: &Path, table: &mut toml::Table) -> Result<Vec<String>, SkillMinerError> {
+    let mut changes = Vec::new();
+    let Some(toml::Value::Array(entries)) = table.get_mut("entries") else {
+        return Ok(changes);
+    };
+    for entry in entries.iter_mut().filter_map(|e| e.as_table_mut()) {
+        let slug = entry.get("slug").and_then(|s| s.as_str()).unwrap_or_default().to_string();
+        if slug.is_empty() || !crate::layout::exists(dir, &slug) {
+            continue;
+        }
+        let old = compute_hash(&crate::layout::read_skill_md(dir, &slug)?);
+        let new = crate::layout::hash_skill(dir, &slug)?;
+        if old == new {
+            continue;
+        }
+        let mut rehash = |value: Option<&mut toml::Value>, what: &str| {
+            if let Some(toml::Value::String(hash)) = value {
+                if *hash == old {
+                    changes.push(format!("{}: {} covers supporting files", slug, what));
+                    *hash = new.clone();
+                }
+            }
+        };
+        rehash(entry.get_mut("content_hash"), "content_hash");
+        rehash(entry.get_mut("deployed_hash"), "deployed_hash");
+        if let Some(toml::Value::Array(targets)) = entry.get_mut("targets") {
+            for target in targets.iter_mut().filter_map(|t| t.as_table_mut()) {
+                rehash(target.get_mut("deployed_hash"), "target deployed_hash");
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Result of migrating one manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub changes: Vec<String>,
+    /// Copy of the original file (None for dry runs)
+    pub backup: Option<PathBuf>,
+}
+
+fn read_table(path: &Path) -> Result<toml::Table, SkillMinerError> {
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| SkillMinerError::Parse(format!("manifest.toml: {}", e)))
+}
+
+fn table_version(table: &toml::Table) -> Result<u32, SkillMinerError> {
+    schema_version(table.get("version").and_then(|v| v.as_str()))
+}
+
+fn newer_error(path: &Path, version: u32) -> SkillMinerError {
+    SkillMinerError::Config(format!(
+        "{} has schema version {}, but this skill-miner supports up to {}; upgrade skill-miner",
+        path.display(),
+        version,
+        SCHEMA_VERSION
+    ))
+}
+
+/// Run the migration chain on a parsed manifest. Returns None if it is already current.
+fn migrate_table(
+    path: &Path,
+    table: &mut toml::Table,
+) -> Result<Option<MigrationReport>, SkillMinerError> {
+    let from = table_version(table)?;
+    if from > SCHEMA_VERSION {
+        return Err(newer_error(path, from));
+    }
+    if from == SCHEMA_VERSION {
+        return Ok(None);
+    }
+    let mut changes = Vec::new();
+    let dir = path.parent().unwrap_or(Path::new("."));
+    for step in MIGRATIONS.iter().filter(|m| m.from >= from) {
+        changes.extend((step.apply)(dir, table)?);
+    }
+    table.insert("version".to_string(), toml::Value::String(current_version()));
+    Ok(Some(MigrationReport {
+        from,
+        to: SCHEMA_VERSION,
+        changes,
+        backup: None,
+    }))
+}
+
+/// Upgrade `<dir>/manifest.toml` to the current schema. The original is kept as
+/// `manifest.toml.v<N>.bak`. With `dry_run`, only reports what would change.
+pub fn migrate(dir: &Path, dry_run: bool) -> Result<Option<MigrationReport>, SkillMinerError> {
+    let path = dir.join("manifest.toml");
+    let mut table = read_table(&path)?;
+    let Some(mut report) = migrate_table(&path, &mut table)? else {
+        return Ok(None);
+    };
+    if !dry_run {
+        let backup = keep_backup(dir, report.from)?;
+        let content =
+            toml::to_string_pretty(&table).map_err(|e| SkillMinerError::Config(e.to_string()))?;
+        crate::util::write_atomic(&path, content.as_bytes())?;
+        report.backup = Some(backup);
+    }
+    Ok(Some(report))
+}
+
+/// Copy `<dir>/manifest.toml` (schema `version`) to `manifest.toml.v<N>.bak`, unless an
+/// earlier upgrade already did.
+fn keep_backup(dir: &Path, version: u32) -> Result<PathBuf, SkillMinerError> {
+    let backup = dir.join(format!("manifest.toml.v{}.bak", version));
+    if !backup.exists() {
+        std::fs::copy(dir.join("manifest.toml"), &backup)?;
+    }
+    Ok(backup)
+}
+
+/// Read manifest.toml from a drafts directory. Older schemas are migrated in memory only;
+/// the upgrade is saved by the next `write_manifest` (or `migrate`).
 pub fn read_manifest(dir: &Path) -> Result<Manifest, SkillMinerError> {
     let path = dir.join("manifest.toml");
-    let content = std::fs::read_to_string(&path)?;
-    toml::from_str(&content).map_err(|e| SkillMinerError::Parse(format!("manifest.toml: {}", e)))
+    let mut table = read_table(&path)?;
+    migrate_table(&path, &mut table)?;
+    table
+        .try_into()
+        .map_err(|e| SkillMinerError::Parse(format!("manifest.toml: {}", e)))
 }
 
-/// Write manifest.toml to a drafts directory.
+/// Write manifest.toml to a drafts directory (temp file + rename, so it is never half-written).
+/// Changes against the manifest on disk are appended to the audit log (`audit.jsonl`).
+/// The current content of each draft is added to its version chain (see `versions`).
+/// Refuses to overwrite a manifest written by a newer schema; one written by an older schema
+/// is kept as `manifest.toml.v<N>.bak`.
 pub fn write_manifest(dir: &Path, manifest: &Manifest) -> Result<(), SkillMinerError> {
+    let events = write_manifest_unaudited(dir, manifest)?;
+    audit::append(dir, &events)
+}
+
+/// Write manifest.toml like `write_manifest`, but return the audit events of the change
+/// instead of appending them, for callers that must finish their own work once the
+/// manifest is on disk.
+pub fn write_manifest_unaudited(
+    dir: &Path,
+    manifest: &Manifest,
+) -> Result<Vec<AuditEvent>, SkillMinerError> {
     let path = dir.join("manifest.toml");
+    if path.exists() {
+        if let Ok(table) = read_table(&path) {
+            let version = table_version(&table)?;
+            if version > SCHEMA_VERSION {
+                return Err(newer_error(&path, version));
+            }
+            if version < SCHEMA_VERSION {
+                keep_backup(dir, version)?;
+            }
+        }
+    }
+    let previous = if path.exists() { read_manifest(dir).ok() } else { None };
+    let mut manifest = manifest.clone();
+    crate::versions::record(dir, &mut manifest, previous.as_ref())?;
+    let mut table =
+        toml::Table::try_from(&manifest).map_err(|e| SkillMinerError::Config(e.to_string()))?;
+    table.insert("version".to_string(), toml::Value::String(current_version()));
     let content =
-        toml::to_string_pretty(manifest).map_err(|e| SkillMinerError::Config(e.to_string()))?;
-    std::fs::write(&path, content)?;
-    Ok(())
+        toml::to_string_pretty(&table).map_err(|e| SkillMinerError::Config(e.to_string()))?;
+    crate::util::write_atomic(&path, content.as_bytes())?;
+    Ok(audit::diff(previous.as_ref(), &manifest))
+}
+
+/// Attach a reason (and optionally an explicit action) to the next audit event of a slug.
+pub fn annotate(manifest: &mut Manifest, slug: &str, action: Option<AuditAction>, reason: &str) {
+    manifest.audit_notes.insert(
+        slug.to_string(),
+        AuditNote {
+            action,
+            reason: Some(reason.to_string()),
+        },
+    );
 }
 
 /// Find an entry in the manifest by slug.
@@ -67,6 +339,8 @@ fn validate_transition(
             | (DraftStatus::Approved, DraftStatus::Draft) // un-approve
             | (DraftStatus::Rejected, DraftStatus::Draft) // reconsider
             | (DraftStatus::Deployed, DraftStatus::Draft) // re-generate
+            | (DraftStatus::Deployed, DraftStatus::Approved) // undeploy
+            | (DraftStatus::Deployed, DraftStatus::Rejected) // undeploy and reject
     );
     if valid {
         Ok(())
@@ -136,9 +410,21 @@ pub fn create_from_drafts(
 
         let conv_count = cluster.map(|c| c.conversations.len()).unwrap_or(0);
 
-        // Compute hash from the file content
-        let content = draft.format_md();
-        let hash = compute_hash(&content);
+        // Only project-specific patterns pin a draft to a repository; the rest deploy globally
+        let targets = cluster
+            .map(|c| {
+                let domain_level = crate::domains::normalize(&c.domain).slug == draft.name;
+                let patterns: Vec<_> = c
+                    .patterns
+                    .iter()
+                    .filter(|p| domain_level || p.skill_slug.as_deref() == Some(&draft.name))
+                    .collect();
+                crate::targets::infer(&patterns)
+            })
+            .unwrap_or_default();
+
+        // Compute hash from the files the draft is written as
+        let hash = crate::layout::draft_hash(draft);
 
         let domain = cluster
             .map(|c| c.domain.clone())
@@ -151,19 +437,20 @@ pub fn create_from_drafts(
             pattern_count,
             conversation_count: conv_count,
             generated_at: Utc::now(),
-            deployed_at: None,
             content_hash: hash,
-            score: None,
-            fire_count: None,
+            targets,
+            ..Default::default()
         });
     }
 
     Manifest {
-        version: "1.0".to_string(),
+        version: current_version(),
         generated_at: Utc::now(),
         entries,
         mined_ids: HashSet::new(),
         pending_extracts: Vec::new(),
+        forgotten: Default::default(),
+        audit_notes: Default::default(),
     }
 }
 
@@ -177,21 +464,53 @@ pub fn merge_drafts(
     let new_mf = create_from_drafts(drafts, clusters, Path::new(""));
 
     for new_entry in new_mf.entries {
-        if let Some(existing) = manifest.entries.iter_mut().find(|e| e.slug == new_entry.slug) {
-            // Update counts/hash, preserve status/deployed_at/score/fire_count
-            existing.pattern_count = new_entry.pattern_count;
-            existing.conversation_count = new_entry.conversation_count;
-            existing.content_hash = new_entry.content_hash;
-            existing.generated_at = new_entry.generated_at;
-        } else {
-            manifest.entries.push(new_entry);
-        }
+        merge_entry(manifest, new_entry);
     }
 
     manifest.generated_at = chrono::Utc::now();
 }
 
-/// Scan .md files in a directory and create a manifest (fallback for legacy dirs without manifest.toml).
+/// Merge a single freshly generated entry: update counts/hash if the slug exists, else append.
+pub fn merge_entry(manifest: &mut Manifest, new_entry: DraftEntry) {
+    if let Some(existing) = find_entry_mut(manifest, &new_entry.slug) {
+        // Update counts/hash, preserve status/deployed_at/score/fire_count and targets
+        // (a re-mine never moves a skill; `target` does)
+        existing.pattern_count = new_entry.pattern_count;
+        existing.conversation_count = new_entry.conversation_count;
+        existing.content_hash = new_entry.content_hash;
+        existing.generated_at = new_entry.generated_at;
+    } else {
+        manifest.entries.push(new_entry);
+    }
+}
+
+/// Manifest entry for a skill file found on disk (status draft, no patterns or sources).
+pub fn entry_from_skill(slug: &str, content: &str) -> DraftEntry {
+    // Try to extract domain from frontmatter
+    let domain = extract_domain_from_frontmatter(content).unwrap_or_else(|| slug.to_string());
+
+    // Count pattern sections (## N.)
+    let pattern_count = content
+        .lines()
+        .filter(|l| {
+            l.starts_with("## ") && l.chars().nth(3).map(|c| c.is_ascii_digit()).unwrap_or(false)
+        })
+        .count();
+
+    DraftEntry {
+        slug: slug.to_string(),
+        domain,
+        status: DraftStatus::Draft,
+        pattern_count,
+        conversation_count: 0,
+        generated_at: chrono::Utc::now(),
+        content_hash: compute_hash(content),
+        ..Default::default()
+    }
+}
+
+/// Scan skills (.md files and <slug>/SKILL.md folders) in a directory and create a manifest
+/// (fallback for legacy dirs without manifest.toml).
 pub fn create_from_directory(dir: &Path) -> Result<Manifest, SkillMinerError> {
     use chrono::Utc;
 
@@ -199,59 +518,31 @@ pub fn create_from_directory(dir: &Path) -> Result<Manifest, SkillMinerError> {
 
     if !dir.exists() {
         return Ok(Manifest {
-            version: "1.0".to_string(),
+            version: current_version(),
             generated_at: Utc::now(),
             entries,
             mined_ids: HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         });
     }
 
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.extension().map(|e| e == "md").unwrap_or(false) {
-            let slug = path
-                .file_stem()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-
-            let content = std::fs::read_to_string(&path)?;
-            let hash = compute_hash(&content);
-
-            // Try to extract domain from frontmatter
-            let domain = extract_domain_from_frontmatter(&content).unwrap_or_else(|| slug.clone());
-
-            // Count pattern sections (## N.)
-            let pattern_count = content
-                .lines()
-                .filter(|l| {
-                    l.starts_with("## ") && l.chars().nth(3).map(|c| c.is_ascii_digit()).unwrap_or(false)
-                })
-                .count();
-
-            entries.push(DraftEntry {
-                slug,
-                domain,
-                status: DraftStatus::Draft,
-                pattern_count,
-                conversation_count: 0,
-                generated_at: Utc::now(),
-                deployed_at: None,
-                content_hash: hash,
-                score: None,
-                fire_count: None,
-            });
-        }
+    for (slug, path) in crate::layout::list_skills(dir)? {
+        let content = std::fs::read_to_string(&path)?;
+        let mut entry = entry_from_skill(&slug, &content);
+        entry.content_hash = crate::layout::hash_skill(dir, &slug)?;
+        entries.push(entry);
     }
 
     Ok(Manifest {
-        version: "1.0".to_string(),
+        version: current_version(),
         generated_at: Utc::now(),
         entries,
         mined_ids: HashSet::new(),
         pending_extracts: Vec::new(),
+        forgotten: Default::default(),
+        audit_notes: Default::default(),
     })
 }
 
@@ -300,13 +591,13 @@ mod tests {
                 pattern_count: 3,
                 conversation_count: 5,
                 generated_at: Utc::now(),
-                deployed_at: None,
                 content_hash: compute_hash("test content"),
-                score: None,
-                fire_count: None,
+                ..Default::default()
             }],
             mined_ids: HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         }
     }
 
@@ -324,11 +615,53 @@ mod tests {
         let manifest = make_manifest();
         write_manifest(dir.path(), &manifest).unwrap();
         let loaded = read_manifest(dir.path()).unwrap();
-        assert_eq!(loaded.version, "1.0");
+        assert_eq!(loaded.version, current_version());
         assert_eq!(loaded.entries.len(), 1);
         assert_eq!(loaded.entries[0].slug, "test-skill");
     }
 
+    #[test]
+    fn review_meta_roundtrip_and_survives_regeneration() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = make_manifest();
+        let meta = &mut manifest.entries[0].meta;
+        meta.add_tags(&["windows".to_string(), "needs-example".to_string()]);
+        meta.owner = Some("yuuji".to_string());
+        meta.notes = Some("check PowerShell quoting".to_string());
+        let first = meta.add_comment("add a Windows example", None, None).unwrap();
+        assert_eq!(meta.add_comment("done", Some("b".into()), Some(first)), Ok(2));
+        assert!(meta.add_comment("orphan", None, Some(9)).is_err());
+        write_manifest(dir.path(), &manifest).unwrap();
+
+        let mut loaded = read_manifest(dir.path()).unwrap();
+        assert_eq!(loaded.entries[0].meta, manifest.entries[0].meta);
+
+        let mut regenerated = make_manifest().entries.remove(0);
+        regenerated.content_hash = compute_hash("new content");
+        merge_entry(&mut loaded, regenerated);
+        assert_eq!(loaded.entries[0].meta, manifest.entries[0].meta);
+    }
+
+    #[test]
+    fn merge_keeps_existing_targets() {
+        let mut manifest = make_manifest();
+        crate::targets::set(&mut manifest.entries[0], &["/repo".to_string()]);
+
+        // A re-mine infers global, but the skill stays where it was
+        let mut regenerated = make_manifest().entries.remove(0);
+        regenerated.content_hash = compute_hash("new content");
+        merge_entry(&mut manifest, regenerated);
+        assert_eq!(crate::targets::of(&manifest.entries[0]), vec!["/repo"]);
+        assert_eq!(manifest.entries[0].content_hash, compute_hash("new content"));
+
+        // New entries take the inferred targets
+        let mut new_entry = make_manifest().entries.remove(0);
+        new_entry.slug = "other".to_string();
+        new_entry.targets = vec![crate::types::DeployTarget::new("/other")];
+        merge_entry(&mut manifest, new_entry);
+        assert_eq!(crate::targets::of(&manifest.entries[1]), vec!["/other"]);
+    }
+
     #[test]
     fn status_transition_valid() {
         let mut manifest = make_manifest();
@@ -380,4 +713,181 @@ mod tests {
         let loaded = read_manifest(dir.path()).unwrap();
         assert!(loaded.mined_ids.is_empty());
     }
+
+    const V1_MANIFEST: &str = r#"version = "1.0"
+generated_at = "2026-01-01T00:00:00Z"
+
+[[entries]]
+slug = "a"
+domain = "Test"
+status = "Approved"
+pattern_count = 1
+conversation_count = 1
+generated_at = "2026-01-01T00:00:00Z"
+content_hash = "h1"
+
+[[entries]]
+slug = "a"
+domain = "Test"
+status = "draft"
+pattern_count = 1
+conversation_count = 1
+generated_at = "2026-01-02T00:00:00Z"
+content_hash = "h2"
+"#;
+
+    #[test]
+    fn schema_version_parsing() {
+        assert_eq!(schema_version(None).unwrap(), 1);
+        assert_eq!(schema_version(Some("1.0")).unwrap(), 1);
+        assert_eq!(schema_version(Some("2")).unwrap(), 2);
+        assert!(schema_version(Some("x.y")).is_err());
+        assert_eq!(current_version(), format!("{}.0", SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn read_migrates_in_memory_and_write_keeps_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.toml");
+        std::fs::write(&path, V1_MANIFEST).unwrap();
+
+        let loaded = read_manifest(dir.path()).unwrap();
+        assert_eq!(loaded.version, current_version());
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].status, DraftStatus::Approved);
+        assert_eq!(loaded.entries[0].content_hash, "h1");
+        // Reading leaves the file alone
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V1_MANIFEST);
+        let backup = dir.path().join("manifest.toml.v1.bak");
+        assert!(!backup.exists());
+
+        write_manifest(dir.path(), &loaded).unwrap();
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), V1_MANIFEST);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains(&format!("version = \"{}\"", current_version())));
+        // Already current: nothing left to do
+        assert!(migrate(dir.path(), false).unwrap().is_none());
+    }
+
+    #[test]
+    fn dry_run_reports_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.toml");
+        std::fs::write(&path, V1_MANIFEST).unwrap();
+
+        let report = migrate(dir.path(), true).unwrap().unwrap();
+        assert_eq!((report.from, report.to), (1, SCHEMA_VERSION));
+        assert_eq!(
+            report.changes,
+            vec!["a: drop duplicate entry".to_string(), "a: status Approved → approved".to_string()]
+        );
+        assert!(report.backup.is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V1_MANIFEST);
+        assert!(!dir.path().join("manifest.toml.v1.bak").exists());
+    }
+
+    #[test]
+    fn chain_covers_every_version() {
+        let steps: Vec<u32> = MIGRATIONS.iter().map(|m| m.from).collect();
+        assert_eq!(steps, (1..SCHEMA_VERSION).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn additive_versions_keep_their_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let v5 = r#"version = "5.0"
+generated_at = "2026-01-01T00:00:00Z"
+
+[forgotten]
+ids = ["c1"]
+
+[[entries]]
+slug = "a"
+domain = "Test"
+status = "deployed"
+pattern_count = 1
+conversation_count = 1
+generated_at = "2026-01-01T00:00:00Z"
+content_hash = "h1"
+
+[entries.meta]
+tags = ["windows"]
+
+[[entries.versions]]
+hash = "h1"
+at = "2026-01-01T00:00:00Z"
+"#;
+        std::fs::write(dir.path().join("manifest.toml"), v5).unwrap();
+        let report = migrate(dir.path(), false).unwrap().unwrap();
+        assert_eq!((report.from, report.to), (5, SCHEMA_VERSION));
+        assert!(report.changes.is_empty());
+
+        let loaded = read_manifest(dir.path()).unwrap();
+        assert!(loaded.forgotten.ids.contains("c1"));
+        assert_eq!(loaded.entries[0].meta.tags, vec!["windows".to_string()]);
+        assert_eq!(loaded.entries[0].versions.len(), 1);
+        assert!(loaded.entries[0].targets.is_empty());
+    }
+
+    #[test]
+    fn v8_hashes_cover_supporting_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("s");
+        std::fs::create_dir_all(skill.join("examples")).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# S\n").unwrap();
+        std::fs::write(skill.join("examples").join("1-1.rs"), "fn main() {}\n").unwrap();
+        let md_hash = compute_hash("# S\n");
+        let v7 = format!(
+            r#"version = "7.0"
+generated_at = "2026-01-01T00:00:00Z"
+
+[[entries]]
+slug = "s"
+domain = "Test"
+status = "deployed"
+pattern_count = 1
+conversation_count = 1
+generated_at = "2026-01-01T00:00:00Z"
+content_hash = "{md}"
+deployed_hash = "older"
+
+[[entries.targets]]
+path = "/repo"
+deployed_hash = "{md}"
+"#,
+            md = md_hash
+        );
+        std::fs::write(dir.path().join("manifest.toml"), v7).unwrap();
+
+        let report = migrate(dir.path(), true).unwrap().unwrap();
+        assert_eq!(
+            report.changes,
+            vec![
+                "s: content_hash covers supporting files".to_string(),
+                "s: target deployed_hash covers supporting files".to_string(),
+            ]
+        );
+        let loaded = read_manifest(dir.path()).unwrap();
+        let folder_hash = crate::layout::hash_skill(dir.path(), "s").unwrap();
+        assert_eq!(loaded.entries[0].content_hash, folder_hash);
+        assert_eq!(loaded.entries[0].deployed_hash.as_deref(), Some("older"));
+        assert_eq!(loaded.entries[0].targets[0].deployed_hash.as_deref(), Some(folder_hash.as_str()));
+    }
+
+    #[test]
+    fn newer_manifest_is_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.toml");
+        let newer = format!(
+            "version = \"{}.0\"\ngenerated_at = \"2026-01-01T00:00:00Z\"\nentries = []\n",
+            SCHEMA_VERSION + 1
+        );
+        std::fs::write(&path, &newer).unwrap();
+
+        let err = read_manifest(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("upgrade skill-miner"), "{}", err);
+        assert!(write_manifest(dir.path(), &make_manifest()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), newer);
+    }
 }
@@ -0,0 +1,314 @@
+// Merge module: three-way merge of deployed skills that were edited by hand
+// Merges section by section on `## N. Title` blocks; conflicting sections get git-style markers.
+
+use crate::error::SkillMinerError;
+use std::path::{Path, PathBuf};
+
+/// Directory (inside the drafts dir) holding the last deployed generated version of each skill.
+pub const BASE_DIR: &str = ".base";
+
+pub const CONFLICT_START: &str = "<<<<<<< deployed";
+pub const CONFLICT_SEP: &str = "=======";
+pub const CONFLICT_END: &str = ">>>>>>> draft";
+
+/// Result of a three-way merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult {
+    pub content: String,
+    /// Names of blocks with conflicts ("frontmatter", "header" or the section title)
+    pub conflicts: Vec<String>,
+}
+
+/// One block of a skill file: frontmatter, header (text before the first section) or a section.
+#[derive(Debug, Clone)]
+pub(crate) struct Block {
+    pub(crate) key: String,
+    /// Section title without the `## N.` prefix (None for frontmatter/header)
+    pub(crate) title: Option<String>,
+    /// Raw text after the heading line (or the whole block for frontmatter/header)
+    pub(crate) body: String,
+}
+
+impl Block {
+    /// Text used for comparison: ignores numbering and trailing whitespace.
+    pub(crate) fn text(&self) -> String {
+        format!("{}\n{}", self.title.as_deref().unwrap_or(""), self.body.trim_end())
+    }
+
+    pub(crate) fn name(&self) -> String {
+        self.title.clone().unwrap_or_else(|| self.key.clone())
+    }
+
+    fn render(&self, number: usize) -> String {
+        match &self.title {
+            Some(title) => format!("## {}. {}\n{}", number, title, self.body),
+            None => self.body.clone(),
+        }
+    }
+}
+
+/// Parse a `## N. Title` heading into its title.
+fn section_title(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("## ")?;
+    let (num, title) = rest.split_once(". ")?;
+    if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) {
+        Some(title.trim_end())
+    } else {
+        None
+    }
+}
+
+/// Split a skill file into blocks. Headings inside fenced code blocks are ignored.
+pub(crate) fn split_blocks(content: &str) -> Vec<Block> {
+    let content = content.replace("\r\n", "\n");
+    let mut lines = content.split_inclusive('\n').peekable();
+    let mut blocks = Vec::new();
+
+    if lines.peek().map(|l| l.trim_end() == "---").unwrap_or(false) {
+        let mut fm = lines.next().unwrap_or_default().to_string();
+        for line in lines.by_ref() {
+            fm.push_str(line);
+            if line.trim_end() == "---" {
+                break;
+            }
+        }
+        blocks.push(Block {
+            key: "frontmatter".to_string(),
+            title: None,
+            body: fm,
+        });
+    }
+
+    let mut current = Block {
+        key: "header".to_string(),
+        title: None,
+        body: String::new(),
+    };
+    let mut in_fence = false;
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let title = if in_fence { None } else { section_title(line.trim_end()) };
+        match title {
+            Some(title) => {
+                blocks.push(current);
+                // Duplicate titles get an occurrence suffix so keys stay unique
+                let mut key = format!("section:{}", title);
+                let dup = blocks.iter().filter(|b| b.title.as_deref() == Some(title)).count();
+                if dup > 0 {
+                    key = format!("{}#{}", key, dup + 1);
+                }
+                current = Block {
+                    key,
+                    title: Some(title.to_string()),
+                    body: String::new(),
+                };
+            }
+            None => current.body.push_str(line),
+        }
+    }
+    blocks.push(current);
+    blocks.retain(|b| b.title.is_some() || !b.body.is_empty());
+    blocks
+}
+
+enum Resolution<'a> {
+    Take(&'a Block),
+    Drop,
+    Conflict(Option<&'a Block>, Option<&'a Block>),
+}
+
+fn resolve<'a>(
+    base: Option<&'a Block>,
+    ours: Option<&'a Block>,
+    theirs: Option<&'a Block>,
+) -> Resolution<'a> {
+    let text = |b: Option<&Block>| b.map(|b| b.text());
+    let pick = |b: Option<&'a Block>| b.map(Resolution::Take).unwrap_or(Resolution::Drop);
+    if text(ours) == text(theirs) {
+        pick(ours)
+    } else if text(ours) == text(base) {
+        pick(theirs)
+    } else if text(theirs) == text(base) {
+        pick(ours)
+    } else {
+        Resolution::Conflict(ours, theirs)
+    }
+}
+
+/// Three-way merge of a skill file.
+/// `base` is the last generated version that was deployed, `ours` the (hand-edited) deployed file,
+/// `theirs` the newly generated draft. Sections are matched by title and renumbered in draft order;
+/// sections added by hand stay after the section they followed in the deployed file.
+pub fn merge_skill(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base = split_blocks(base);
+    let ours = split_blocks(ours);
+    let theirs = split_blocks(theirs);
+    let find = |blocks: &[Block], key: &str| -> Option<usize> { blocks.iter().position(|b| b.key == key) };
+
+    // Output order: draft order, with blocks only present in the deployed file inserted
+    // after their predecessor there.
+    let mut keys: Vec<String> = theirs.iter().map(|b| b.key.clone()).collect();
+    for (i, block) in ours.iter().enumerate() {
+        if keys.contains(&block.key) {
+            continue;
+        }
+        let pos = ours[..i]
+            .iter()
+            .rev()
+            .find_map(|prev| keys.iter().position(|k| *k == prev.key))
+            .map(|p| p + 1)
+            .unwrap_or(0);
+        keys.insert(pos, block.key.clone());
+    }
+
+    let mut content = String::new();
+    let mut conflicts = Vec::new();
+    let mut number = 0;
+    for key in &keys {
+        let b = find(&base, key).map(|i| &base[i]);
+        let o = find(&ours, key).map(|i| &ours[i]);
+        let t = find(&theirs, key).map(|i| &theirs[i]);
+        let is_section = key.starts_with("section:");
+        match resolve(b, o, t) {
+            Resolution::Take(block) => {
+                if is_section {
+                    number += 1;
+                }
+                content.push_str(&block.render(number));
+            }
+            Resolution::Drop => {}
+            Resolution::Conflict(o, t) => {
+                if is_section {
+                    number += 1;
+                }
+                let name = o.or(t).map(|b| b.name()).unwrap_or_default();
+                conflicts.push(name);
+                content.push_str(CONFLICT_START);
+                content.push('\n');
+                push_side(&mut content, o, number);
+                content.push_str(CONFLICT_SEP);
+                content.push('\n');
+                push_side(&mut content, t, number);
+                content.push_str(CONFLICT_END);
+                content.push('\n');
+            }
+        }
+    }
+
+    MergeResult { content, conflicts }
+}
+
+fn push_side(content: &mut String, block: Option<&Block>, number: usize) {
+    if let Some(block) = block {
+        content.push_str(block.render(number).trim_end());
+        content.push('\n');
+    }
+}
+
+/// Whether a file still contains unresolved conflict markers.
+pub fn has_conflict_markers(content: &str) -> bool {
+    content.lines().any(|l| l == CONFLICT_START || l == CONFLICT_END)
+}
+
+pub(crate) fn base_path(draft_dir: &Path, slug: &str) -> PathBuf {
+    draft_dir.join(BASE_DIR).join(format!("{}.md", slug))
+}
+
+/// Record the generated SKILL.md content that was just deployed (the base of the next merge).
+pub fn save_base(draft_dir: &Path, slug: &str, content: &str) -> Result<(), SkillMinerError> {
+    let path = base_path(draft_dir, slug);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Load the last deployed generated version, if recorded.
+pub fn load_base(draft_dir: &Path, slug: &str) -> Result<Option<String>, SkillMinerError> {
+    let path = base_path(draft_dir, slug);
+    if path.exists() {
+        Ok(Some(std::fs::read_to_string(path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "---\nname: s\ndescription: \"d\"\n---\n\n# Domain\n\n## 1. Alpha\n\nalpha v1\n\n## 2. Beta\n\nbeta v1\n\n";
+
+    #[test]
+    fn unchanged_deployed_takes_draft() {
+        let theirs = BASE.replace("beta v1", "beta v2");
+        let r = merge_skill(BASE, BASE, &theirs);
+        assert!(r.conflicts.is_empty());
+        assert_eq!(r.content, theirs);
+    }
+
+    #[test]
+    fn hand_edit_preserved_when_draft_changes_other_section() {
+        let ours = BASE.replace("alpha v1", "alpha fixed by hand");
+        let theirs = BASE.replace("beta v1", "beta v2");
+        let r = merge_skill(BASE, &ours, &theirs);
+        assert!(r.conflicts.is_empty());
+        assert!(r.content.contains("alpha fixed by hand"));
+        assert!(r.content.contains("beta v2"));
+    }
+
+    #[test]
+    fn same_section_changed_on_both_sides_conflicts() {
+        let ours = BASE.replace("alpha v1", "alpha by hand");
+        let theirs = BASE.replace("alpha v1", "alpha v2");
+        let r = merge_skill(BASE, &ours, &theirs);
+        assert_eq!(r.conflicts, vec!["Alpha".to_string()]);
+        assert!(has_conflict_markers(&r.content));
+        let start = r.content.find(CONFLICT_START).unwrap();
+        let sep = r.content.find(CONFLICT_SEP).unwrap();
+        let end = r.content.find(CONFLICT_END).unwrap();
+        assert!(r.content[start..sep].contains("alpha by hand"));
+        assert!(r.content[sep..end].contains("alpha v2"));
+    }
+
+    #[test]
+    fn hand_added_section_kept_and_renumbered() {
+        let ours = BASE.replace("## 2. Beta", "## 2. Local note\n\nmine\n\n## 3. Beta");
+        let theirs = format!("{}## 3. Gamma\n\ngamma\n\n", BASE);
+        let r = merge_skill(BASE, &ours, &theirs);
+        assert!(r.conflicts.is_empty());
+        let local = r.content.find("## 2. Local note").unwrap();
+        let beta = r.content.find("## 3. Beta").unwrap();
+        let gamma = r.content.find("## 4. Gamma").unwrap();
+        assert!(local < beta && beta < gamma);
+    }
+
+    #[test]
+    fn section_removed_by_draft_is_dropped_unless_edited() {
+        let theirs = BASE.replace("## 2. Beta\n\nbeta v1\n\n", "");
+        let r = merge_skill(BASE, BASE, &theirs);
+        assert!(!r.content.contains("Beta"));
+
+        let ours = BASE.replace("beta v1", "beta by hand");
+        let r = merge_skill(BASE, &ours, &theirs);
+        assert_eq!(r.conflicts, vec!["Beta".to_string()]);
+    }
+
+    #[test]
+    fn headings_inside_code_fences_are_not_sections() {
+        let content = "# D\n\n## 1. A\n\n```\n## 2. Not a section\n```\n";
+        let blocks = split_blocks(content);
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn base_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_base(dir.path(), "s").unwrap().is_none());
+        save_base(dir.path(), "s", "content").unwrap();
+        assert_eq!(load_base(dir.path(), "s").unwrap().as_deref(), Some("content"));
+    }
+}
@@ -2,13 +2,14 @@
 // stopping when no new (unprocessed) conversations are found.
 
 use crate::types::{
-    ClassifiedConversation, Conversation, DomainCluster, Manifest, MineConfig, PipelineStats,
-    SkillDraft,
+    ClassifiedConversation, Conversation, DomainCluster, ForgetList, Manifest, MineConfig,
+    PipelineStats, SkillDraft,
 };
-use crate::{classifier, compressor, extractor, generator, manifest, parser};
+use crate::project::{self, ProjectDraft};
+use crate::{classifier, compressor, extractor, generator, manifest, parser, patterns};
 use anyhow::Result;
 use chrono::{Duration, Utc};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
 /// Number of consecutive empty windows before stopping progressive mining.
@@ -18,6 +19,10 @@ pub const DEFAULT_MAX_CONSECUTIVE_EMPTY: usize = 2;
 pub struct MineResult {
     pub drafts: Vec<SkillDraft>,
     pub clusters: Vec<DomainCluster>,
+    /// Drafts for patterns tied to a single repository
+    pub project_drafts: Vec<ProjectDraft>,
+    /// Per-project clusters the project drafts were built from
+    pub project_clusters: BTreeMap<String, Vec<DomainCluster>>,
     pub stats: PipelineStats,
     pub windows_processed: usize,
     pub new_conversations: usize,
@@ -193,7 +198,8 @@ enum WindowAction {
     Stop,
 }
 
-/// Parse conversations in a time window and filter out already-processed ones.
+/// Parse conversations in a time window and filter out already-processed
+/// and forgotten ones.
 fn load_window_conversations(
     config: &MineConfig,
     min_messages: usize,
@@ -201,6 +207,7 @@ fn load_window_conversations(
     clamped_start_hours: i64,
     mined_ids: &HashSet<String>,
     pending_ids: &HashSet<String>,
+    forgotten: &ForgetList,
 ) -> Result<Vec<Conversation>> {
     let now = Utc::now();
     let end = now - Duration::hours(cursor_hours);
@@ -211,6 +218,7 @@ fn load_window_conversations(
     Ok(convs
         .into_iter()
         .filter(|c| !mined_ids.contains(&c.id) && !pending_ids.contains(&c.id))
+        .filter(|c| !forgotten.contains(&c.id, c.cwd.as_deref()))
         .collect())
 }
 
@@ -326,6 +334,7 @@ fn scan_windows(
             clamped_start_hours,
             &manifest.mined_ids,
             &pending_ids,
+            &manifest.forgotten,
         )?;
 
         if new_convs.is_empty() {
@@ -389,6 +398,8 @@ fn finalize_mining(
         return Ok(MineResult {
             drafts: Vec::new(),
             clusters: Vec::new(),
+            project_drafts: Vec::new(),
+            project_clusters: BTreeMap::new(),
             stats: PipelineStats::default(),
             windows_processed: scan.windows_processed,
             new_conversations: 0,
@@ -408,9 +419,16 @@ fn finalize_mining(
     // Update manifest with extraction results
     update_manifest_after_extraction(manifest, &extraction.clusters, extraction.still_pending);
 
-    // Generate skill drafts
+    // Keep every extracted pattern so drafts can be regenerated without AI
+    if !dry_run {
+        patterns::record(manifest_dir, &extraction.clusters)?;
+    }
+
+    // Generate skill drafts; project-specific patterns become project drafts
     eprintln!("Generating skills...");
-    let mut drafts = generator::generate_skills(&extraction.clusters);
+    let (clusters, project_clusters) = project::split_clusters(&extraction.clusters);
+    let mut drafts = generator::generate_skills(&clusters);
+    let project_drafts = project::generate_project_drafts(&project_clusters);
     if !dry_run {
         generator::check_existing_skills(&mut drafts, &config.skills_dir)?;
     }
@@ -424,7 +442,9 @@ fn finalize_mining(
 
     Ok(MineResult {
         drafts,
-        clusters: extraction.clusters,
+        clusters,
+        project_drafts,
+        project_clusters,
         stats,
         windows_processed: scan.windows_processed,
         new_conversations,
@@ -470,6 +490,8 @@ mod tests {
             entries: Vec::new(),
             mined_ids: HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         }
     }
 
@@ -483,6 +505,8 @@ mod tests {
             sources: vec!["conv1".to_string()],
             existing_skill: None,
             diff: None,
+            files: Vec::new(),
+            allowed_tools: Vec::new(),
         }];
         let clusters = vec![DomainCluster {
             domain: "test-skill".to_string(),
@@ -495,6 +519,7 @@ mod tests {
                 source_ids: vec![],
                 frequency: 1,
                 skill_slug: None,
+                project: None,
             }],
         }];
 
@@ -513,10 +538,10 @@ mod tests {
             pattern_count: 2,
             conversation_count: 3,
             generated_at: Utc::now(),
-            deployed_at: None,
             content_hash: "old-hash".to_string(),
             score: Some(0.8),
             fire_count: Some(5),
+            ..Default::default()
         });
 
         let drafts = vec![SkillDraft {
@@ -526,6 +551,8 @@ mod tests {
             sources: vec![],
             existing_skill: None,
             diff: None,
+            files: Vec::new(),
+            allowed_tools: Vec::new(),
         }];
         let clusters = vec![DomainCluster {
             domain: "existing".to_string(),
@@ -0,0 +1,317 @@
+// Overlap module: trigger-collision detection between skills.
+//
+// Claude picks skills by their description, so skills with overlapping descriptions fire on
+// the same prompts. For every pair of skills (drafts and deployed skills):
+//   description_similarity = cosine of TF-IDF vectors of the descriptions
+//   keyword_similarity     = Jaccard of keyword sets (slug words + pattern section titles)
+//   similarity             = 0.6*description_similarity + 0.4*keyword_similarity
+// Real `trigger_context` prompts from the invocation history are then replayed against every
+// skill (description + keywords): a prompt collides when a second skill matches it nearly as
+// well as the best one (>= AMBIGUITY_RATIO of the best match).
+
+use crate::error::SkillMinerError;
+use crate::layout;
+use crate::types::{DraftStatus, Manifest, OverlapPair, OverlapSuggestion, SkillInvocation};
+use crate::util;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+const DESCRIPTION_WEIGHT: f64 = 0.6;
+const KEYWORD_WEIGHT: f64 = 0.4;
+/// Minimum prompt-to-skill similarity for a skill to be a candidate for a prompt.
+const TRIGGER_MIN: f64 = 0.15;
+/// A second candidate at this fraction of the best match makes the prompt ambiguous.
+const AMBIGUITY_RATIO: f64 = 0.75;
+/// Pairs this similar in both description and keywords cover the same ground.
+const MERGE_DESCRIPTION_MIN: f64 = 0.5;
+const MERGE_KEYWORD_MIN: f64 = 0.4;
+const MAX_EXAMPLES: usize = 3;
+const MAX_SHARED_TERMS: usize = 5;
+
+/// Words that say nothing about when a skill applies.
+const STOPWORDS: &[&str] = &[
+    "and", "any", "are", "can", "for", "from", "how", "into", "its", "not", "skill", "that",
+    "the", "this", "use", "used", "using", "via", "what", "when", "with", "you", "your",
+];
+
+/// What a skill is selected by: its description and keywords.
+#[derive(Debug, Clone)]
+pub struct SkillText {
+    pub slug: String,
+    pub description: String,
+    pub keywords: Vec<String>,
+}
+
+impl SkillText {
+    /// Build from a skill's markdown: frontmatter description, slug words and the titles of
+    /// its `## N. Title` pattern sections.
+    pub fn from_md(slug: &str, content: &str) -> Self {
+        let mut keywords: BTreeSet<String> = tokenize(&slug.replace('-', " ")).into_iter().collect();
+        for line in content.lines() {
+            let Some(title) = line.strip_prefix("## ") else {
+                continue;
+            };
+            if title.starts_with(|c: char| c.is_ascii_digit()) {
+                let title = title.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+                keywords.extend(tokenize(title));
+            }
+        }
+        SkillText {
+            slug: slug.to_string(),
+            description: util::extract_description_from_md(content).unwrap_or_default(),
+            keywords: keywords.into_iter().collect(),
+        }
+    }
+}
+
+/// Drafts that are not rejected, plus skills in `skills_dir` the manifest does not know
+/// (Claude chooses between all of them).
+pub fn load_skills(
+    draft_dir: &Path,
+    skills_dir: &Path,
+    manifest: &Manifest,
+) -> Result<Vec<SkillText>, SkillMinerError> {
+    let mut skills = Vec::new();
+    for entry in &manifest.entries {
+        if entry.status == DraftStatus::Rejected || !layout::exists(draft_dir, &entry.slug) {
+            continue;
+        }
+        let content = layout::read_skill_md(draft_dir, &entry.slug)?;
+        skills.push(SkillText::from_md(&entry.slug, &content));
+    }
+    for (slug, path) in layout::list_skills(skills_dir)? {
+        if !skills.iter().any(|s| s.slug == slug)
+            && !manifest.entries.iter().any(|e| e.slug == slug)
+        {
+            skills.push(SkillText::from_md(&slug, &std::fs::read_to_string(path)?));
+        }
+    }
+    Ok(skills)
+}
+
+/// Lowercase terms of a text. ASCII words of 3+ letters that are not stopwords; runs of
+/// other scripts (e.g. Japanese, written without spaces) become character bigrams.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.is_ascii() {
+            if word.len() >= 3 && !STOPWORDS.contains(&word.as_str()) {
+                tokens.push(word);
+            }
+        } else {
+            let chars: Vec<char> = word.chars().collect();
+            if chars.len() == 1 {
+                tokens.push(word);
+            }
+            tokens.extend(chars.windows(2).map(|w| w.iter().collect::<String>()));
+        }
+    }
+    tokens
+}
+
+type Vector = HashMap<String, f64>;
+
+/// Inverse document frequency over the skills' descriptions and keywords.
+fn idf(docs: &[Vec<String>]) -> HashMap<String, f64> {
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for doc in docs {
+        for term in doc.iter().collect::<BTreeSet<_>>() {
+            *df.entry(term.as_str()).or_insert(0) += 1;
+        }
+    }
+    let n = docs.len() as f64;
+    df.into_iter()
+        .map(|(t, d)| (t.to_string(), ((n + 1.0) / (d as f64 + 1.0)).ln() + 1.0))
+        .collect()
+}
+
+fn vectorize(terms: &[String], idf: &HashMap<String, f64>) -> Vector {
+    let mut v = Vector::new();
+    for term in terms {
+        // Terms unseen in any skill cannot match one
+        if let Some(w) = idf.get(term) {
+            *v.entry(term.clone()).or_insert(0.0) += w;
+        }
+    }
+    v
+}
+
+fn cosine(a: &Vector, b: &Vector) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(t, x)| b.get(t).map(|y| x * y)).sum();
+    let norm = |v: &Vector| v.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+fn jaccard(a: &[String], b: &[String]) -> f64 {
+    let a: BTreeSet<&String> = a.iter().collect();
+    let b: BTreeSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        0.0
+    } else {
+        a.intersection(&b).count() as f64 / union as f64
+    }
+}
+
+/// Pairs of skills likely to collide, most collisions first, then most similar.
+/// A pair is reported if its similarity reaches `threshold` or a real prompt collided on it.
+pub fn analyze(skills: &[SkillText], invocations: &[SkillInvocation], threshold: f64) -> Vec<OverlapPair> {
+    let descriptions: Vec<Vec<String>> = skills.iter().map(|s| tokenize(&s.description)).collect();
+    let profiles: Vec<Vec<String>> = skills
+        .iter()
+        .zip(&descriptions)
+        .map(|(s, d)| d.iter().chain(&s.keywords).cloned().collect())
+        .collect();
+    let idf = idf(&profiles);
+    let description_vecs: Vec<Vector> = descriptions.iter().map(|d| vectorize(d, &idf)).collect();
+    let profile_vecs: Vec<Vector> = profiles.iter().map(|p| vectorize(p, &idf)).collect();
+
+    // Replay prompts: (i, j) -> colliding prompts
+    let mut collisions: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+    let prompts: BTreeSet<&str> = invocations
+        .iter()
+        .filter_map(|inv| inv.trigger_context.as_deref())
+        .collect();
+    for prompt in prompts {
+        let v = vectorize(&tokenize(prompt), &idf);
+        let mut matches: Vec<(usize, f64)> = profile_vecs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, cosine(&v, p)))
+            .filter(|(_, sim)| *sim >= TRIGGER_MIN)
+            .collect();
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let Some(&(best, best_sim)) = matches.first() else {
+            continue;
+        };
+        for &(other, sim) in &matches[1..] {
+            if sim >= best_sim * AMBIGUITY_RATIO {
+                let key = (best.min(other), best.max(other));
+                collisions.entry(key).or_default().push(prompt.to_string());
+            }
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for i in 0..skills.len() {
+        for j in i + 1..skills.len() {
+            let description_similarity = cosine(&description_vecs[i], &description_vecs[j]);
+            let keyword_similarity = jaccard(&skills[i].keywords, &skills[j].keywords);
+            let similarity =
+                DESCRIPTION_WEIGHT * description_similarity + KEYWORD_WEIGHT * keyword_similarity;
+            let prompts = collisions.remove(&(i, j)).unwrap_or_default();
+            if similarity < threshold && prompts.is_empty() {
+                continue;
+            }
+
+            let mut shared: Vec<&String> = description_vecs[i]
+                .keys()
+                .filter(|t| description_vecs[j].contains_key(*t))
+                .collect();
+            shared.sort_by(|a, b| idf[*b].total_cmp(&idf[*a]).then(a.cmp(b)));
+            let suggestion = if description_similarity >= MERGE_DESCRIPTION_MIN
+                && keyword_similarity >= MERGE_KEYWORD_MIN
+            {
+                OverlapSuggestion::Merge
+            } else {
+                OverlapSuggestion::Reword
+            };
+            pairs.push(OverlapPair {
+                a: skills[i].slug.clone(),
+                b: skills[j].slug.clone(),
+                description_similarity,
+                keyword_similarity,
+                collisions: prompts.len(),
+                examples: prompts.into_iter().take(MAX_EXAMPLES).collect(),
+                shared_terms: shared.into_iter().take(MAX_SHARED_TERMS).cloned().collect(),
+                suggestion,
+            });
+        }
+    }
+
+    pairs.sort_by(|x, y| {
+        y.collisions.cmp(&x.collisions).then(
+            (y.description_similarity + y.keyword_similarity)
+                .total_cmp(&(x.description_similarity + x.keyword_similarity)),
+        )
+    });
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(slug: &str, description: &str, sections: &[&str]) -> SkillText {
+        let body: String = sections
+            .iter()
+            .enumerate()
+            .map(|(i, t)| format!("## {}. {}\n\ntext\n\n", i + 1, t))
+            .collect();
+        let md = format!("---\nname: {}\ndescription: \"{}\"\n---\n\n# X\n\n{}", slug, description, body);
+        SkillText::from_md(slug, &md)
+    }
+
+    fn prompt(skill: &str, text: &str) -> SkillInvocation {
+        SkillInvocation {
+            skill_name: skill.to_string(),
+            conversation_id: "c".to_string(),
+            timestamp: None,
+            was_productive: true,
+            trigger_context: Some(text.to_string()),
+            outcome: Default::default(),
+            followed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn tokenize_drops_stopwords_and_splits_cjk() {
+        assert_eq!(tokenize("Use Docker for the build"), vec!["docker", "build"]);
+        assert_eq!(tokenize("写真整理"), vec!["写真", "真整", "整理"]);
+    }
+
+    #[test]
+    fn from_md_collects_keywords() {
+        let s = skill("docker-compose", "Run stacks", &["Restart services", "Logs"]);
+        assert_eq!(s.description, "Run stacks");
+        assert_eq!(s.keywords, vec!["compose", "docker", "logs", "restart", "services"]);
+    }
+
+    #[test]
+    fn reports_colliding_pairs_with_suggestions() {
+        let skills = vec![
+            skill(
+                "docker-compose",
+                "Docker compose stacks: restart containers, read logs",
+                &["Restart containers", "Logs"],
+            ),
+            skill(
+                "docker-ops",
+                "Docker containers: restart containers and read logs",
+                &["Restart containers", "Logs"],
+            ),
+            skill("rust-errors", "Rust error handling with thiserror and anyhow", &["Errors"]),
+        ];
+        let invocations = vec![
+            prompt("docker-compose", "restart the docker containers please"),
+            prompt("rust-errors", "convert this error with thiserror"),
+        ];
+        let pairs = analyze(&skills, &invocations, 0.3);
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!((pair.a.as_str(), pair.b.as_str()), ("docker-compose", "docker-ops"));
+        assert_eq!(pair.collisions, 1);
+        assert_eq!(pair.examples, vec!["restart the docker containers please"]);
+        assert_eq!(pair.suggestion, OverlapSuggestion::Merge);
+        assert!(pair.shared_terms.contains(&"restart".to_string()));
+
+        // Below the threshold and without collisions nothing is reported
+        assert!(analyze(&skills, &[], 0.99).is_empty());
+    }
+}
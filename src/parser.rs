@@ -1,7 +1,11 @@
 use crate::error::SkillMinerError;
-use crate::types::{Conversation, Message, Role, SkillInvocation, ToolUse};
+use crate::generator;
+use crate::types::{
+    Conversation, InvocationOutcome, Message, Role, SkillInvocation, TaskCall, ToolUse,
+};
 use crate::util;
 use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
@@ -19,11 +23,13 @@ pub fn parse_conversation(path: &Path) -> Result<Conversation, SkillMinerError>
         .to_string_lossy()
         .to_string();
 
-    let mut messages = Vec::new();
+    let mut messages: Vec<Message> = Vec::new();
     let mut start_time: Option<DateTime<Utc>> = None;
     let mut end_time: Option<DateTime<Utc>> = None;
     let mut cwd = None;
     let mut git_branch = None;
+    // Tool use id -> (message index, tool index), to mark the calls whose result failed
+    let mut tool_ids: HashMap<String, (usize, usize)> = HashMap::new();
 
     for line in reader.lines() {
         let line = line?;
@@ -82,6 +88,17 @@ pub fn parse_conversation(path: &Path) -> Result<Conversation, SkillMinerError>
             _ => continue,
         };
 
+        // Tool results arrive in user entries that are otherwise skipped
+        for block in blocks(message, "tool_result") {
+            if block.get("is_error").and_then(|v| v.as_bool()) != Some(true) {
+                continue;
+            }
+            let id = block.get("tool_use_id").and_then(|v| v.as_str()).unwrap_or("");
+            if let Some(&(m, t)) = tool_ids.get(id) {
+                messages[m].tool_uses[t].failed = true;
+            }
+        }
+
         let (content, tool_uses) = extract_content(message);
 
         // Skip empty or system-only content
@@ -94,11 +111,23 @@ pub fn parse_conversation(path: &Path) -> Result<Conversation, SkillMinerError>
             continue;
         }
 
+        let sidechain = entry
+            .get("isSidechain")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        for (t, block) in blocks(message, "tool_use").enumerate() {
+            if let Some(id) = block.get("id").and_then(|v| v.as_str()) {
+                tool_ids.insert(id.to_string(), (messages.len(), t));
+            }
+        }
+
         messages.push(Message {
             role,
             content,
             timestamp: ts_parsed,
             tool_uses,
+            sidechain,
         });
     }
 
@@ -113,6 +142,19 @@ pub fn parse_conversation(path: &Path) -> Result<Conversation, SkillMinerError>
     })
 }
 
+/// Content blocks of one type in a message value
+fn blocks<'a>(
+    message: &'a serde_json::Value,
+    kind: &'a str,
+) -> impl Iterator<Item = &'a serde_json::Value> + 'a {
+    message
+        .get("content")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(move |b| b.get("type").and_then(|t| t.as_str()) == Some(kind))
+}
+
 /// Extract text content and tool uses from a message value
 fn extract_content(message: &serde_json::Value) -> (String, Vec<ToolUse>) {
     let content = message.get("content");
@@ -162,11 +204,29 @@ fn extract_content(message: &serde_json::Value) -> (String, Vec<ToolUse>) {
                             None
                         };
 
+                        // Extract delegation details for Task tool
+                        let task = if name == "Task" {
+                            input_val.map(|i| {
+                                let field = |key: &str| {
+                                    i.get(key).and_then(|v| v.as_str()).unwrap_or_default()
+                                };
+                                TaskCall {
+                                    subagent_type: field("subagent_type").to_string(),
+                                    description: field("description").to_string(),
+                                    prompt: util::truncate(field("prompt"), 500),
+                                }
+                            })
+                        } else {
+                            None
+                        };
+
                         tool_uses.push(ToolUse {
                             name,
                             input_summary: input,
                             file_path,
                             command,
+                            task,
+                            failed: false,
                         });
                     }
                     Some("tool_result") => {
@@ -348,8 +408,10 @@ pub fn extract_skill_invocations(conversations: &[Conversation]) -> Vec<SkillInv
                     continue;
                 }
 
+                let after = &conv.messages[i + 1..];
+
                 // Check if the next assistant message has tool_uses
-                let was_productive = conv.messages[i + 1..]
+                let was_productive = after
                     .iter()
                     .find(|m| m.role == Role::Assistant)
                     .map(|m| !m.tool_uses.is_empty())
@@ -368,6 +430,11 @@ pub fn extract_skill_invocations(conversations: &[Conversation]) -> Vec<SkillInv
                     timestamp: msg.timestamp,
                     was_productive,
                     trigger_context,
+                    outcome: invocation_outcome(after),
+                    followed: after[..next_user(after)]
+                        .iter()
+                        .flat_map(|m| m.tool_uses.iter().cloned())
+                        .collect(),
                 });
             }
         }
@@ -376,6 +443,64 @@ pub fn extract_skill_invocations(conversations: &[Conversation]) -> Vec<SkillInv
     invocations
 }
 
+/// Command prefixes (see `generator::bash_prefixes`) that run a test suite
+const TEST_COMMANDS: &[&str] = &[
+    "cargo test", "cargo nextest", "go test", "npm test", "pnpm test", "yarn test", "bun test",
+    "deno test", "make test", "just test", "mvn test", "gradle test", "dotnet test", "pytest",
+    "jest", "vitest",
+];
+
+/// Openings of a user message that corrects the assistant
+const CORRECTIONS: &[&str] = &[
+    "no,", "no.", "no!", "nope", "wrong", "that's wrong", "that's not", "that is not",
+    "not what i", "don't", "do not", "stop", "undo", "revert", "you broke", "違う", "ちがう",
+    "そうじゃな", "やり直", "戻して", "元に戻",
+];
+
+/// Whether a user message opens by correcting the assistant.
+fn is_correction(content: &str) -> bool {
+    let text = content.trim_start().to_lowercase();
+    CORRECTIONS.iter().any(|c| text.starts_with(c))
+}
+
+/// Index of the next (non-sidechain) user message, or the length of `after` if none.
+fn next_user(after: &[Message]) -> usize {
+    after
+        .iter()
+        .position(|m| m.role == Role::User && !m.sidechain)
+        .unwrap_or(after.len())
+}
+
+/// Outcome of an invocation, from the messages after it: tool calls until the next user
+/// message, whether that message is a correction, and the commits and test runs left in
+/// the session.
+fn invocation_outcome(after: &[Message]) -> InvocationOutcome {
+    let mut outcome = InvocationOutcome::default();
+    let next_user = next_user(after);
+
+    for tool in after[..next_user].iter().flat_map(|m| &m.tool_uses) {
+        outcome.tool_calls += 1;
+        if tool.failed {
+            outcome.failed_tool_calls += 1;
+        }
+    }
+    outcome.corrected = after.get(next_user).is_some_and(|m| is_correction(&m.content));
+
+    for tool in after.iter().flat_map(|m| &m.tool_uses) {
+        let Some(ref command) = tool.command else {
+            continue;
+        };
+        let prefixes = generator::bash_prefixes(command);
+        if !tool.failed && prefixes.iter().any(|p| p == "git commit") {
+            outcome.committed = true;
+        }
+        if prefixes.iter().any(|p| TEST_COMMANDS.contains(&p.as_str())) {
+            outcome.tests_passed = Some(!tool.failed);
+        }
+    }
+    outcome
+}
+
 /// Extract skill name from Skill tool input_summary.
 /// Tries JSON parsing first, falls back to string pattern matching.
 fn extract_skill_name(input_summary: &str) -> String {
@@ -433,6 +558,7 @@ mod tests {
                     content: "organize the photos".to_string(),
                     timestamp: None,
                     tool_uses: vec![],
+                    sidechain: false,
                 },
                 Message {
                     role: Role::Assistant,
@@ -443,7 +569,10 @@ mod tests {
                         input_summary: r#"{"skill":"my-skill","args":""}"#.to_string(),
                         file_path: None,
                         command: None,
+                        task: None,
+                        failed: false,
                     }],
+                    sidechain: false,
                 },
                 Message {
                     role: Role::Assistant,
@@ -454,7 +583,10 @@ mod tests {
                         input_summary: "editing file".to_string(),
                         file_path: Some("test.rs".to_string()),
                         command: None,
+                        task: None,
+                        failed: false,
                     }],
+                    sidechain: false,
                 },
             ],
             start_time: None,
@@ -467,6 +599,7 @@ mod tests {
         assert_eq!(invocations[0].skill_name, "my-skill");
         assert!(invocations[0].was_productive);
         assert_eq!(invocations[0].trigger_context, Some("organize the photos".to_string()));
+        assert_eq!(invocations[0].followed[0].file_path.as_deref(), Some("test.rs"));
     }
 
     #[test]
@@ -481,6 +614,7 @@ mod tests {
                     content: "run the skill".to_string(),
                     timestamp: None,
                     tool_uses: vec![],
+                    sidechain: false,
                 },
                 Message {
                     role: Role::Assistant,
@@ -491,7 +625,10 @@ mod tests {
                         input_summary: r#"{"skill":"lonely-skill"}"#.to_string(),
                         file_path: None,
                         command: None,
+                        task: None,
+                        failed: false,
                     }],
+                    sidechain: false,
                 },
                 // No follow-up assistant message with tools
                 Message {
@@ -499,6 +636,7 @@ mod tests {
                     content: "thanks".to_string(),
                     timestamp: None,
                     tool_uses: vec![],
+                    sidechain: false,
                 },
             ],
             start_time: None,
@@ -527,6 +665,7 @@ mod tests {
                     content: long_message.clone(),
                     timestamp: None,
                     tool_uses: vec![],
+                    sidechain: false,
                 },
                 Message {
                     role: Role::Assistant,
@@ -537,7 +676,10 @@ mod tests {
                         input_summary: r#"{"skill":"long-trigger"}"#.to_string(),
                         file_path: None,
                         command: None,
+                        task: None,
+                        failed: false,
                     }],
+                    sidechain: false,
                 },
             ],
             start_time: None,
@@ -596,4 +738,57 @@ mod tests {
         .unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_parse_task_delegation_and_sidechain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conv-task.jsonl");
+        let lines = [
+            r#"{"message":{"role":"user","content":"review my change"}}"#,
+            r#"{"message":{"role":"assistant","content":[{"type":"tool_use","name":"Task","input":{"subagent_type":"general-purpose","description":"Review diff","prompt":"Look at the diff"}}]}}"#,
+            r#"{"isSidechain":true,"message":{"role":"assistant","content":[{"type":"tool_use","name":"Read","input":{"file_path":"/a.rs"}}]}}"#,
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let conv = parse_conversation(&path).unwrap();
+        let task = conv.messages[1].tool_uses[0].task.as_ref().unwrap();
+        assert_eq!(task.subagent_type, "general-purpose");
+        assert_eq!(task.description, "Review diff");
+        assert_eq!(task.prompt, "Look at the diff");
+        assert!(!conv.messages[1].sidechain);
+        assert!(conv.messages[2].sidechain);
+    }
+
+    #[test]
+    fn test_invocation_outcome_from_tool_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conv-outcome.jsonl");
+        let lines = [
+            r#"{"message":{"role":"user","content":"fix the build"}}"#,
+            r#"{"message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Skill","input":{"skill":"rust-build"}}]}}"#,
+            r#"{"message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+            r#"{"message":{"role":"assistant","content":[{"type":"tool_use","id":"t2","name":"Bash","input":{"command":"cargo build"}},{"type":"tool_use","id":"t3","name":"Bash","input":{"command":"cd /r && cargo test"}}]}}"#,
+            r#"{"message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t2","is_error":true,"content":"error[E0308]"},{"type":"tool_result","tool_use_id":"t3","is_error":true,"content":"failed"}]}}"#,
+            r#"{"message":{"role":"user","content":"No, that's the wrong crate"}}"#,
+            r#"{"message":{"role":"assistant","content":[{"type":"tool_use","id":"t4","name":"Bash","input":{"command":"cargo test"}},{"type":"tool_use","id":"t5","name":"Bash","input":{"command":"git commit -m fix"}}]}}"#,
+            r#"{"message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t4","content":"ok"},{"type":"tool_result","tool_use_id":"t5","content":"ok"}]}}"#,
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let conv = parse_conversation(&path).unwrap();
+        assert!(conv.messages[2].tool_uses.iter().all(|t| t.failed));
+        let invocations = extract_skill_invocations(&[conv]);
+        assert_eq!(
+            invocations[0].outcome,
+            InvocationOutcome {
+                tool_calls: 2,
+                failed_tool_calls: 2,
+                corrected: true,
+                committed: true,
+                tests_passed: Some(true),
+            }
+        );
+        assert!(!is_correction("now add the tests"));
+        assert!(is_correction("違う、そっちじゃない"));
+    }
 }
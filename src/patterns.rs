@@ -0,0 +1,286 @@
+// Patterns module: structured store of every extracted KnowledgePattern
+// Mining appends its clusters to `<drafts>/patterns.json`, so drafts can be rebuilt
+// (new template, new scoring) with `regenerate` instead of re-running AI extraction.
+
+use crate::error::SkillMinerError;
+use crate::types::{DomainCluster, PatternStore, StoredPattern};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Pattern store file name, next to manifest.toml.
+pub const PATTERNS_FILE: &str = "patterns.json";
+
+/// Read the pattern store of a drafts directory (empty if none has been written yet).
+pub fn load(dir: &Path) -> Result<PatternStore, SkillMinerError> {
+    let path = dir.join(PATTERNS_FILE);
+    if !path.exists() {
+        return Ok(PatternStore::default());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| SkillMinerError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Write the pattern store (temp file + rename).
+pub fn save(dir: &Path, store: &PatternStore) -> Result<(), SkillMinerError> {
+    let content =
+        serde_json::to_string_pretty(store).map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+    crate::util::write_atomic(&dir.join(PATTERNS_FILE), content.as_bytes())?;
+    Ok(())
+}
+
+/// Identity of a stored pattern: the same title in the same domain and project.
+fn key(domain: &str, title: &str, project: Option<&str>) -> (String, String, String) {
+    (
+        domain.to_string(),
+        title.trim().to_lowercase(),
+        project.unwrap_or_default().to_string(),
+    )
+}
+
+/// Merge freshly extracted clusters into the store.
+/// A pattern extracted again keeps its `first_seen`, takes the new content, and gains the
+/// new source conversations and frequency. Conversations are replaced by id.
+/// Returns the number of patterns that were new to the store.
+pub fn merge(store: &mut PatternStore, clusters: &[DomainCluster]) -> usize {
+    let now = chrono::Utc::now();
+    let mut index: BTreeMap<(String, String, String), usize> = store
+        .patterns
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (key(&p.domain, &p.pattern.title, p.pattern.project.as_deref()), i))
+        .collect();
+    let mut added = 0;
+
+    for cluster in clusters {
+        for pattern in &cluster.patterns {
+            let k = key(&cluster.domain, &pattern.title, pattern.project.as_deref());
+            match index.get(&k) {
+                Some(&i) => {
+                    let stored = &mut store.patterns[i];
+                    let mut source_ids = std::mem::take(&mut stored.pattern.source_ids);
+                    let mut new_sources = 0;
+                    for id in &pattern.source_ids {
+                        if !source_ids.contains(id) {
+                            source_ids.push(id.clone());
+                            new_sources += 1;
+                        }
+                    }
+                    // Re-extracting the same conversations must not inflate the frequency
+                    let frequency = if new_sources > 0 {
+                        stored.pattern.frequency + pattern.frequency
+                    } else {
+                        stored.pattern.frequency
+                    };
+                    stored.pattern = pattern.clone();
+                    stored.pattern.source_ids = source_ids;
+                    stored.pattern.frequency = frequency;
+                    stored.last_seen = now;
+                }
+                None => {
+                    index.insert(k, store.patterns.len());
+                    store.patterns.push(StoredPattern {
+                        domain: cluster.domain.clone(),
+                        pattern: pattern.clone(),
+                        first_seen: now,
+                        last_seen: now,
+                    });
+                    added += 1;
+                }
+            }
+        }
+
+        for conv in &cluster.conversations {
+            match store
+                .conversations
+                .iter_mut()
+                .find(|c| c.summary.id == conv.summary.id)
+            {
+                Some(existing) => *existing = conv.clone(),
+                None => store.conversations.push(conv.clone()),
+            }
+        }
+    }
+
+    added
+}
+
+/// Merge clusters into the store on disk. Returns the number of new patterns.
+pub fn record(dir: &Path, clusters: &[DomainCluster]) -> Result<usize, SkillMinerError> {
+    if clusters.iter().all(|c| c.patterns.is_empty()) {
+        return Ok(0);
+    }
+    let mut store = load(dir)?;
+    let added = merge(&mut store, clusters);
+    save(dir, &store)?;
+    Ok(added)
+}
+
+/// Remove conversations from the store. Patterns left without sources are dropped; the
+/// others lose the forgotten sources and one frequency per source (never below 1).
+/// Returns (dropped, trimmed) pattern counts.
+pub fn forget(store: &mut PatternStore, ids: &HashSet<String>) -> (usize, usize) {
+    let (mut dropped, mut trimmed) = (0, 0);
+    store.patterns.retain_mut(|stored| {
+        let before = stored.pattern.source_ids.len();
+        stored.pattern.source_ids.retain(|id| !ids.contains(id));
+        let removed = before - stored.pattern.source_ids.len();
+        if removed == 0 {
+            return true;
+        }
+        if stored.pattern.source_ids.is_empty() {
+            dropped += 1;
+            return false;
+        }
+        stored.pattern.frequency = stored.pattern.frequency.saturating_sub(removed).max(1);
+        trimmed += 1;
+        true
+    });
+    store.conversations.retain(|c| !ids.contains(&c.summary.id));
+    (dropped, trimmed)
+}
+
+/// Rebuild domain clusters from the store (domains in name order, patterns in store order).
+/// Each cluster gets the stored conversations classified under its domain.
+pub fn clusters(store: &PatternStore) -> Vec<DomainCluster> {
+    let mut by_domain: BTreeMap<&str, DomainCluster> = BTreeMap::new();
+    for stored in &store.patterns {
+        by_domain
+            .entry(stored.domain.as_str())
+            .or_insert_with(|| DomainCluster {
+                domain: stored.domain.clone(),
+                conversations: store
+                    .conversations
+                    .iter()
+                    .filter(|c| c.domain == stored.domain)
+                    .cloned()
+                    .collect(),
+                patterns: Vec::new(),
+            })
+            .patterns
+            .push(stored.pattern.clone());
+    }
+    by_domain.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ClassifiedConversation, ConversationSummary, KnowledgePattern};
+    use std::path::PathBuf;
+
+    fn conv(id: &str, domain: &str) -> ClassifiedConversation {
+        ClassifiedConversation {
+            summary: ConversationSummary {
+                id: id.to_string(),
+                source_path: PathBuf::from(format!("{}.jsonl", id)),
+                first_message: "hello".to_string(),
+                message_count: 4,
+                start_time: None,
+                cwd: None,
+                topics: vec![],
+                tools_used: vec![],
+                files_touched: vec![],
+                commands_used: vec![],
+            },
+            domain: domain.to_string(),
+            slug: String::new(),
+            tags: vec![],
+            confidence: 0.9,
+        }
+    }
+
+    fn pattern(title: &str, sources: &[&str], frequency: usize) -> KnowledgePattern {
+        KnowledgePattern {
+            title: title.to_string(),
+            description: format!("{} description", title),
+            steps: vec!["step".to_string()],
+            code_examples: vec![],
+            source_ids: sources.iter().map(|s| s.to_string()).collect(),
+            frequency,
+            skill_slug: None,
+            project: None,
+        }
+    }
+
+    fn cluster(domain: &str, convs: &[&str], patterns: Vec<KnowledgePattern>) -> DomainCluster {
+        DomainCluster {
+            domain: domain.to_string(),
+            conversations: convs.iter().map(|id| conv(id, domain)).collect(),
+            patterns,
+        }
+    }
+
+    #[test]
+    fn merge_accumulates_sources_and_frequency() {
+        let mut store = PatternStore::default();
+        let first = cluster("Rust", &["c1"], vec![pattern("Error handling", &["c1"], 2)]);
+        assert_eq!(merge(&mut store, std::slice::from_ref(&first)), 1);
+        let first_seen = store.patterns[0].first_seen;
+
+        let second = cluster(
+            "Rust",
+            &["c2"],
+            vec![pattern("error handling ", &["c2"], 1), pattern("Lifetimes", &["c2"], 1)],
+        );
+        assert_eq!(merge(&mut store, &[second]), 1);
+        // Same conversations again: no new sources, frequency unchanged
+        merge(&mut store, &[first]);
+
+        assert_eq!(store.patterns.len(), 2);
+        let stored = &store.patterns[0];
+        assert_eq!(stored.pattern.source_ids, vec!["c1", "c2"]);
+        assert_eq!(stored.pattern.frequency, 3);
+        assert_eq!(stored.first_seen, first_seen);
+        assert_eq!(store.conversations.len(), 2);
+    }
+
+    #[test]
+    fn record_round_trips_into_clusters() {
+        let dir = tempfile::tempdir().unwrap();
+        record(
+            dir.path(),
+            &[
+                cluster("Rust", &["c1"], vec![pattern("Cargo workspaces", &["c1"], 1)]),
+                cluster("Git", &["c2"], vec![pattern("Rebasing", &["c2"], 1)]),
+            ],
+        )
+        .unwrap();
+
+        let clusters = clusters(&load(dir.path()).unwrap());
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].domain, "Git");
+        assert_eq!(clusters[0].conversations[0].summary.id, "c2");
+        assert_eq!(clusters[1].patterns[0].title, "Cargo workspaces");
+        assert_eq!(crate::generator::generate_skills(&clusters).len(), 2);
+    }
+
+    #[test]
+    fn forget_drops_and_trims_patterns() {
+        let mut store = PatternStore::default();
+        merge(
+            &mut store,
+            &[cluster(
+                "Rust",
+                &["c1", "c2"],
+                vec![pattern("Only c1", &["c1"], 1), pattern("Both", &["c1", "c2"], 3)],
+            )],
+        );
+        let ids: HashSet<String> = ["c1".to_string()].into();
+        assert_eq!(forget(&mut store, &ids), (1, 1));
+
+        assert_eq!(store.patterns.len(), 1);
+        assert_eq!(store.patterns[0].pattern.source_ids, vec!["c2"]);
+        assert_eq!(store.patterns[0].pattern.frequency, 2);
+        assert_eq!(store.conversations.len(), 1);
+        assert_eq!(forget(&mut store, &ids), (0, 0));
+    }
+
+    #[test]
+    fn load_missing_store_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path()).unwrap().patterns.is_empty());
+        assert_eq!(record(dir.path(), &[cluster("Rust", &["c1"], vec![])]).unwrap(), 0);
+        assert!(!dir.path().join(PATTERNS_FILE).exists());
+    }
+}
@@ -0,0 +1,557 @@
+// Project module: project-scoped knowledge output
+// Patterns that only apply to one repository stay out of ~/.claude/skills and are
+// emitted as suggested CLAUDE.md sections or project .claude/skills/ drafts instead.
+
+use crate::deployer;
+use crate::error::SkillMinerError;
+use crate::generator;
+use crate::layout;
+use crate::manifest;
+use crate::transaction::{self, Transaction};
+use crate::types::{
+    ClassifiedConversation, DeployResult, DomainCluster, DraftEntry, DraftStatus,
+    KnowledgePattern, Manifest, SkillDraft,
+};
+use crate::util;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Subdirectory of the drafts dir holding project drafts and their own manifest.
+pub const PROJECTS_SUBDIR: &str = "projects";
+
+/// A skill draft scoped to a single repository.
+#[derive(Debug, Clone)]
+pub struct ProjectDraft {
+    /// Project root (normalized cwd of the source conversations)
+    pub project: String,
+    /// Manifest slug: `<project-key>/<skill-name>`
+    pub slug: String,
+    pub draft: SkillDraft,
+}
+
+/// Where an approved project draft is written in its repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectOutput {
+    /// Marked section in `<project>/CLAUDE.md`
+    ClaudeMd,
+    /// `<project>/.claude/skills/<name>/SKILL.md`
+    Skill,
+}
+
+/// Normalize a cwd for comparison (forward slashes, no trailing slash).
+pub fn normalize_project(cwd: &str) -> String {
+    let normalized = util::normalize_path(Path::new(cwd));
+    let trimmed = normalized.trim_end_matches('/');
+    if trimmed.is_empty() {
+        normalized
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Shared cwd of the given conversations, or None if they span several projects.
+pub fn infer_project(conversations: &[&ClassifiedConversation]) -> Option<String> {
+    let mut projects = conversations
+        .iter()
+        .map(|c| c.summary.cwd.as_deref().map(normalize_project));
+    let first = projects.next()??;
+    if projects.all(|p| p.as_deref() == Some(first.as_str())) {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+/// Short, filesystem-safe key for a project path (its last path component).
+pub fn project_key(project: &str) -> String {
+    let name = project
+        .rsplit('/')
+        .find(|s| !s.is_empty())
+        .unwrap_or_default();
+    let key: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let key = key.trim_matches('-');
+    if key.is_empty() {
+        "project".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+/// Split clusters into global clusters and per-project clusters.
+/// Project clusters keep only the conversations that ran in that project.
+pub fn split_clusters(
+    clusters: &[DomainCluster],
+) -> (Vec<DomainCluster>, BTreeMap<String, Vec<DomainCluster>>) {
+    let mut global = Vec::new();
+    let mut by_project: BTreeMap<String, Vec<DomainCluster>> = BTreeMap::new();
+
+    for cluster in clusters {
+        let mut global_patterns = Vec::new();
+        let mut project_patterns: BTreeMap<String, Vec<KnowledgePattern>> = BTreeMap::new();
+        for pattern in &cluster.patterns {
+            match pattern.project {
+                Some(ref project) => project_patterns
+                    .entry(project.clone())
+                    .or_default()
+                    .push(pattern.clone()),
+                None => global_patterns.push(pattern.clone()),
+            }
+        }
+
+        for (project, patterns) in project_patterns {
+            let conversations = cluster
+                .conversations
+                .iter()
+                .filter(|c| {
+                    c.summary.cwd.as_deref().map(normalize_project).as_deref()
+                        == Some(project.as_str())
+                })
+                .cloned()
+                .collect();
+            by_project.entry(project).or_default().push(DomainCluster {
+                domain: cluster.domain.clone(),
+                conversations,
+                patterns,
+            });
+        }
+
+        global.push(DomainCluster {
+            domain: cluster.domain.clone(),
+            conversations: cluster.conversations.clone(),
+            patterns: global_patterns,
+        });
+    }
+
+    (global, by_project)
+}
+
+/// Generate project drafts from per-project clusters.
+/// Projects whose directory names collide get a short hash suffix on their key.
+pub fn generate_project_drafts(
+    by_project: &BTreeMap<String, Vec<DomainCluster>>,
+) -> Vec<ProjectDraft> {
+    let mut key_counts: HashMap<String, usize> = HashMap::new();
+    for project in by_project.keys() {
+        *key_counts.entry(project_key(project)).or_insert(0) += 1;
+    }
+
+    let mut drafts = Vec::new();
+    for (project, clusters) in by_project {
+        let mut key = project_key(project);
+        if key_counts[&key] > 1 {
+            key = format!("{}-{}", key, &manifest::compute_hash(project)[..6]);
+        }
+        for draft in generator::generate_skills(clusters) {
+            drafts.push(ProjectDraft {
+                project: project.clone(),
+                slug: format!("{}/{}", key, draft.name),
+                draft,
+            });
+        }
+    }
+    drafts
+}
+
+/// Read the project drafts manifest (empty if none has been written yet).
+pub fn load_manifest(drafts_dir: &Path) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(PROJECTS_SUBDIR);
+    if dir.join("manifest.toml").exists() {
+        manifest::read_manifest(&dir)
+    } else {
+        Ok(Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: Vec::new(),
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        })
+    }
+}
+
+/// Write project drafts under `<drafts_dir>/projects/` and merge them into its manifest.
+/// Existing entries keep their review status.
+pub fn write_project_drafts(
+    drafts_dir: &Path,
+    drafts: &[ProjectDraft],
+    by_project: &BTreeMap<String, Vec<DomainCluster>>,
+) -> Result<Manifest, SkillMinerError> {
+    let dir = drafts_dir.join(PROJECTS_SUBDIR);
+    std::fs::create_dir_all(&dir)?;
+    let mut mf = load_manifest(drafts_dir)?;
+
+    for pd in drafts {
+        layout::write_skill(&dir, &pd.slug, &pd.draft)?;
+
+        let clusters = by_project.get(&pd.project).map(|c| c.as_slice()).unwrap_or(&[]);
+        let new_mf = manifest::create_from_drafts(std::slice::from_ref(&pd.draft), clusters, &dir);
+        for mut entry in new_mf.entries {
+            entry.slug = pd.slug.clone();
+            entry.project = Some(pd.project.clone());
+            manifest::merge_entry(&mut mf, entry);
+        }
+    }
+
+    mf.generated_at = chrono::Utc::now();
+    manifest::write_manifest(&dir, &mf)?;
+    Ok(mf)
+}
+
+/// Apply a single project draft to its repository.
+pub fn apply_project_draft(
+    projects_dir: &Path,
+    entry: &DraftEntry,
+    output: ProjectOutput,
+) -> Result<DeployResult, SkillMinerError> {
+    let project = entry.project.as_deref().ok_or_else(|| {
+        SkillMinerError::Config(format!("no project recorded for draft: {}", entry.slug))
+    })?;
+    if !layout::exists(projects_dir, &entry.slug) {
+        return Err(SkillMinerError::Config(format!(
+            "draft file not found: {}",
+            layout::skill_md_path(projects_dir, &entry.slug).display()
+        )));
+    }
+    // Slugs are `<project-key>/<name>`: split into the key folder and skill name
+    let (key_dir, name) = match entry.slug.rsplit_once('/') {
+        Some((key, name)) => (projects_dir.join(key), name),
+        None => (projects_dir.to_path_buf(), entry.slug.as_str()),
+    };
+    let project_dir = Path::new(project);
+
+    let (target, was_update) = match output {
+        ProjectOutput::Skill => {
+            let skills_dir = project_dir.join(".claude").join("skills");
+            let was_update = layout::exists(&skills_dir, name);
+            let target = layout::copy_skill(&key_dir, name, &skills_dir, name)?;
+            (target, was_update)
+        }
+        ProjectOutput::ClaudeMd => {
+            let target = project_dir.join("CLAUDE.md");
+            let was_update = target.exists();
+            let existing = if was_update {
+                std::fs::read_to_string(&target)?
+            } else {
+                String::new()
+            };
+            // CLAUDE.md is a single document: pull supporting files back inline
+            let content = layout::read_skill_md(&key_dir, name)?;
+            let files = layout::read_supporting_files(&key_dir, name)?;
+            let body = generator::inline_examples(&deployer::extract_body(&content), &files);
+            std::fs::write(&target, upsert_section(&existing, name, &demote_headings(&body)))?;
+            (target, was_update)
+        }
+    };
+
+    Ok(DeployResult {
+        slug: entry.slug.clone(),
+        target_path: target,
+        was_update,
+        merged: false,
+        conflicts: Vec::new(),
+    })
+}
+
+/// Apply project drafts by slug and mark them deployed, as one transaction of the projects
+/// dir that `deploy --project --undo` can revert, and write its manifest.
+/// Drafts that have not been approved (or deployed before) are skipped.
+pub fn deploy_project_drafts(
+    projects_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    output: ProjectOutput,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    transaction::run(projects_dir, manifest, |tx, manifest| {
+        apply_project_drafts(tx, projects_dir, manifest, slugs, output)
+    })
+}
+
+fn apply_project_drafts(
+    tx: &mut Transaction,
+    projects_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    output: ProjectOutput,
+) -> Result<Vec<DeployResult>, SkillMinerError> {
+    let mut results = Vec::new();
+    for slug in slugs {
+        let entry = manifest::find_entry(manifest, slug)
+            .cloned()
+            .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", slug)))?;
+        if !matches!(entry.status, DraftStatus::Approved | DraftStatus::Deployed) {
+            continue;
+        }
+        tx.remember(&entry)?;
+        // Without a project, apply_project_draft fails before writing anything
+        if let Some(project) = entry.project.as_deref() {
+            let project_dir = Path::new(project);
+            match output {
+                ProjectOutput::Skill => {
+                    let name = slug.rsplit('/').next().unwrap_or(slug);
+                    tx.snapshot_skill(&project_dir.join(".claude").join("skills"), name)?
+                }
+                ProjectOutput::ClaudeMd => tx.snapshot(&project_dir.join("CLAUDE.md"))?,
+            }
+        }
+        results.push(apply_project_draft(projects_dir, &entry, output)?);
+
+        if let Some(e) = manifest::find_entry_mut(manifest, slug) {
+            e.status = DraftStatus::Deployed;
+            e.deployed_at = Some(chrono::Utc::now());
+        }
+    }
+    Ok(results)
+}
+
+/// Insert or replace a marked skill-miner section in a CLAUDE.md document.
+pub fn upsert_section(existing: &str, name: &str, section: &str) -> String {
+    let begin = format!("<!-- skill-miner:{} -->", name);
+    let end = format!("<!-- /skill-miner:{} -->", name);
+    let block = format!("{}\n{}\n{}\n", begin, section.trim_end(), end);
+
+    if let Some(start) = existing.find(&begin) {
+        if let Some(end_offset) = existing[start..].find(&end) {
+            let mut stop = start + end_offset + end.len();
+            if existing[stop..].starts_with('\n') {
+                stop += 1;
+            }
+            return format!("{}{}{}", &existing[..start], block, &existing[stop..]);
+        }
+    }
+
+    let head = existing.trim_end();
+    if head.is_empty() {
+        block
+    } else {
+        format!("{}\n\n{}", head, block)
+    }
+}
+
+/// Content of a marked skill-miner section, if the document has one.
+pub fn section<'a>(existing: &'a str, name: &str) -> Option<&'a str> {
+    let begin = format!("<!-- skill-miner:{} -->\n", name);
+    let end = format!("\n<!-- /skill-miner:{} -->", name);
+    let start = existing.find(&begin)? + begin.len();
+    let len = existing[start..].find(&end)?;
+    Some(&existing[start..start + len])
+}
+
+/// Push markdown headings one level down so a skill body nests under CLAUDE.md's own headings.
+pub(crate) fn demote_headings(body: &str) -> String {
+    let mut in_fence = false;
+    body.lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+            if !in_fence && line.starts_with('#') {
+                format!("#{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConversationSummary;
+    use std::path::PathBuf;
+
+    fn make_conv(id: &str, cwd: Option<&str>) -> ClassifiedConversation {
+        ClassifiedConversation {
+            summary: ConversationSummary {
+                id: id.to_string(),
+                source_path: PathBuf::from("/tmp/dummy.jsonl"),
+                first_message: String::new(),
+                message_count: 4,
+                start_time: None,
+                cwd: cwd.map(String::from),
+                topics: vec![],
+                tools_used: vec![],
+                files_touched: vec![],
+                commands_used: vec![],
+            },
+            domain: "Testing & QA".to_string(),
+            slug: "testing".to_string(),
+            tags: vec![],
+            confidence: 0.9,
+        }
+    }
+
+    fn make_pattern(title: &str, project: Option<&str>) -> KnowledgePattern {
+        KnowledgePattern {
+            title: title.to_string(),
+            description: format!("{} description", title),
+            steps: vec!["step".to_string()],
+            code_examples: vec![],
+            source_ids: vec!["c1".to_string()],
+            frequency: 1,
+            skill_slug: Some("test-db".to_string()),
+            project: project.map(String::from),
+        }
+    }
+
+    #[test]
+    fn infer_project_shared_cwd() {
+        let a = make_conv("a", Some("/home/u/repo/"));
+        let b = make_conv("b", Some("/home/u/repo"));
+        assert_eq!(infer_project(&[&a, &b]), Some("/home/u/repo".to_string()));
+    }
+
+    #[test]
+    fn infer_project_mixed_or_missing_cwd() {
+        let a = make_conv("a", Some("/home/u/repo"));
+        let b = make_conv("b", Some("/home/u/other"));
+        let c = make_conv("c", None);
+        assert_eq!(infer_project(&[&a, &b]), None);
+        assert_eq!(infer_project(&[&a, &c]), None);
+        assert_eq!(infer_project(&[]), None);
+    }
+
+    #[test]
+    fn project_key_uses_last_component() {
+        assert_eq!(project_key("/home/u/My Repo"), "my-repo");
+        assert_eq!(project_key("C:/work/skill-miner"), "skill-miner");
+        assert_eq!(project_key("/"), "project");
+    }
+
+    #[test]
+    fn split_clusters_separates_project_patterns() {
+        let cluster = DomainCluster {
+            domain: "Testing & QA".to_string(),
+            conversations: vec![
+                make_conv("c1", Some("/home/u/repo")),
+                make_conv("c2", Some("/home/u/other")),
+            ],
+            patterns: vec![
+                make_pattern("Global", None),
+                make_pattern("Start DB first", Some("/home/u/repo")),
+            ],
+        };
+        let (global, by_project) = split_clusters(&[cluster]);
+        assert_eq!(global[0].patterns.len(), 1);
+        assert_eq!(global[0].patterns[0].title, "Global");
+        let repo = &by_project["/home/u/repo"][0];
+        assert_eq!(repo.patterns.len(), 1);
+        assert_eq!(repo.conversations.len(), 1);
+        assert_eq!(repo.conversations[0].summary.id, "c1");
+    }
+
+    #[test]
+    fn generate_project_drafts_prefixes_slug_with_key() {
+        let mut by_project = BTreeMap::new();
+        by_project.insert(
+            "/home/u/repo".to_string(),
+            vec![DomainCluster {
+                domain: "Testing & QA".to_string(),
+                conversations: vec![make_conv("c1", Some("/home/u/repo"))],
+                patterns: vec![make_pattern("Start DB first", Some("/home/u/repo"))],
+            }],
+        );
+        let drafts = generate_project_drafts(&by_project);
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].slug, "repo/test-db");
+        assert_eq!(drafts[0].draft.name, "test-db");
+    }
+
+    #[test]
+    fn upsert_section_appends_then_replaces() {
+        let doc = "# Project\n\nExisting notes.\n";
+        let once = upsert_section(doc, "test-db", "## Test DB\nold");
+        assert!(once.starts_with("# Project\n\nExisting notes.\n\n<!-- skill-miner:test-db -->"));
+        let twice = upsert_section(&once, "test-db", "## Test DB\nnew");
+        assert!(twice.contains("new"));
+        assert!(!twice.contains("old"));
+        assert_eq!(twice.matches("<!-- skill-miner:test-db -->").count(), 1);
+        assert!(twice.starts_with("# Project"));
+        assert_eq!(section(&twice, "test-db"), Some("## Test DB\nnew"));
+        assert_eq!(section(&twice, "other"), None);
+    }
+
+    #[test]
+    fn demote_headings_skips_code_fences() {
+        let body = "# Title\n```bash\n# comment\n```\n## Sub";
+        assert_eq!(demote_headings(body), "## Title\n```bash\n# comment\n```\n### Sub");
+    }
+
+    #[test]
+    fn write_and_apply_project_drafts() {
+        let drafts_dir = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let project = normalize_project(&repo.path().to_string_lossy());
+
+        let mut by_project = BTreeMap::new();
+        by_project.insert(
+            project.clone(),
+            vec![DomainCluster {
+                domain: "Testing & QA".to_string(),
+                conversations: vec![make_conv("c1", Some(&project))],
+                patterns: vec![make_pattern("Start DB first", Some(&project))],
+            }],
+        );
+        let drafts = generate_project_drafts(&by_project);
+        let mut mf = write_project_drafts(drafts_dir.path(), &drafts, &by_project).unwrap();
+        assert_eq!(mf.entries.len(), 1);
+        assert_eq!(mf.entries[0].project.as_deref(), Some(project.as_str()));
+
+        // Unreviewed drafts are not deployed
+        let projects_dir = drafts_dir.path().join(PROJECTS_SUBDIR);
+        let slug = mf.entries[0].slug.clone();
+        let results = deploy_project_drafts(
+            &projects_dir,
+            &mut mf,
+            std::slice::from_ref(&slug),
+            ProjectOutput::ClaudeMd,
+        )
+        .unwrap();
+        assert!(results.is_empty());
+        assert!(!repo.path().join("CLAUDE.md").exists());
+        assert_eq!(mf.entries[0].status, DraftStatus::Draft);
+
+        // Re-writing keeps review status and targets
+        mf.entries[0].status = DraftStatus::Approved;
+        crate::targets::set(&mut mf.entries[0], &["/elsewhere".to_string()]);
+        manifest::write_manifest(&projects_dir, &mf).unwrap();
+        let mut mf = write_project_drafts(drafts_dir.path(), &drafts, &by_project).unwrap();
+        assert_eq!(mf.entries[0].status, DraftStatus::Approved);
+        assert_eq!(crate::targets::of(&mf.entries[0]), vec!["/elsewhere"]);
+
+        let results = deploy_project_drafts(
+            &projects_dir,
+            &mut mf,
+            std::slice::from_ref(&slug),
+            ProjectOutput::ClaudeMd,
+        )
+        .unwrap();
+        assert_eq!(results[0].target_path, repo.path().join("CLAUDE.md"));
+        let claude_md = std::fs::read_to_string(repo.path().join("CLAUDE.md")).unwrap();
+        assert!(claude_md.contains("<!-- skill-miner:test-db -->"));
+        assert!(claude_md.contains("Start DB first"));
+        assert_eq!(mf.entries[0].status, DraftStatus::Deployed);
+        let written = manifest::read_manifest(&projects_dir).unwrap();
+        assert_eq!(written.entries[0].status, DraftStatus::Deployed);
+
+        // The deploy is a transaction of the projects dir
+        transaction::undo(&projects_dir, &mut mf).unwrap().unwrap();
+        assert!(!repo.path().join("CLAUDE.md").exists());
+        assert_eq!(mf.entries[0].status, DraftStatus::Approved);
+
+        let entry = mf.entries[0].clone();
+        let r = apply_project_draft(&projects_dir, &entry, ProjectOutput::Skill).unwrap();
+        assert!(r.target_path.ends_with(".claude/skills/test-db/SKILL.md"));
+        assert!(std::fs::read_to_string(&r.target_path).unwrap().starts_with("---\nname: test-db"));
+    }
+}
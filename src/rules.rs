@@ -0,0 +1,330 @@
+// Rules module: export skills to other agents' rule formats
+// Cursor rules (`.cursor/rules/<slug>.mdc` with glob frontmatter), marked sections of
+// `.github/copilot-instructions.md` and marked sections of `AGENTS.md`. Each format renders
+// a `SkillDraft` its own way; exports are tracked per format and repository in the manifest.
+
+use crate::deployer;
+use crate::error::SkillMinerError;
+use crate::generator;
+use crate::layout;
+use crate::manifest;
+use crate::project;
+use crate::targets;
+use crate::transaction::{self, Transaction};
+use crate::types::{
+    escape_yaml_double_quoted, AgentFormat, DraftEntry, Manifest, RuleExport, RuleExportResult,
+    SkillDraft,
+};
+use crate::util;
+use std::path::{Path, PathBuf};
+
+/// Fence languages that name commands to run rather than files to edit (no glob).
+const COMMAND_LANGUAGES: &[&str] = &["sh", "ps1", "txt"];
+
+impl AgentFormat {
+    /// File the format is written to in a repository.
+    pub fn path(&self, repo: &Path, slug: &str) -> PathBuf {
+        match self {
+            AgentFormat::Cursor => repo.join(".cursor").join("rules").join(format!("{}.mdc", slug)),
+            AgentFormat::Copilot => repo.join(".github").join("copilot-instructions.md"),
+            AgentFormat::AgentsMd => repo.join("AGENTS.md"),
+        }
+    }
+
+    /// Whether skills share one document as marked sections (vs one file per skill).
+    pub fn is_section(&self) -> bool {
+        !matches!(self, AgentFormat::Cursor)
+    }
+
+    /// Render a draft in this format: the whole `.mdc` file, or the section content.
+    pub fn render(&self, draft: &SkillDraft) -> String {
+        match self {
+            AgentFormat::Cursor => render_cursor(draft),
+            AgentFormat::Copilot => render_copilot(draft),
+            AgentFormat::AgentsMd => render_agents_md(draft),
+        }
+    }
+}
+
+/// Load a draft from the drafts dir (frontmatter description, body and supporting files).
+pub fn load_draft(draft_dir: &Path, slug: &str) -> Result<SkillDraft, SkillMinerError> {
+    let content = layout::read_skill_md(draft_dir, slug)?;
+    Ok(SkillDraft {
+        name: slug.to_string(),
+        description: util::extract_description_from_md(&content).unwrap_or_default(),
+        body: deployer::extract_body(&content),
+        sources: Vec::new(),
+        existing_skill: None,
+        diff: None,
+        files: layout::read_supporting_files(draft_dir, slug)?,
+        allowed_tools: Vec::new(),
+    })
+}
+
+/// Globs of the files a skill is about, from the languages of its code examples
+/// (`rust` → `**/*.rs`). Shell snippets are commands, not files, and give no glob.
+pub fn cursor_globs(body: &str) -> Vec<String> {
+    let mut globs: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let Some(lang) = line.trim_start().strip_prefix("```") else {
+            continue;
+        };
+        if !in_fence {
+            let ext = generator::extension_for(lang.trim());
+            let glob = format!("**/*.{}", ext);
+            if !COMMAND_LANGUAGES.contains(&ext) && !globs.contains(&glob) {
+                globs.push(glob);
+            }
+        }
+        in_fence = !in_fence;
+    }
+    globs
+}
+
+/// Cursor rule: description and globs in frontmatter, so Cursor attaches the rule to
+/// matching files or when the description fits the request.
+fn render_cursor(draft: &SkillDraft) -> String {
+    let body = generator::inline_examples(&draft.body, &draft.files);
+    let globs = cursor_globs(&body);
+    let mut front = format!(
+        "---\ndescription: \"{}\"\n",
+        escape_yaml_double_quoted(&draft.description)
+    );
+    if !globs.is_empty() {
+        front.push_str(&format!("globs: {}\n", globs.join(",")));
+    }
+    front.push_str("alwaysApply: false\n---\n");
+    format!("{}\n{}\n", front, body.trim_end())
+}
+
+/// Copilot reads every instruction on each request: say when the section applies.
+fn render_copilot(draft: &SkillDraft) -> String {
+    section_with_trigger(draft, "Applies when")
+}
+
+/// AGENTS.md is read by Codex and other agents as plain markdown.
+fn render_agents_md(draft: &SkillDraft) -> String {
+    section_with_trigger(draft, "Use when")
+}
+
+/// Section body: headings nested under the document's own, examples inlined and the
+/// description placed under the first heading.
+fn section_with_trigger(draft: &SkillDraft, label: &str) -> String {
+    let body = project::demote_headings(&generator::inline_examples(&draft.body, &draft.files));
+    let trigger = format!("*{}: {}*", label, draft.description.trim());
+    let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+    match lines.iter().position(|l| l.starts_with('#')) {
+        Some(i) if !draft.description.trim().is_empty() => {
+            lines.insert(i + 1, String::new());
+            lines.insert(i + 2, trigger);
+        }
+        None if !draft.description.trim().is_empty() => {
+            lines.insert(0, trigger);
+            lines.insert(1, String::new());
+        }
+        _ => {}
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+/// Rendered content currently in a repository (None if not exported there).
+fn read_current(format: AgentFormat, path: &Path, slug: &str) -> Result<Option<String>, SkillMinerError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    if format.is_section() {
+        Ok(project::section(&content, slug).map(str::to_string))
+    } else {
+        Ok(Some(content))
+    }
+}
+
+/// Repositories a draft exports to: `repo` if given, else its project targets.
+pub fn repos(entry: &DraftEntry, repo: Option<&str>) -> Vec<String> {
+    match repo {
+        Some(repo) => vec![repo.to_string()],
+        None => targets::of(entry)
+            .into_iter()
+            .filter(|t| t != targets::GLOBAL)
+            .collect(),
+    }
+}
+
+/// Export state of a draft for a format and repository.
+pub fn export_state<'a>(entry: &'a DraftEntry, format: AgentFormat, repo: &str) -> Option<&'a RuleExport> {
+    entry.exports.iter().find(|e| e.format == format && e.repo == repo)
+}
+
+fn mark_exported(entry: &mut DraftEntry, format: AgentFormat, repo: &str, hash: String) {
+    let state = RuleExport {
+        format,
+        repo: repo.to_string(),
+        deployed_at: chrono::Utc::now(),
+        deployed_hash: hash,
+    };
+    match entry.exports.iter_mut().find(|e| e.format == format && e.repo == repo) {
+        Some(e) => *e = state,
+        None => entry.exports.push(state),
+    }
+}
+
+/// Write a draft in `format` to a repository. Returns the result and the hash of what was
+/// written.
+pub fn export_skill(
+    draft_dir: &Path,
+    entry: &DraftEntry,
+    format: AgentFormat,
+    repo: &str,
+) -> Result<(RuleExportResult, String), SkillMinerError> {
+    let draft = load_draft(draft_dir, &entry.slug)?;
+    let rendered = format.render(&draft);
+    let path = format.path(Path::new(repo), &entry.slug);
+
+    let current = read_current(format, &path, &entry.slug)?;
+    let overwrote_edits = match (&current, export_state(entry, format, repo)) {
+        (Some(current), Some(state)) => manifest::compute_hash(current) != state.deployed_hash,
+        _ => false,
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let written = if format.is_section() {
+        let existing = if path.exists() {
+            std::fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+        project::upsert_section(&existing, &entry.slug, &rendered)
+    } else {
+        rendered.clone()
+    };
+    util::write_atomic(&path, written.as_bytes())?;
+
+    Ok((
+        RuleExportResult {
+            slug: entry.slug.clone(),
+            format,
+            path,
+            was_update: current.is_some(),
+            overwrote_edits,
+        },
+        manifest::compute_hash(&rendered),
+    ))
+}
+
+/// Export drafts in `format` as one transaction (undone by `deploy --undo`), recording the
+/// export state of each draft per repository in the written manifest. Drafts without a repository are an error.
+pub fn export(
+    draft_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    format: AgentFormat,
+    repo: Option<&str>,
+) -> Result<Vec<RuleExportResult>, SkillMinerError> {
+    for slug in slugs {
+        let entry = manifest::find_entry(manifest, slug)
+            .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", slug)))?;
+        if repos(entry, repo).is_empty() {
+            return Err(SkillMinerError::Config(format!(
+                "{} has no project target to export to (set one with `target`, or pass --repo)",
+                slug
+            )));
+        }
+    }
+
+    transaction::run(draft_dir, manifest, |tx, manifest| {
+        apply_exports(tx, draft_dir, manifest, slugs, format, repo)
+    })
+}
+
+fn apply_exports(
+    tx: &mut Transaction,
+    draft_dir: &Path,
+    manifest: &mut Manifest,
+    slugs: &[String],
+    format: AgentFormat,
+    repo: Option<&str>,
+) -> Result<Vec<RuleExportResult>, SkillMinerError> {
+    let mut results = Vec::new();
+    for slug in slugs {
+        let Some(entry) = manifest::find_entry(manifest, slug).cloned() else {
+            continue;
+        };
+        tx.remember(&entry)?;
+        for repo in repos(&entry, repo) {
+            tx.snapshot(&format.path(Path::new(&repo), slug))?;
+            let (result, hash) = export_skill(draft_dir, &entry, format, &repo)?;
+            if let Some(e) = manifest::find_entry_mut(manifest, slug) {
+                mark_exported(e, format, &repo, hash);
+            }
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_draft(dir: &Path) {
+        std::fs::create_dir_all(dir.join("rust-errors")).unwrap();
+        std::fs::write(
+            dir.join("rust-errors").join(layout::SKILL_FILE),
+            "---\nname: rust-errors\ndescription: \"Rust error handling: thiserror\"\n---\n\n\
+             # Rust\n\n## 1. Errors\n\nUse thiserror.\n\n```rust\nenum E {}\n```\n\n\
+             ```bash\ncargo test\n```\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn render_cursor_rule_with_globs() {
+        let drafts = tempfile::tempdir().unwrap();
+        write_draft(drafts.path());
+        let draft = load_draft(drafts.path(), "rust-errors").unwrap();
+        let rule = AgentFormat::Cursor.render(&draft);
+        assert!(rule.starts_with(
+            "---\ndescription: \"Rust error handling: thiserror\"\nglobs: **/*.rs\nalwaysApply: false\n---\n\n# Rust\n"
+        ));
+
+        let section = AgentFormat::AgentsMd.render(&draft);
+        assert!(section.starts_with("## Rust\n\n*Use when: Rust error handling: thiserror*\n"));
+        assert!(section.contains("### 1. Errors"));
+    }
+
+    #[test]
+    fn export_tracks_state_per_format_and_detects_edits() {
+        let drafts = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        write_draft(drafts.path());
+        std::fs::write(repo.path().join("AGENTS.md"), "# Agents\n\nHouse rules.\n").unwrap();
+        let mut mf = manifest::create_from_directory(drafts.path()).unwrap();
+        let slugs = vec!["rust-errors".to_string()];
+
+        // No project target and no --repo
+        assert!(export(drafts.path(), &mut mf, &slugs, AgentFormat::Cursor, None).is_err());
+
+        for format in [AgentFormat::Cursor, AgentFormat::AgentsMd] {
+            let results = export(drafts.path(), &mut mf, &slugs, format, Some(&repo_path)).unwrap();
+            assert!(!results[0].overwrote_edits);
+        }
+        assert!(repo.path().join(".cursor/rules/rust-errors.mdc").exists());
+        let agents_md = std::fs::read_to_string(repo.path().join("AGENTS.md")).unwrap();
+        assert!(agents_md.starts_with("# Agents\n\nHouse rules.\n\n<!-- skill-miner:rust-errors -->"));
+        assert_eq!(mf.entries[0].exports.len(), 2);
+
+        // Hand edit inside the section is reported when it is overwritten
+        std::fs::write(repo.path().join("AGENTS.md"), agents_md.replace("Use thiserror.", "Edited."))
+            .unwrap();
+        let results =
+            export(drafts.path(), &mut mf, &slugs, AgentFormat::AgentsMd, Some(&repo_path)).unwrap();
+        assert!(results[0].was_update && results[0].overwrote_edits);
+        let again = std::fs::read_to_string(repo.path().join("AGENTS.md")).unwrap();
+        assert_eq!(again, agents_md);
+    }
+}
@@ -1,50 +1,176 @@
 // Scorer module: compute consolidation scores for skills and patterns.
 //
-// Scoring formula for skills:
+// Scoring formula for skills (weights and half-life from `ScoringConfig`):
 //   fire_score      = fire_count / max_fire_count           (normalized 0..1)
 //   pattern_score   = sum(pattern.frequency) / max_sum      (normalized 0..1)
 //   productive_rate = productive_count / fire_count          (1.0 if no fires)
-//   dormancy_mult   = if fire_count==0: >14d→0.2, >7d→0.5, else→1.0; if fires: 1.0
-//   score           = (0.6*fire_score + 0.4*pattern_score) * (0.5+0.5*productive_rate) * dormancy_mult
+//   outcome_rate    = mean outcome of the invocations         (1.0 if none)
+//   decay           = 0.5 ^ (days_inactive / half_life_days)
+//   score           = (fire_weight*fire_score + pattern_weight*pattern_score)
+//                     * (productive_base + productive_weight*productive_rate)
+//                     * (outcome_base + outcome_weight*outcome_rate) * decay
+//
+// The outcome of one invocation (0..1) adds up the share of the following tool calls that
+// succeeded (0.5), no correction in the next user message (0.3), and a commit or passing
+// test run later in the session (0.2).
+//
+// days_inactive counts from the last invocation, or from the deploy when the skill was not
+// invoked since (never-deployed drafts do not decay).
+//
+// The parameters are loaded in this priority:
+// 1. Runtime config: `~/.config/skill-miner/scoring.toml` (missing keys keep their default)
+// 2. Built-in defaults
 
+use crate::attribution;
 use crate::domains;
-use crate::types::{DomainCluster, Manifest, SkillInvocation};
-use chrono::Utc;
+use crate::types::{DomainCluster, InvocationOutcome, Manifest, ScoreBreakdown, SkillInvocation};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+const OUTCOME_SUCCESS_WEIGHT: f64 = 0.5;
+const OUTCOME_UNCORRECTED_WEIGHT: f64 = 0.3;
+const OUTCOME_FINISHED_WEIGHT: f64 = 0.2;
+
+/// Scoring parameters.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+    /// Weight of the normalized fire count in the base score
+    pub fire_weight: f64,
+    /// Weight of the normalized pattern frequency sum in the base score
+    pub pattern_weight: f64,
+    /// Productivity multiplier for a skill whose invocations were never productive
+    pub productive_base: f64,
+    /// Added to the multiplier in proportion to the productive rate
+    pub productive_weight: f64,
+    /// Outcome multiplier for a skill whose invocations all went badly
+    pub outcome_base: f64,
+    /// Added to the multiplier in proportion to the mean invocation outcome
+    pub outcome_weight: f64,
+    /// Days without invocation after which the score is halved
+    pub half_life_days: f64,
+    /// Pattern scores: weight of the normalized frequency
+    pub pattern_freq_weight: f64,
+    /// Pattern scores: weight of the share of invocations that followed the section
+    pub pattern_usage_weight: f64,
+    /// Invocations of a skill needed before unfollowed sections are dropped
+    pub min_section_invocations: usize,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        ScoringConfig {
+            fire_weight: 0.6,
+            pattern_weight: 0.4,
+            productive_base: 0.5,
+            productive_weight: 0.5,
+            outcome_base: 0.5,
+            outcome_weight: 0.5,
+            half_life_days: 7.0,
+            pattern_freq_weight: 0.4,
+            pattern_usage_weight: 0.6,
+            min_section_invocations: 3,
+        }
+    }
+}
+
+/// Runtime scoring config path (`~/.config/skill-miner/scoring.toml`).
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("skill-miner").join("scoring.toml"))
+}
+
+/// Parse a scoring config; a non-positive half-life is rejected.
+pub fn parse_config(content: &str) -> Result<ScoringConfig, String> {
+    let config: ScoringConfig = toml::from_str(content).map_err(|e| e.to_string())?;
+    if config.half_life_days <= 0.0 {
+        return Err("half_life_days must be positive".to_string());
+    }
+    Ok(config)
+}
 
-// --- score_skills constants ---
-const FIRE_WEIGHT: f64 = 0.6;
-const PATTERN_WEIGHT: f64 = 0.4;
-const PRODUCTIVE_BASE: f64 = 0.5;
-const PRODUCTIVE_WEIGHT: f64 = 0.5;
-const DORMANCY_SEVERE_DAYS: i64 = 14;
-const DORMANCY_SEVERE_MULT: f64 = 0.2;
-const DORMANCY_MODERATE_DAYS: i64 = 7;
-const DORMANCY_MODERATE_MULT: f64 = 0.5;
-
-// --- score_patterns constants ---
-const PATTERN_FREQ_WEIGHT: f64 = 0.4;
-const PATTERN_FIRE_WEIGHT: f64 = 0.6;
-
-/// Score each skill based on invocation frequency, pattern richness, and productivity.
-/// Returns Vec<(slug, score)> sorted by score descending.
+/// Active scoring config, lazily loaded. Falls back to the defaults if the runtime
+/// config is missing or invalid.
+static CONFIG: LazyLock<ScoringConfig> = LazyLock::new(|| {
+    if let Some(path) = config_path() {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            match parse_config(&content) {
+                Ok(config) => return config,
+                Err(e) => eprintln!("warn: invalid {}, using default scoring: {e}", path.display()),
+            }
+        }
+    }
+    ScoringConfig::default()
+});
+
+/// Access the active scoring config.
+pub fn config() -> &'static ScoringConfig {
+    &CONFIG
+}
+
+/// How well an invocation went (0..1): successful tool calls, no correction by the user,
+/// and a commit or passing tests afterwards.
+pub fn outcome_score(outcome: &InvocationOutcome) -> f64 {
+    let success = if outcome.tool_calls > 0 {
+        1.0 - outcome.failed_tool_calls as f64 / outcome.tool_calls as f64
+    } else {
+        0.0
+    };
+    let uncorrected = if outcome.corrected { 0.0 } else { 1.0 };
+    let finished = if outcome.committed || outcome.tests_passed == Some(true) {
+        1.0
+    } else {
+        0.0
+    };
+    OUTCOME_SUCCESS_WEIGHT * success
+        + OUTCOME_UNCORRECTED_WEIGHT * uncorrected
+        + OUTCOME_FINISHED_WEIGHT * finished
+}
+
+/// Score each skill based on invocation frequency, pattern richness, productivity, outcome
+/// and time since last use. Returns Vec<(slug, score)> sorted by score descending.
 pub fn score_skills(
     invocations: &[SkillInvocation],
     manifest: &Manifest,
     clusters: &[DomainCluster],
+    config: &ScoringConfig,
 ) -> Vec<(String, f64)> {
+    explain_skills(invocations, manifest, clusters, config)
+        .into_iter()
+        .map(|b| (b.slug, b.score))
+        .collect()
+}
+
+/// Score each skill with every factor that went into it, sorted by score descending.
+pub fn explain_skills(
+    invocations: &[SkillInvocation],
+    manifest: &Manifest,
+    clusters: &[DomainCluster],
+    config: &ScoringConfig,
+) -> Vec<ScoreBreakdown> {
     if manifest.entries.is_empty() {
         return vec![];
     }
 
-    // Group invocations by skill_name -> (total_count, productive_count)
-    let mut inv_map: HashMap<&str, (usize, usize)> = HashMap::new();
+    // Group invocations by skill_name
+    #[derive(Default, Clone, Copy)]
+    struct Fires {
+        count: usize,
+        productive: usize,
+        outcome_sum: f64,
+        last: Option<DateTime<Utc>>,
+    }
+    let mut inv_map: HashMap<&str, Fires> = HashMap::new();
     for inv in invocations {
-        let entry = inv_map.entry(inv.skill_name.as_str()).or_insert((0, 0));
-        entry.0 += 1;
+        let fires = inv_map.entry(inv.skill_name.as_str()).or_default();
+        fires.count += 1;
         if inv.was_productive {
-            entry.1 += 1;
+            fires.productive += 1;
         }
+        fires.outcome_sum += outcome_score(&inv.outcome);
+        fires.last = fires.last.max(inv.timestamp);
     }
 
     // Build cluster lookup: slug -> &DomainCluster
@@ -57,36 +183,12 @@ pub fn score_skills(
         })
         .collect();
 
-    // Build deployed_at lookup: slug -> Option<days_since_deploy>
-    let deployed_map: HashMap<&str, Option<i64>> = manifest
-        .entries
-        .iter()
-        .map(|e| {
-            let days = e.deployed_at.map(|dt| (Utc::now() - dt).num_days());
-            (e.slug.as_str(), days)
-        })
-        .collect();
-
-    // Compute raw values per manifest entry
-    struct RawScore {
-        slug: String,
-        fire_count: usize,
-        productive_count: usize,
-        pattern_freq_sum: usize,
-        deployed_days: Option<i64>,
-    }
-
-    let mut raw_scores: Vec<RawScore> = Vec::with_capacity(manifest.entries.len());
+    let now = Utc::now();
+    let mut breakdowns: Vec<ScoreBreakdown> = Vec::with_capacity(manifest.entries.len());
     for entry in &manifest.entries {
-        let fire_count = entry
-            .fire_count
-            .or_else(|| inv_map.get(entry.slug.as_str()).map(|(c, _)| *c))
-            .unwrap_or(0);
-
-        let productive_count = inv_map
-            .get(entry.slug.as_str())
-            .map(|(_, p)| *p)
-            .unwrap_or(0);
+        let fires = inv_map.get(entry.slug.as_str()).copied().unwrap_or_default();
+        let fire_count = entry.fire_count.unwrap_or(fires.count);
+        let (productive_count, last_invoked) = (fires.productive, fires.last);
 
         // Match entry to cluster via normalized slug
         let entry_slug = &domains::normalize(&entry.domain).slug;
@@ -95,81 +197,86 @@ pub fn score_skills(
             .map(|c| c.patterns.iter().map(|p| p.frequency).sum::<usize>())
             .unwrap_or(0);
 
-        let deployed_days = deployed_map
-            .get(entry.slug.as_str())
-            .copied()
-            .flatten();
+        // Inactive since the last invocation, or since the deploy if not invoked after it
+        let last_active = last_invoked.max(entry.deployed_at);
+        let days_inactive =
+            last_active.map(|t| ((now - t).num_seconds() as f64 / 86400.0).max(0.0));
 
-        raw_scores.push(RawScore {
+        breakdowns.push(ScoreBreakdown {
             slug: entry.slug.clone(),
             fire_count,
-            productive_count,
+            max_fire_count: 0,
+            fire_score: 0.0,
             pattern_freq_sum,
-            deployed_days,
+            max_pattern_freq_sum: 0,
+            pattern_score: 0.0,
+            productive_count,
+            productive_rate: if fire_count > 0 {
+                productive_count as f64 / fire_count as f64
+            } else {
+                1.0
+            },
+            outcome_rate: if fires.count > 0 {
+                fires.outcome_sum / fires.count as f64
+            } else {
+                1.0
+            },
+            outcome_multiplier: 0.0,
+            last_invoked,
+            days_inactive,
+            decay: days_inactive.map_or(1.0, |d| 0.5f64.powf(d / config.half_life_days)),
+            base_score: 0.0,
+            productive_multiplier: 0.0,
+            score: 0.0,
         });
     }
 
-    let max_fire = raw_scores.iter().map(|r| r.fire_count).max().unwrap_or(0);
-    let max_pattern = raw_scores
+    let max_fire = breakdowns.iter().map(|b| b.fire_count).max().unwrap_or(0);
+    let max_pattern = breakdowns
         .iter()
-        .map(|r| r.pattern_freq_sum)
+        .map(|b| b.pattern_freq_sum)
         .max()
         .unwrap_or(0);
 
-    let mut results: Vec<(String, f64)> = raw_scores
-        .into_iter()
-        .map(|r| {
-            let fire_score = if max_fire > 0 {
-                r.fire_count as f64 / max_fire as f64
-            } else {
-                0.0
-            };
-            let pattern_score = if max_pattern > 0 {
-                r.pattern_freq_sum as f64 / max_pattern as f64
-            } else {
-                0.0
-            };
-            let productive_rate = if r.fire_count > 0 {
-                r.productive_count as f64 / r.fire_count as f64
-            } else {
-                1.0
-            };
-            let dormancy_multiplier = if r.fire_count == 0 {
-                match r.deployed_days {
-                    Some(days) if days > DORMANCY_SEVERE_DAYS => DORMANCY_SEVERE_MULT,
-                    Some(days) if days > DORMANCY_MODERATE_DAYS => DORMANCY_MODERATE_MULT,
-                    _ => 1.0,
-                }
-            } else {
-                1.0
-            };
-            let base_score = FIRE_WEIGHT * fire_score + PATTERN_WEIGHT * pattern_score;
-            let productive_multiplier = PRODUCTIVE_BASE + PRODUCTIVE_WEIGHT * productive_rate;
-            let score = base_score * productive_multiplier * dormancy_multiplier;
-            (r.slug, score)
-        })
-        .collect();
+    for b in &mut breakdowns {
+        b.max_fire_count = max_fire;
+        b.max_pattern_freq_sum = max_pattern;
+        b.fire_score = if max_fire > 0 {
+            b.fire_count as f64 / max_fire as f64
+        } else {
+            0.0
+        };
+        b.pattern_score = if max_pattern > 0 {
+            b.pattern_freq_sum as f64 / max_pattern as f64
+        } else {
+            0.0
+        };
+        b.base_score = config.fire_weight * b.fire_score + config.pattern_weight * b.pattern_score;
+        b.productive_multiplier =
+            config.productive_base + config.productive_weight * b.productive_rate;
+        b.outcome_multiplier = config.outcome_base + config.outcome_weight * b.outcome_rate;
+        b.score = b.base_score * b.productive_multiplier * b.outcome_multiplier * b.decay;
+    }
 
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    results
+    breakdowns.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    breakdowns
 }
 
-/// Score patterns within a cluster based on frequency and invocation overlap.
+/// Score patterns within a cluster based on frequency and how often the section was
+/// followed after its skill was invoked (see `attribution`). Once a skill has
+/// `min_section_invocations` invocations, sections nobody followed score 0; with fewer, usage
+/// counts as full and patterns rank by frequency alone.
 /// Returns Vec<(pattern_index, score)> sorted by score descending.
 pub fn score_patterns(
     cluster: &DomainCluster,
     invocations: &[SkillInvocation],
+    config: &ScoringConfig,
 ) -> Vec<(usize, f64)> {
     if cluster.patterns.is_empty() {
         return vec![];
     }
 
-    // Collect conversation IDs from invocations for fast lookup
-    let inv_conversations: std::collections::HashSet<&str> = invocations
-        .iter()
-        .map(|i| i.conversation_id.as_str())
-        .collect();
-
+    let usage = attribution::section_usage(cluster, invocations);
     let max_frequency = cluster
         .patterns
         .iter()
@@ -180,26 +287,24 @@ pub fn score_patterns(
     let mut results: Vec<(usize, f64)> = cluster
         .patterns
         .iter()
+        .zip(&usage)
         .enumerate()
-        .map(|(idx, pattern)| {
+        .map(|(idx, (pattern, usage))| {
             let frequency_score = if max_frequency > 0 {
                 pattern.frequency as f64 / max_frequency as f64
             } else {
                 0.0
             };
 
-            let source_fire_score = if pattern.source_ids.is_empty() {
-                0.0
+            let usage_score = if usage.invocations < config.min_section_invocations {
+                1.0
+            } else if usage.used == 0 {
+                return (idx, 0.0);
             } else {
-                let hits = pattern
-                    .source_ids
-                    .iter()
-                    .filter(|sid| inv_conversations.contains(sid.as_str()))
-                    .count();
-                hits as f64 / pattern.source_ids.len() as f64
+                usage.used as f64 / usage.invocations as f64
             };
-
-            let score = PATTERN_FREQ_WEIGHT * frequency_score + PATTERN_FIRE_WEIGHT * source_fire_score;
+            let score = config.pattern_freq_weight * frequency_score
+                + config.pattern_usage_weight * usage_score;
             (idx, score)
         })
         .collect();
@@ -221,6 +326,13 @@ mod tests {
             timestamp: Some(Utc::now()),
             was_productive: productive,
             trigger_context: None,
+            // A clean run: the tool call succeeded and the change was committed
+            outcome: InvocationOutcome {
+                tool_calls: 1,
+                committed: true,
+                ..Default::default()
+            },
+            followed: Vec::new(),
         }
     }
 
@@ -231,6 +343,8 @@ mod tests {
             entries,
             mined_ids: std::collections::HashSet::new(),
             pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
         }
     }
 
@@ -242,10 +356,9 @@ mod tests {
             pattern_count: 0,
             conversation_count: 0,
             generated_at: Utc::now(),
-            deployed_at: None,
             content_hash: "abc".to_string(),
-            score: None,
             fire_count,
+            ..Default::default()
         }
     }
 
@@ -266,19 +379,20 @@ mod tests {
             source_ids: source_ids.into_iter().map(String::from).collect(),
             frequency,
             skill_slug: None,
+            project: None,
         }
     }
 
     #[test]
     fn empty_inputs_return_empty() {
-        let result = score_skills(&[], &make_manifest(vec![]), &[]);
+        let result = score_skills(&[], &make_manifest(vec![]), &[], &ScoringConfig::default());
         assert!(result.is_empty());
     }
 
     #[test]
     fn empty_patterns_return_empty() {
         let cluster = make_cluster("Miscellaneous", vec![]);
-        let result = score_patterns(&cluster, &[]);
+        let result = score_patterns(&cluster, &[], &ScoringConfig::default());
         assert!(result.is_empty());
     }
 
@@ -297,7 +411,7 @@ mod tests {
             make_cluster("Miscellaneous", vec![make_pattern(1, vec![])]),
         ];
 
-        let result = score_skills(&invocations, &manifest, &clusters);
+        let result = score_skills(&invocations, &manifest, &clusters, &ScoringConfig::default());
         assert_eq!(result.len(), 2);
         // pavement should be first (higher score)
         assert_eq!(result[0].0, "pavement");
@@ -319,8 +433,9 @@ mod tests {
         let manifest = make_manifest(vec![make_entry("pavement", "Web Development", Some(2))]);
         let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec!["c1"])])];
 
-        let result_prod = score_skills(&invocations_productive, &manifest, &clusters);
-        let result_unprod = score_skills(&invocations_unproductive, &manifest, &clusters);
+        let config = ScoringConfig::default();
+        let result_prod = score_skills(&invocations_productive, &manifest, &clusters, &config);
+        let result_unprod = score_skills(&invocations_unproductive, &manifest, &clusters, &config);
 
         // productive_rate = 1.0 => multiplier = 1.0
         // productive_rate = 0.0 => multiplier = 0.5
@@ -339,7 +454,7 @@ mod tests {
         );
         let invocations = vec![make_invocation("pavement", "c1", true)];
 
-        let result = score_patterns(&cluster, &invocations);
+        let result = score_patterns(&cluster, &invocations, &ScoringConfig::default());
         assert_eq!(result.len(), 3);
         // Pattern index 1 (frequency=5) should be first
         assert_eq!(result[0].0, 1);
@@ -356,7 +471,7 @@ mod tests {
         let manifest = make_manifest(vec![make_entry("pavement", "Web Development", Some(1))]);
         let clusters = vec![make_cluster("Web Development", vec![make_pattern(3, vec!["c1"])])];
 
-        let result = score_skills(&invocations, &manifest, &clusters);
+        let result = score_skills(&invocations, &manifest, &clusters, &ScoringConfig::default());
         assert_eq!(result.len(), 1);
         // fire_score = 1/1 = 1.0, pattern_score = 3/3 = 1.0
         // productive_rate = 1/1 = 1.0
@@ -365,27 +480,37 @@ mod tests {
     }
 
     #[test]
-    fn pattern_source_fire_score_works() {
-        let cluster = make_cluster(
-            "Web Development",
-            vec![
-                make_pattern(2, vec!["c1", "c2"]), // both in invocations
-                make_pattern(2, vec!["c3", "c4"]), // neither in invocations
-            ],
-        );
-        let invocations = vec![
-            make_invocation("pavement", "c1", true),
-            make_invocation("pavement", "c2", true),
-        ];
+    fn pattern_usage_score_drops_unfollowed_sections() {
+        let mut tests = make_pattern(2, vec![]);
+        tests.steps = vec!["Run `cargo test`".to_string()];
+        let mut bench = make_pattern(2, vec![]);
+        bench.steps = vec!["Run `cargo bench`".to_string()];
+        let cluster = make_cluster("Rust", vec![tests, bench]);
+        let slug = domains::normalize("Rust").slug.clone();
+
+        let mut invocation = make_invocation(&slug, "c1", true);
+        invocation.followed = vec![ToolUse {
+            name: "Bash".to_string(),
+            input_summary: String::new(),
+            file_path: None,
+            command: Some("cargo test".to_string()),
+            task: None,
+            failed: false,
+        }];
+        let config = ScoringConfig::default();
+
+        // Too few invocations to tell: both count as followed
+        let result = score_patterns(&cluster, &[invocation.clone()], &config);
+        assert!((result[0].1 - 1.0).abs() < f64::EPSILON);
+        assert!((result[1].1 - 1.0).abs() < f64::EPSILON);
 
-        let result = score_patterns(&cluster, &invocations);
-        assert_eq!(result.len(), 2);
-        // First pattern: freq_score = 1.0, source_fire = 2/2 = 1.0 -> 0.4 + 0.6 = 1.0
-        // Second pattern: freq_score = 1.0, source_fire = 0/2 = 0.0 -> 0.4 + 0.0 = 0.4
+        // Followed in 2 of 3 invocations: 0.4*1.0 + 0.6*(2/3); never followed: 0
+        let mut idle = invocation.clone();
+        idle.followed.clear();
+        let result = score_patterns(&cluster, &[invocation.clone(), invocation, idle], &config);
         assert_eq!(result[0].0, 0);
-        assert!((result[0].1 - 1.0).abs() < f64::EPSILON);
-        assert_eq!(result[1].0, 1);
-        assert!((result[1].1 - 0.4).abs() < f64::EPSILON);
+        assert!((result[0].1 - 0.8).abs() < 1e-9);
+        assert_eq!(result[1], (1, 0.0));
     }
 
     #[test]
@@ -400,7 +525,7 @@ mod tests {
             make_cluster("Miscellaneous", vec![make_pattern(1, vec![])]),
         ];
 
-        let result = score_skills(&[], &manifest, &clusters);
+        let result = score_skills(&[], &manifest, &clusters, &ScoringConfig::default());
         // fire_score = 0 for both, but pattern_score differs
         // productive_rate = 1.0 (no fires)
         // pavement: (0.6*0 + 0.4*1.0) * 1.0 = 0.4
@@ -423,60 +548,87 @@ mod tests {
     }
 
     #[test]
-    fn dormancy_penalty_zero_fires_new_deploy() {
-        // deployed_at = now, fire_count=0 -> no penalty (1.0 multiplier)
-        let manifest = make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(0), 0)]);
+    fn decay_new_deploy_has_no_penalty() {
+        let manifest =
+            make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(0), 0)]);
         let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec![])])];
 
-        let result = score_skills(&[], &manifest, &clusters);
-        // pattern_score = 1.0, fire_score = 0.0
-        // base = 0.6*0 + 0.4*1.0 = 0.4, productive = 1.0, dormancy = 1.0
-        // score = 0.4 * 1.0 * 1.0 = 0.4
+        let result = score_skills(&[], &manifest, &clusters, &ScoringConfig::default());
+        // base = 0.6*0 + 0.4*1.0 = 0.4, productive = 1.0, decay = 0.5^0 = 1.0
         assert_eq!(result.len(), 1);
-        assert!((result[0].1 - 0.4).abs() < f64::EPSILON);
+        assert!((result[0].1 - 0.4).abs() < 1e-6);
     }
 
     #[test]
-    fn dormancy_penalty_zero_fires_8_days() {
-        // deployed_at = 8 days ago, fire_count=0 -> 0.5 multiplier
-        let manifest =
-            make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(0), 8)]);
+    fn decay_halves_per_half_life_since_deploy() {
+        let manifest = make_manifest(vec![
+            make_entry_deployed("week", "Web Development", Some(0), 7),
+            make_entry_deployed("fortnight", "Web Development", Some(0), 14),
+        ]);
         let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec![])])];
 
-        let result = score_skills(&[], &manifest, &clusters);
-        // base = 0.4, productive = 1.0, dormancy = 0.5
-        // score = 0.4 * 1.0 * 0.5 = 0.2
-        assert_eq!(result.len(), 1);
-        assert!((result[0].1 - 0.2).abs() < f64::EPSILON);
+        let result = score_skills(&[], &manifest, &clusters, &ScoringConfig::default());
+        // base = 0.4, productive = 1.0, decay = 0.5^(7/7) and 0.5^(14/7)
+        assert_eq!(result[0].0, "week");
+        assert!((result[0].1 - 0.2).abs() < 1e-6);
+        assert_eq!(result[1].0, "fortnight");
+        assert!((result[1].1 - 0.1).abs() < 1e-6);
     }
 
     #[test]
-    fn dormancy_penalty_zero_fires_15_days() {
-        // deployed_at = 15 days ago, fire_count=0 -> 0.2 multiplier
+    fn decay_counts_from_last_invocation() {
+        let mut old = make_invocation("pavement", "c1", true);
+        old.timestamp = Some(Utc::now() - chrono::Duration::days(14));
         let manifest =
-            make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(0), 15)]);
-        let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec![])])];
+            make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(1), 20)]);
+        let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec!["c1"])])];
+        let config = ScoringConfig::default();
 
-        let result = score_skills(&[], &manifest, &clusters);
-        // base = 0.4, productive = 1.0, dormancy = 0.2
-        // score = 0.4 * 1.0 * 0.2 = 0.08
-        assert_eq!(result.len(), 1);
-        assert!((result[0].1 - 0.08).abs() < f64::EPSILON);
+        // Fired, but not for two half-lives: base = 1.0, productive = 1.0, decay = 0.25
+        let result = score_skills(&[old], &manifest, &clusters, &config);
+        assert!((result[0].1 - 0.25).abs() < 1e-6);
+
+        // Invoked just now: the deploy age no longer matters
+        let recent = make_invocation("pavement", "c1", true);
+        let breakdown = explain_skills(&[recent], &manifest, &clusters, &config);
+        assert!(breakdown[0].days_inactive.unwrap() < 0.01);
+        assert!((breakdown[0].score - 1.0).abs() < 1e-6);
     }
 
     #[test]
-    fn dormancy_penalty_has_fires_ignores_days() {
-        // deployed_at = 15 days ago, fire_count > 0 -> no penalty
-        let invocations = vec![make_invocation("pavement", "c1", true)];
-        let manifest =
-            make_manifest(vec![make_entry_deployed("pavement", "Web Development", Some(1), 15)]);
+    fn bad_outcomes_lower_score() {
+        let manifest = make_manifest(vec![make_entry("pavement", "Web Development", Some(2))]);
         let clusters = vec![make_cluster("Web Development", vec![make_pattern(2, vec!["c1"])])];
+        let config = ScoringConfig::default();
+        let good = vec![
+            make_invocation("pavement", "c1", true),
+            make_invocation("pavement", "c2", true),
+        ];
+        let mut bad = good.clone();
+        bad[1].outcome = InvocationOutcome {
+            tool_calls: 2,
+            failed_tool_calls: 2,
+            corrected: true,
+            committed: false,
+            tests_passed: Some(false),
+        };
+        assert_eq!(outcome_score(&bad[1].outcome), 0.0);
+
+        let b = explain_skills(&bad, &manifest, &clusters, &config).remove(0);
+        // outcome_rate = (1.0 + 0.0) / 2 = 0.5 -> multiplier = 0.5 + 0.5*0.5 = 0.75
+        assert!((b.outcome_rate - 0.5).abs() < 1e-9);
+        assert!((b.score - 0.75).abs() < 1e-6);
+        let good_score = score_skills(&good, &manifest, &clusters, &config)[0].1;
+        assert!((good_score - 1.0).abs() < 1e-6);
+    }
 
-        let result = score_skills(&invocations, &manifest, &clusters);
-        // fire_score=1.0, pattern_score=1.0, productive_rate=1.0
-        // base = 0.6*1.0 + 0.4*1.0 = 1.0, productive = 1.0, dormancy = 1.0
-        // score = 1.0 * 1.0 * 1.0 = 1.0
-        assert_eq!(result.len(), 1);
-        assert!((result[0].1 - 1.0).abs() < f64::EPSILON);
+    #[test]
+    fn parse_config_keeps_defaults_for_missing_keys() {
+        let config = parse_config("half_life_days = 30.0\nfire_weight = 0.8\n").unwrap();
+        assert_eq!(config.half_life_days, 30.0);
+        assert_eq!(config.fire_weight, 0.8);
+        assert_eq!(config.pattern_weight, ScoringConfig::default().pattern_weight);
+        assert!(parse_config("half_life_days = 0.0").is_err());
+        assert!(parse_config("fire_wieght = 0.8").is_err());
     }
 }
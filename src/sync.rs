@@ -50,6 +50,38 @@ pub fn ensure_git_repo(dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Append a line to a file under `.git/info/` unless it is already there.
+/// Local git config kept there never shows up in the working tree.
+fn ensure_info_line(dir: &Path, file: &str, line: &str) -> Result<()> {
+    let info = dir.join(".git").join("info");
+    std::fs::create_dir_all(&info)?;
+    let path = info.join(file);
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    if !existing.lines().any(|l| l.trim() == line) {
+        let mut content = existing;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(line);
+        content.push('\n');
+        std::fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+/// Merge audit logs line by line (git's union driver), so events appended on two machines
+/// are both kept instead of conflicting.
+pub fn ensure_audit_merge(dir: &Path) -> Result<()> {
+    ensure_info_line(dir, "attributes", &format!("{} merge=union", crate::audit::AUDIT_FILE))
+}
+
+/// Keep drafts directory locks out of commits (`mine --sync` runs while holding one),
+/// and deploy snapshots, which hold paths of this machine (in every scope dir).
+pub fn ensure_lock_excluded(dir: &Path) -> Result<()> {
+    ensure_info_line(dir, "exclude", crate::lock::LOCK_FILE)?;
+    ensure_info_line(dir, "exclude", &format!("{}/", crate::transaction::DEPLOYS_DIR))
+}
+
 /// Check if a remote is configured for the repository.
 fn has_remote(dir: &Path, remote: &str) -> bool {
     git(dir, &["remote", "get-url", remote]).is_ok()
@@ -151,6 +183,12 @@ pub fn sync_drafts(config: &SyncConfig, new_drafts: usize, updated_drafts: usize
         eprintln!("[sync] failed to ensure git repo: {}", e);
         return result;
     }
+    if let Err(e) = ensure_audit_merge(&config.drafts_dir) {
+        eprintln!("[sync] failed to set audit log merge driver: {}", e);
+    }
+    if let Err(e) = ensure_lock_excluded(&config.drafts_dir) {
+        eprintln!("[sync] failed to exclude lock files: {}", e);
+    }
 
     // Check for changes
     let status = match git(&config.drafts_dir, &["status", "--porcelain"]) {
@@ -343,4 +381,60 @@ mod tests {
         assert!(result.committed);
         assert!(!result.pushed);
     }
+
+    #[test]
+    fn test_sync_keeps_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_dir = dir.path().join("sync-audit");
+        ensure_git_repo(&repo_dir).unwrap();
+        let _ = git(&repo_dir, &["config", "user.email", "test@test.com"]);
+        let _ = git(&repo_dir, &["config", "user.name", "Test"]);
+
+        let drafts = repo_dir.join("drafts");
+        let _lock = crate::lock::DirLock::acquire(&drafts, "mine").unwrap();
+        let mf = crate::types::Manifest {
+            version: "1.0".to_string(),
+            generated_at: chrono::Utc::now(),
+            entries: Vec::new(),
+            mined_ids: Default::default(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        };
+        crate::manifest::write_manifest(&drafts, &mf).unwrap();
+        crate::audit::append(
+            &drafts,
+            &[crate::types::AuditEvent {
+                at: chrono::Utc::now(),
+                slug: "s".to_string(),
+                action: crate::types::AuditAction::Approved,
+                from: None,
+                to: None,
+                score: None,
+                reason: None,
+                actor: None,
+            }],
+        )
+        .unwrap();
+
+        let config = SyncConfig {
+            drafts_dir: repo_dir.clone(),
+            remote: "origin".to_string(),
+            branch: "main".to_string(),
+        };
+        let result = sync_drafts(&config, 0, 0);
+        assert!(result.committed);
+        assert_eq!(result.files_changed, 1); // attributes live outside the tree
+
+        let tracked = git(&repo_dir, &["ls-files"]).unwrap();
+        assert!(tracked.contains("drafts/audit.jsonl"));
+        assert!(!tracked.contains(crate::lock::LOCK_FILE));
+        let attr = git(&repo_dir, &["check-attr", "merge", "drafts/audit.jsonl"]).unwrap();
+        assert!(attr.ends_with("merge: union"));
+
+        // Idempotent
+        ensure_audit_merge(&repo_dir).unwrap();
+        let attrs = std::fs::read_to_string(repo_dir.join(".git/info/attributes")).unwrap();
+        assert_eq!(attrs.matches("merge=union").count(), 1);
+    }
 }
@@ -0,0 +1,215 @@
+// Targets module: where a draft is deployed
+// A draft deploys to the global skills dir or to `<repo>/.claude/skills` of one or more
+// repositories. A new draft built from project-specific patterns targets their repository,
+// any other draft is global; `target` changes them by hand. Each target keeps its own
+// deployment state.
+
+use crate::project;
+use crate::types::{DeployTarget, DraftEntry, KnowledgePattern};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+/// Target name of the configured skills dir (`~/.claude/skills`).
+pub const GLOBAL: &str = "global";
+
+impl DeployTarget {
+    pub fn new(path: &str) -> Self {
+        DeployTarget {
+            path: path.to_string(),
+            deployed_at: None,
+            deployed_hash: None,
+        }
+    }
+
+    pub fn is_global(&self) -> bool {
+        self.path == GLOBAL
+    }
+}
+
+/// Parse a target argument: "global", or a project path (made absolute and normalized).
+pub fn parse(arg: &str) -> String {
+    if arg == GLOBAL {
+        return GLOBAL.to_string();
+    }
+    let path = Path::new(arg);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map(|d| d.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    };
+    project::normalize_project(&path.to_string_lossy())
+}
+
+/// Skills directory of a target.
+pub fn dir(skills_dir: &Path, target: &str) -> PathBuf {
+    if target == GLOBAL {
+        skills_dir.to_path_buf()
+    } else {
+        Path::new(target).join(".claude").join("skills")
+    }
+}
+
+/// Target paths of an entry; a draft without targets deploys globally.
+pub fn of(entry: &DraftEntry) -> Vec<String> {
+    if entry.targets.is_empty() {
+        vec![GLOBAL.to_string()]
+    } else {
+        entry.targets.iter().map(|t| t.path.clone()).collect()
+    }
+}
+
+/// Hash of the content last deployed to a target.
+pub fn deployed_hash<'a>(entry: &'a DraftEntry, target: &str) -> Option<&'a str> {
+    if target == GLOBAL {
+        return entry.deployed_hash.as_deref();
+    }
+    entry
+        .targets
+        .iter()
+        .find(|t| t.path == target)
+        .and_then(|t| t.deployed_hash.as_deref())
+}
+
+/// When the draft was last deployed to a target.
+pub fn deployed_at(entry: &DraftEntry, target: &str) -> Option<DateTime<Utc>> {
+    if target == GLOBAL {
+        return entry.deployed_at;
+    }
+    entry
+        .targets
+        .iter()
+        .find(|t| t.path == target)
+        .and_then(|t| t.deployed_at)
+}
+
+/// Record a deploy of `hash` to a target.
+pub fn mark_deployed(entry: &mut DraftEntry, target: &str, hash: &str) {
+    let now = Utc::now();
+    if target == GLOBAL {
+        entry.deployed_at = Some(now);
+        entry.deployed_hash = Some(hash.to_string());
+    } else if let Some(t) = entry.targets.iter_mut().find(|t| t.path == target) {
+        t.deployed_at = Some(now);
+        t.deployed_hash = Some(hash.to_string());
+    }
+}
+
+/// Forget the deployment state of a target.
+pub fn clear_deployed(entry: &mut DraftEntry, target: &str) {
+    if target == GLOBAL {
+        entry.deployed_at = None;
+        entry.deployed_hash = None;
+    } else if let Some(t) = entry.targets.iter_mut().find(|t| t.path == target) {
+        t.deployed_at = None;
+        t.deployed_hash = None;
+    }
+}
+
+/// Repository root containing `path` (nearest ancestor with `.git`), or `path` itself.
+pub fn repo_root(path: &str) -> String {
+    Path::new(path)
+        .ancestors()
+        .find(|p| p.join(".git").exists())
+        .map(|p| project::normalize_project(&p.to_string_lossy()))
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Targets inferred from the patterns of a draft: the repository they are all specific to,
+/// or none (global) when any of them is not project-specific.
+pub fn infer(patterns: &[&KnowledgePattern]) -> Vec<DeployTarget> {
+    let Some(project) = patterns.first().and_then(|p| p.project.as_deref()) else {
+        return Vec::new();
+    };
+    if patterns.iter().all(|p| p.project.as_deref() == Some(project)) {
+        vec![DeployTarget::new(&repo_root(project))]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Replace the targets of an entry, keeping the deployment state of targets that remain.
+/// The global target's state lives on the entry, so it is never dropped here.
+pub fn set(entry: &mut DraftEntry, paths: &[String]) {
+    let old = std::mem::take(&mut entry.targets);
+    entry.targets = paths
+        .iter()
+        .map(|p| {
+            old.iter()
+                .find(|t| t.path == *p)
+                .cloned()
+                .unwrap_or_else(|| DeployTarget::new(p))
+        })
+        .collect();
+    // Only "global": same as no targets
+    if entry.targets.len() == 1 && entry.targets[0].is_global() {
+        entry.targets.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(title: &str, project: Option<&str>) -> KnowledgePattern {
+        KnowledgePattern {
+            title: title.to_string(),
+            description: format!("{} description", title),
+            steps: vec!["step".to_string()],
+            code_examples: vec![],
+            source_ids: vec!["c1".to_string()],
+            frequency: 1,
+            skill_slug: None,
+            project: project.map(String::from),
+        }
+    }
+
+    #[test]
+    fn infer_project_specific_or_global() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(repo.path().join(".git")).unwrap();
+        std::fs::create_dir_all(repo.path().join("crates/core")).unwrap();
+        let root = project::normalize_project(&repo.path().to_string_lossy());
+        let sub = format!("{}/crates/core", root);
+
+        let (a, b) = (pattern("a", Some(&sub)), pattern("b", Some(&sub)));
+        let targets = infer(&[&a, &b]);
+        assert_eq!(targets, vec![DeployTarget::new(&root)]);
+
+        // Patterns that merely ran in one repository are not project-specific
+        let general = pattern("c", None);
+        assert!(infer(&[&a, &general]).is_empty());
+        assert!(infer(&[&general]).is_empty());
+        let other = pattern("d", Some("/elsewhere"));
+        assert!(infer(&[&a, &other]).is_empty());
+        assert!(infer(&[]).is_empty());
+    }
+
+    #[test]
+    fn set_keeps_state_of_remaining_targets() {
+        let mut entry = DraftEntry {
+            slug: "s".to_string(),
+            domain: "D".to_string(),
+            status: crate::types::DraftStatus::Deployed,
+            pattern_count: 1,
+            conversation_count: 1,
+            generated_at: Utc::now(),
+            content_hash: "h".to_string(),
+            targets: vec![DeployTarget::new("/repo")],
+            ..Default::default()
+        };
+        mark_deployed(&mut entry, "/repo", "abc");
+        assert_eq!(of(&entry), vec!["/repo"]);
+
+        set(&mut entry, &[GLOBAL.to_string(), "/repo".to_string()]);
+        assert_eq!(deployed_hash(&entry, "/repo"), Some("abc"));
+        assert_eq!(deployed_hash(&entry, GLOBAL), None);
+        assert_eq!(
+            dir(Path::new("/home/u/.claude/skills"), "/repo"),
+            Path::new("/repo/.claude/skills")
+        );
+
+        set(&mut entry, &[GLOBAL.to_string()]);
+        assert!(entry.targets.is_empty());
+        assert_eq!(of(&entry), vec![GLOBAL]);
+    }
+}
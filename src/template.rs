@@ -0,0 +1,470 @@
+// Template module: pluggable skill body templates
+// Minimal mustache-like syntax: {{var}}, {{#if var}}...{{else}}...{{/if}}, {{#each list}}...{{/each}}
+//
+// The body template is loaded in this priority:
+// 1. Runtime config: `~/.config/skill-miner/templates/body.md`
+// 2. Embedded `templates/body.md` (compile-time, the default layout)
+
+use crate::error::SkillMinerError;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Embedded default body template (compile-time).
+const DEFAULT_BODY: &str = include_str!("../templates/body.md");
+
+/// File name of the body template inside the templates dir.
+pub const BODY_TEMPLATE: &str = "body.md";
+
+/// Variables available at the top level of a body template.
+const TOP_VARS: &[&str] = &["domain", "pattern_count", "conversation_count", "scored", "patterns"];
+/// Variables available inside `{{#each patterns}}`.
+const PATTERN_VARS: &[&str] = &[
+    "number",
+    "title",
+    "description",
+    "steps",
+    "examples",
+    "sources",
+    "frequency",
+    "repeated",
+    "score",
+    "project",
+];
+/// Variables available inside `{{#each steps}}`, `{{#each examples}}` and `{{#each sources}}`.
+const ITEM_VARS: &[&str] = &["number", "this"];
+
+/// Template context value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::List(items) => !items.is_empty(),
+            Value::Map(_) => true,
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Value::Str(s) => s.clone(),
+            Value::Bool(b) => if *b { "true".to_string() } else { String::new() },
+            Value::List(items) => items.iter().map(|v| v.render()).collect::<Vec<_>>().join(", "),
+            Value::Map(_) => String::new(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Str(n.to_string())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Var(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        name: String,
+        body: Vec<Node>,
+    },
+}
+
+/// A parsed template.
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+enum Tag<'a> {
+    Var(&'a str),
+    If(&'a str),
+    Each(&'a str),
+    Else,
+    EndIf,
+    EndEach,
+}
+
+fn parse_tag(tag: &str) -> Result<Tag<'_>, String> {
+    let tag = tag.trim();
+    let (kind, name) = match tag.split_once(char::is_whitespace) {
+        Some((kind, name)) => (kind, name.trim()),
+        None => (tag, ""),
+    };
+    let check_name = |name: &'_ str| {
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            Ok(())
+        } else {
+            Err(format!("invalid name in {{{{{}}}}}", tag))
+        }
+    };
+    match kind {
+        "#if" => check_name(name).map(|_| Tag::If(name)),
+        "#each" => check_name(name).map(|_| Tag::Each(name)),
+        "else" if name.is_empty() => Ok(Tag::Else),
+        "/if" if name.is_empty() => Ok(Tag::EndIf),
+        "/each" if name.is_empty() => Ok(Tag::EndEach),
+        _ if kind.starts_with('#') || kind.starts_with('/') => {
+            Err(format!("unknown block {{{{{}}}}}", tag))
+        }
+        _ => check_name(tag).map(|_| Tag::Var(tag)),
+    }
+}
+
+/// Open `#if`/`#each` block being parsed.
+struct Frame {
+    each: bool,
+    name: String,
+    line: usize,
+    then: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+}
+
+impl Frame {
+    fn nodes(&mut self) -> &mut Vec<Node> {
+        self.otherwise.as_mut().unwrap_or(&mut self.then)
+    }
+}
+
+fn syntax_error(line: usize, msg: String) -> SkillMinerError {
+    SkillMinerError::Config(format!("template line {}: {}", line, msg))
+}
+
+impl Template {
+    /// Parse and check a template. Errors name the offending line.
+    pub fn parse(source: &str) -> Result<Self, SkillMinerError> {
+        let source = source.replace("\r\n", "\n");
+        // A single trailing newline belongs to the file, not the output
+        let source = source.strip_suffix('\n').unwrap_or(&source);
+
+        let mut root: Vec<Node> = Vec::new();
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut rest = source;
+        let mut line = 1;
+
+        while !rest.is_empty() {
+            let Some(start) = rest.find("{{") else {
+                push_node(&mut root, &mut stack, Node::Text(rest.to_string()));
+                break;
+            };
+            if start > 0 {
+                push_node(&mut root, &mut stack, Node::Text(rest[..start].to_string()));
+                line += rest[..start].matches('\n').count();
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| syntax_error(line, "unclosed {{".to_string()))?;
+            let raw = &after[..end];
+            let tag = parse_tag(raw).map_err(|e| syntax_error(line, e))?;
+            match tag {
+                Tag::Var(name) => push_node(&mut root, &mut stack, Node::Var(name.to_string())),
+                Tag::If(name) | Tag::Each(name) => stack.push(Frame {
+                    each: matches!(tag, Tag::Each(_)),
+                    name: name.to_string(),
+                    line,
+                    then: Vec::new(),
+                    otherwise: None,
+                }),
+                Tag::Else => match stack.last_mut() {
+                    Some(frame) if !frame.each && frame.otherwise.is_none() => {
+                        frame.otherwise = Some(Vec::new())
+                    }
+                    _ => return Err(syntax_error(line, "{{else}} outside {{#if}}".to_string())),
+                },
+                Tag::EndIf | Tag::EndEach => {
+                    let closing_each = matches!(tag, Tag::EndEach);
+                    let frame = match stack.pop() {
+                        Some(frame) if frame.each == closing_each => frame,
+                        _ => {
+                            return Err(syntax_error(
+                                line,
+                                format!("unexpected {{{{{}}}}}", raw.trim()),
+                            ))
+                        }
+                    };
+                    let node = if frame.each {
+                        Node::Each {
+                            name: frame.name,
+                            body: frame.then,
+                        }
+                    } else {
+                        Node::If {
+                            name: frame.name,
+                            then: frame.then,
+                            otherwise: frame.otherwise.unwrap_or_default(),
+                        }
+                    };
+                    push_node(&mut root, &mut stack, node);
+                }
+            }
+            line += raw.matches('\n').count();
+            rest = &after[end + 2..];
+        }
+
+        if let Some(frame) = stack.last() {
+            let kind = if frame.each { "#each" } else { "#if" };
+            return Err(syntax_error(
+                frame.line,
+                format!("{{{{{} {}}}}} is never closed", kind, frame.name),
+            ));
+        }
+
+        check_vars(&root, &mut vec![TOP_VARS])?;
+        Ok(Template { nodes: root })
+    }
+
+    /// Render the template against a context map.
+    pub fn render(&self, context: &BTreeMap<String, Value>) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, &mut vec![context.clone()], &mut out);
+        out
+    }
+}
+
+fn push_node(root: &mut Vec<Node>, stack: &mut [Frame], node: Node) {
+    match stack.last_mut() {
+        Some(frame) => frame.nodes().push(node),
+        None => root.push(node),
+    }
+}
+
+/// Check that every variable is known in its scope and `#each` only iterates lists.
+fn check_vars(nodes: &[Node], scopes: &mut Vec<&'static [&'static str]>) -> Result<(), SkillMinerError> {
+    let known = |scopes: &Vec<&[&str]>, name: &str| scopes.iter().any(|s| s.contains(&name));
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Var(name) => {
+                if !known(scopes, name) {
+                    return Err(SkillMinerError::Config(format!(
+                        "template: unknown variable {{{{{}}}}}",
+                        name
+                    )));
+                }
+            }
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                if !known(scopes, name) {
+                    return Err(SkillMinerError::Config(format!(
+                        "template: unknown variable in {{{{#if {}}}}}",
+                        name
+                    )));
+                }
+                check_vars(then, scopes)?;
+                check_vars(otherwise, scopes)?;
+            }
+            Node::Each { name, body } => {
+                let item_scope = match name.as_str() {
+                    "patterns" if known(scopes, name) => PATTERN_VARS,
+                    "steps" | "examples" | "sources" if known(scopes, name) => ITEM_VARS,
+                    _ => {
+                        return Err(SkillMinerError::Config(format!(
+                            "template: cannot iterate over {{{{#each {}}}}}",
+                            name
+                        )))
+                    }
+                };
+                scopes.push(item_scope);
+                check_vars(body, scopes)?;
+                scopes.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn lookup<'a>(scopes: &'a [BTreeMap<String, Value>], name: &str) -> Option<&'a Value> {
+    scopes.iter().rev().find_map(|s| s.get(name))
+}
+
+fn render_nodes(nodes: &[Node], scopes: &mut Vec<BTreeMap<String, Value>>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => {
+                if let Some(v) = lookup(scopes, name) {
+                    out.push_str(&v.render());
+                }
+            }
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let truthy = lookup(scopes, name).map(|v| v.truthy()).unwrap_or(false);
+                render_nodes(if truthy { then } else { otherwise }, scopes, out);
+            }
+            Node::Each { name, body } => {
+                let items = match lookup(scopes, name) {
+                    Some(Value::List(items)) => items.clone(),
+                    _ => continue,
+                };
+                for (i, item) in items.into_iter().enumerate() {
+                    let mut scope = match item {
+                        Value::Map(map) => map,
+                        other => BTreeMap::from([("this".to_string(), other)]),
+                    };
+                    scope.entry("number".to_string()).or_insert((i + 1).into());
+                    scopes.push(scope);
+                    render_nodes(body, scopes, out);
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Runtime templates directory (`~/.config/skill-miner/templates`).
+pub fn templates_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("skill-miner").join("templates"))
+}
+
+/// Active body template, lazily loaded. Falls back to the embedded default
+/// if the runtime template is missing or invalid.
+static BODY: LazyLock<Template> = LazyLock::new(|| {
+    if let Some(path) = templates_dir().map(|d| d.join(BODY_TEMPLATE)) {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            match Template::parse(&content) {
+                Ok(t) => return t,
+                Err(e) => eprintln!("warn: invalid {}, using default template: {e}", path.display()),
+            }
+        }
+    }
+    Template::parse(DEFAULT_BODY).expect("embedded body template is valid")
+});
+
+/// Access the active body template.
+pub fn body_template() -> &'static Template {
+    &BODY
+}
+
+/// Parse every `*.md` template in `dir`. Returns (templates checked, errors).
+pub fn check_templates(dir: &Path) -> Result<(usize, Vec<String>), SkillMinerError> {
+    let mut checked = 0;
+    let mut errors = Vec::new();
+    if !dir.exists() {
+        return Ok((checked, errors));
+    }
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|e| e == "md").unwrap_or(false))
+        .collect();
+    paths.sort();
+    for path in paths {
+        checked += 1;
+        let content = std::fs::read_to_string(&path)?;
+        if let Err(e) = Template::parse(&content) {
+            errors.push(format!("{}: {}", path.display(), e));
+        }
+    }
+    Ok((checked, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(pairs: Vec<(&str, Value)>) -> BTreeMap<String, Value> {
+        pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+    }
+
+    #[test]
+    fn default_template_parses() {
+        assert!(Template::parse(DEFAULT_BODY).is_ok());
+    }
+
+    #[test]
+    fn renders_vars_if_and_each() {
+        let t = Template::parse(
+            "# {{domain}}\n{{#each patterns}}{{number}}. {{title}}{{#if score}} ({{score}}){{else}} -{{/if}}\n{{/each}}",
+        )
+        .unwrap();
+        let pattern = |title: &str, score: &str| {
+            Value::Map(ctx(vec![("title", title.into()), ("score", score.into())]))
+        };
+        let out = t.render(&ctx(vec![
+            ("domain", "Rust".into()),
+            ("patterns", Value::List(vec![pattern("A", "0.50"), pattern("B", "")])),
+        ]));
+        assert_eq!(out, "# Rust\n1. A (0.50)\n2. B -\n");
+    }
+
+    #[test]
+    fn nested_each_uses_inner_number() {
+        let t = Template::parse("{{#each patterns}}{{#each steps}}{{number}}:{{this}} {{/each}}{{/each}}").unwrap();
+        let p = Value::Map(ctx(vec![
+            ("number", 7.into()),
+            ("steps", Value::List(vec!["a".into(), "b".into()])),
+        ]));
+        assert_eq!(t.render(&ctx(vec![("patterns", Value::List(vec![p]))])), "1:a 2:b ");
+    }
+
+    #[test]
+    fn syntax_errors_are_reported() {
+        assert!(Template::parse("{{#if scored}}x").is_err());
+        assert!(Template::parse("{{/each}}").is_err());
+        assert!(Template::parse("{{#if scored}}{{/each}}").is_err());
+        assert!(Template::parse("{{domain").is_err());
+        assert!(Template::parse("{{#unless scored}}{{/unless}}").is_err());
+        assert!(Template::parse("{{else}}").is_err());
+        let err = Template::parse("line1\n{{#each patterns}}\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn unknown_variables_are_rejected() {
+        assert!(Template::parse("{{nope}}").is_err());
+        // Pattern variables are only valid inside {{#each patterns}}
+        assert!(Template::parse("{{title}}").is_err());
+        assert!(Template::parse("{{#each patterns}}{{title}}{{/each}}").is_ok());
+        assert!(Template::parse("{{#each domain}}{{/each}}").is_err());
+    }
+
+    #[test]
+    fn check_templates_reports_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("body.md"), "# {{domain}}\n").unwrap();
+        std::fs::write(dir.path().join("broken.md"), "{{#if scored}}\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "{{").unwrap();
+        let (checked, errors) = check_templates(dir.path()).unwrap();
+        assert_eq!(checked, 2);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("broken.md"));
+    }
+}
@@ -0,0 +1,430 @@
+// Transaction module: journaled deploys with snapshots, rollback on error and undo
+// Before a deploy touches a path, its previous content is copied into
+// `<drafts>/.deploys/<id>/files/` and recorded in `journal.json`. A failed deploy restores
+// every snapshot; `deploy --undo` restores the snapshots of the last committed deploy.
+// A transaction commits only once the manifest recording the deploy has been written.
+
+use crate::error::SkillMinerError;
+use crate::types::{DraftEntry, Manifest};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Directory (inside the drafts dir) holding one folder per deploy transaction.
+pub const DEPLOYS_DIR: &str = ".deploys";
+const JOURNAL_FILE: &str = "journal.json";
+/// Transactions kept for `deploy --undo`.
+pub const KEEP: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxState {
+    /// Being applied (a pending journal after a crash is rolled back by the next deploy)
+    Pending,
+    Committed,
+    RolledBack,
+    Undone,
+}
+
+/// A path touched by a transaction and where its previous content was saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Folder under `files/` with the previous content (None: the path did not exist)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub state: TxState,
+    pub backups: Vec<Backup>,
+    /// Manifest entries as they were before the deploy
+    pub entries: Vec<DraftEntry>,
+}
+
+/// An open deploy transaction.
+pub struct Transaction {
+    dir: PathBuf,
+    journal: Journal,
+}
+
+fn deploys_dir(draft_dir: &Path) -> PathBuf {
+    draft_dir.join(DEPLOYS_DIR)
+}
+
+fn write_journal(dir: &Path, journal: &Journal) -> Result<(), SkillMinerError> {
+    let content = serde_json::to_string_pretty(journal)
+        .map_err(|e| SkillMinerError::Parse(e.to_string()))?;
+    crate::util::write_atomic(&dir.join(JOURNAL_FILE), content.as_bytes())?;
+    Ok(())
+}
+
+fn read_journal(dir: &Path) -> Result<Journal, SkillMinerError> {
+    let path = dir.join(JOURNAL_FILE);
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| SkillMinerError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Transactions of a drafts dir, oldest first. Unreadable journals are skipped with a warning.
+pub fn list(draft_dir: &Path) -> Result<Vec<Journal>, SkillMinerError> {
+    let dir = deploys_dir(draft_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut journals = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.join(JOURNAL_FILE).exists() {
+            match read_journal(&path) {
+                Ok(journal) => journals.push(journal),
+                Err(e) => eprintln!("warn: skipping deploy journal: {}", e),
+            }
+        }
+    }
+    journals.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(journals)
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        std::fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+fn copy_path(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if src.is_dir() {
+        std::fs::create_dir_all(dst)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(src, dst).map(|_| ())
+    }
+}
+
+/// Put every backed-up path back as it was, newest first.
+fn restore(dir: &Path, journal: &Journal) -> Result<(), SkillMinerError> {
+    for backup in journal.backups.iter().rev() {
+        remove_path(&backup.path)?;
+        if let Some(ref saved) = backup.saved {
+            copy_path(&dir.join("files").join(saved), &backup.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Put the deploy state of the journaled entries back into the manifest.
+fn restore_entries(manifest: &mut Manifest, journal: &Journal) {
+    for old in &journal.entries {
+        if let Some(e) = manifest.entries.iter_mut().find(|e| e.slug == old.slug) {
+            e.status = old.status.clone();
+            e.deployed_at = old.deployed_at;
+            e.deployed_hash = old.deployed_hash.clone();
+            e.targets = old.targets.clone();
+            e.exports = old.exports.clone();
+        }
+    }
+}
+
+/// Roll back a transaction left pending by an interrupted deploy. Returns its id.
+pub fn recover(draft_dir: &Path) -> Result<Option<String>, SkillMinerError> {
+    let pending = list(draft_dir)?
+        .into_iter()
+        .rev()
+        .find(|j| j.state == TxState::Pending);
+    let Some(mut journal) = pending else {
+        return Ok(None);
+    };
+    let dir = deploys_dir(draft_dir).join(&journal.id);
+    restore(&dir, &journal)?;
+    journal.state = TxState::RolledBack;
+    write_journal(&dir, &journal)?;
+    Ok(Some(journal.id))
+}
+
+impl Transaction {
+    /// Start a transaction, first rolling back one left pending by a crash.
+    pub fn begin(draft_dir: &Path) -> Result<Self, SkillMinerError> {
+        if let Some(id) = recover(draft_dir)? {
+            eprintln!("warn: rolled back interrupted deploy {}", id);
+        }
+        let now = Utc::now();
+        let mut id = now.format("%Y%m%dT%H%M%S%3f").to_string();
+        let root = deploys_dir(draft_dir);
+        while root.join(&id).exists() {
+            id.push('x');
+        }
+        let dir = root.join(&id);
+        std::fs::create_dir_all(dir.join("files"))?;
+        let journal = Journal {
+            id,
+            started_at: now,
+            state: TxState::Pending,
+            backups: Vec::new(),
+            entries: Vec::new(),
+        };
+        write_journal(&dir, &journal)?;
+        Ok(Transaction { dir, journal })
+    }
+
+    /// Save the current content of `path` (once per transaction) before it is changed.
+    pub fn snapshot(&mut self, path: &Path) -> Result<(), SkillMinerError> {
+        if self.journal.backups.iter().any(|b| b.path == path) {
+            return Ok(());
+        }
+        let saved = if path.exists() {
+            let name = self.journal.backups.len().to_string();
+            copy_path(path, &self.dir.join("files").join(&name))?;
+            Some(name)
+        } else {
+            None
+        };
+        self.journal.backups.push(Backup {
+            path: path.to_path_buf(),
+            saved,
+        });
+        write_journal(&self.dir, &self.journal)
+    }
+
+    /// Snapshot a skill in both layouts (`<slug>.md` and `<slug>/`).
+    pub fn snapshot_skill(&mut self, dir: &Path, slug: &str) -> Result<(), SkillMinerError> {
+        self.snapshot(&dir.join(format!("{}.md", slug)))?;
+        self.snapshot(&dir.join(slug))
+    }
+
+    /// Remember a manifest entry's deploy state before it is changed.
+    pub fn remember(&mut self, entry: &DraftEntry) -> Result<(), SkillMinerError> {
+        if self.journal.entries.iter().any(|e| e.slug == entry.slug) {
+            return Ok(());
+        }
+        self.journal.entries.push(entry.clone());
+        write_journal(&self.dir, &self.journal)
+    }
+
+    /// Finish the transaction. Empty transactions leave nothing behind; only the last
+    /// `KEEP` transactions are kept.
+    pub fn commit(mut self) -> Result<String, SkillMinerError> {
+        let id = self.journal.id.clone();
+        if self.journal.backups.is_empty() {
+            std::fs::remove_dir_all(&self.dir)?;
+            return Ok(id);
+        }
+        self.journal.state = TxState::Committed;
+        write_journal(&self.dir, &self.journal)?;
+
+        let root = self.dir.parent().map(Path::to_path_buf).unwrap_or_default();
+        let journals = list(root.parent().unwrap_or(&root))?;
+        let old = journals.len().saturating_sub(KEEP);
+        for journal in journals.iter().take(old) {
+            std::fs::remove_dir_all(root.join(&journal.id))?;
+        }
+        Ok(id)
+    }
+
+    /// Undo everything applied so far: files on disk and the manifest entries.
+    pub fn rollback(mut self, manifest: &mut Manifest) -> Result<(), SkillMinerError> {
+        restore(&self.dir, &self.journal)?;
+        restore_entries(manifest, &self.journal);
+        self.journal.state = TxState::RolledBack;
+        write_journal(&self.dir, &self.journal)
+    }
+}
+
+/// Run `apply` as one transaction of the drafts dir `draft_dir` and write its manifest.
+/// The transaction commits only after the manifest is written; an error in either rolls
+/// back the files and the manifest entries. The audit log is appended after the commit, so
+/// a failure there leaves the deploy in place.
+pub fn run<T>(
+    draft_dir: &Path,
+    manifest: &mut Manifest,
+    apply: impl FnOnce(&mut Transaction, &mut Manifest) -> Result<T, SkillMinerError>,
+) -> Result<T, SkillMinerError> {
+    let mut tx = Transaction::begin(draft_dir)?;
+    let applied = apply(&mut tx, manifest).and_then(|value| {
+        crate::manifest::write_manifest_unaudited(draft_dir, manifest).map(|events| (value, events))
+    });
+    match applied {
+        Ok((value, events)) => {
+            // The notes went into the events of that write
+            manifest.audit_notes.clear();
+            tx.commit()?;
+            crate::audit::append(draft_dir, &events)?;
+            Ok(value)
+        }
+        Err(e) => {
+            tx.rollback(manifest)?;
+            Err(e)
+        }
+    }
+}
+
+/// Restore the skills and manifest deploy state from before the last committed deploy.
+/// Returns the undone journal, or None if there is nothing to undo.
+pub fn undo(draft_dir: &Path, manifest: &mut Manifest) -> Result<Option<Journal>, SkillMinerError> {
+    let last = list(draft_dir)?
+        .into_iter()
+        .rev()
+        .find(|j| j.state == TxState::Committed);
+    let Some(mut journal) = last else {
+        return Ok(None);
+    };
+    let dir = deploys_dir(draft_dir).join(&journal.id);
+    restore(&dir, &journal)?;
+    restore_entries(manifest, &journal);
+    journal.state = TxState::Undone;
+    write_journal(&dir, &journal)?;
+    Ok(Some(journal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_manifest() -> Manifest {
+        Manifest {
+            version: crate::manifest::current_version(),
+            generated_at: Utc::now(),
+            entries: Vec::new(),
+            mined_ids: Default::default(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn rollback_restores_files() {
+        let drafts = tempfile::tempdir().unwrap();
+        let skills = tempfile::tempdir().unwrap();
+        let existing = skills.path().join("old.md");
+        std::fs::write(&existing, "v1").unwrap();
+
+        let mut tx = Transaction::begin(drafts.path()).unwrap();
+        tx.snapshot_skill(skills.path(), "old").unwrap();
+        tx.snapshot_skill(skills.path(), "new").unwrap();
+        std::fs::write(&existing, "v2").unwrap();
+        std::fs::create_dir_all(skills.path().join("new")).unwrap();
+        std::fs::write(skills.path().join("new/SKILL.md"), "new").unwrap();
+        tx.rollback(&mut empty_manifest()).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "v1");
+        assert!(!skills.path().join("new").exists());
+        assert_eq!(list(drafts.path()).unwrap()[0].state, TxState::RolledBack);
+    }
+
+    #[test]
+    fn undo_last_commit_and_recover_pending() {
+        let drafts = tempfile::tempdir().unwrap();
+        let skills = tempfile::tempdir().unwrap();
+        let file = skills.path().join("s.md");
+        std::fs::write(&file, "v1").unwrap();
+
+        let mut tx = Transaction::begin(drafts.path()).unwrap();
+        tx.snapshot(&file).unwrap();
+        std::fs::write(&file, "v2").unwrap();
+        tx.commit().unwrap();
+
+        // An interrupted deploy: never committed nor rolled back
+        let mut tx = Transaction::begin(drafts.path()).unwrap();
+        tx.snapshot(&file).unwrap();
+        std::fs::write(&file, "half").unwrap();
+        drop(tx);
+        assert!(recover(drafts.path()).unwrap().is_some());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+
+        let undone = undo(drafts.path(), &mut empty_manifest()).unwrap().unwrap();
+        assert_eq!(undone.state, TxState::Undone);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+        assert!(undo(drafts.path(), &mut empty_manifest()).unwrap().is_none());
+    }
+
+    #[test]
+    fn failed_manifest_write_rolls_back() {
+        let drafts = tempfile::tempdir().unwrap();
+        let file = drafts.path().join("s.md");
+        std::fs::write(&file, "v1").unwrap();
+        // A directory where manifest.toml should go makes the write fail
+        std::fs::create_dir_all(drafts.path().join("manifest.toml")).unwrap();
+
+        let result = run(drafts.path(), &mut empty_manifest(), |tx, _| {
+            tx.snapshot(&file)?;
+            std::fs::write(&file, "v2")?;
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+        assert_eq!(list(drafts.path()).unwrap()[0].state, TxState::RolledBack);
+
+        std::fs::remove_dir(drafts.path().join("manifest.toml")).unwrap();
+        run(drafts.path(), &mut empty_manifest(), |tx, _| {
+            tx.snapshot(&file)?;
+            std::fs::write(&file, "v2")?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(drafts.path().join("manifest.toml").exists());
+        assert_eq!(list(drafts.path()).unwrap()[1].state, TxState::Committed);
+    }
+
+    #[test]
+    fn failed_audit_append_keeps_the_deploy() {
+        let drafts = tempfile::tempdir().unwrap();
+        let file = drafts.path().join("s.md");
+        std::fs::write(&file, "v1").unwrap();
+        // A directory where audit.jsonl should go makes the append fail
+        std::fs::create_dir_all(drafts.path().join(crate::audit::AUDIT_FILE)).unwrap();
+
+        let mut manifest = empty_manifest();
+        let result = run(drafts.path(), &mut manifest, |tx, manifest| {
+            tx.snapshot(&file)?;
+            std::fs::write(&file, "v2")?;
+            manifest.entries.push(DraftEntry {
+                slug: "s".to_string(),
+                ..Default::default()
+            });
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+        let written = crate::manifest::read_manifest(drafts.path()).unwrap();
+        assert_eq!(written.entries.len(), 1);
+        assert_eq!(list(drafts.path()).unwrap()[0].state, TxState::Committed);
+    }
+
+    #[test]
+    fn list_skips_corrupt_journals() {
+        let drafts = tempfile::tempdir().unwrap();
+        let file = drafts.path().join("s.md");
+        let mut tx = Transaction::begin(drafts.path()).unwrap();
+        tx.snapshot(&file).unwrap();
+        tx.commit().unwrap();
+        let broken = drafts.path().join(DEPLOYS_DIR).join("0-broken");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(broken.join(JOURNAL_FILE), "{ not json").unwrap();
+
+        let journals = list(drafts.path()).unwrap();
+        assert_eq!(journals.len(), 1);
+        assert_eq!(journals[0].state, TxState::Committed);
+        assert!(undo(drafts.path(), &mut empty_manifest()).unwrap().is_some());
+    }
+
+    #[test]
+    fn empty_commit_leaves_nothing() {
+        let drafts = tempfile::tempdir().unwrap();
+        Transaction::begin(drafts.path()).unwrap().commit().unwrap();
+        assert!(list(drafts.path()).unwrap().is_empty());
+    }
+}
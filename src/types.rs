@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
 
@@ -60,6 +60,9 @@ pub struct Message {
     pub timestamp: Option<DateTime<Utc>>,
     /// Tool uses within this message (tool name + input summary)
     pub tool_uses: Vec<ToolUse>,
+    /// Message from a subagent run (Task tool sidechain)
+    #[serde(default)]
+    pub sidechain: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -80,6 +83,21 @@ pub struct ToolUse {
     /// Command string for Bash tool (first ~100 chars)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub command: Option<String>,
+    /// Delegation details for the Task tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskCall>,
+    /// The tool result came back as an error
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub failed: bool,
+}
+
+/// A subagent delegation made through the Task tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskCall {
+    pub subagent_type: String,
+    pub description: String,
+    /// First ~500 chars of the delegated prompt
+    pub prompt: String,
 }
 
 /// A parsed conversation (one session)
@@ -171,6 +189,28 @@ pub struct SkillInvocation {
     /// User message just before invocation (first 200 chars)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub trigger_context: Option<String>,
+    /// What happened after the invocation
+    #[serde(default)]
+    pub outcome: InvocationOutcome,
+    /// Tool calls until the next user message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub followed: Vec<ToolUse>,
+}
+
+/// Outcome signals following a skill invocation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InvocationOutcome {
+    /// Tool calls until the next user message
+    pub tool_calls: usize,
+    /// Of those, calls whose result was an error
+    pub failed_tool_calls: usize,
+    /// The next user message corrected the assistant ("no, ...", "revert that")
+    pub corrected: bool,
+    /// A `git commit` succeeded later in the session
+    pub committed: bool,
+    /// Result of the last test command later in the session (None: no tests run)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests_passed: Option<bool>,
 }
 
 /// A reusable knowledge pattern extracted from conversations
@@ -192,6 +232,31 @@ pub struct KnowledgePattern {
     /// Topic-level slug for grouping patterns into separate skills
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub skill_slug: Option<String>,
+    /// Project root (cwd) when the pattern only applies to one repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+/// A pattern kept in the pattern store, with the domain it was extracted under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPattern {
+    pub domain: String,
+    #[serde(flatten)]
+    pub pattern: KnowledgePattern,
+    /// First extraction of this pattern
+    pub first_seen: DateTime<Utc>,
+    /// Latest extraction (content, sources and frequency merged in)
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Every extracted pattern and the conversations it came from (`<drafts>/patterns.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatternStore {
+    #[serde(default)]
+    pub patterns: Vec<StoredPattern>,
+    /// Classified conversations referenced by `source_ids` (provenance, allowed-tools, projects)
+    #[serde(default)]
+    pub conversations: Vec<ClassifiedConversation>,
 }
 
 /// Generated skill definition
@@ -209,15 +274,36 @@ pub struct SkillDraft {
     pub existing_skill: Option<PathBuf>,
     /// Diff against existing skill if applicable
     pub diff: Option<String>,
+    /// Supporting files written next to SKILL.md (e.g. long code examples)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<SkillFile>,
+    /// Least-privilege `allowed-tools` rules (e.g. "Read", "Bash(cargo test:*)")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+}
+
+/// A supporting file inside a folder-based skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillFile {
+    /// Path relative to the skill folder (e.g. "examples/1-1.rs")
+    pub path: String,
+    pub content: String,
 }
 
 impl SkillDraft {
     /// Format as a complete .md file with YAML frontmatter.
     pub fn format_md(&self) -> String {
         let escaped_desc = escape_yaml_double_quoted(&self.description);
+        let mut allowed = String::new();
+        if !self.allowed_tools.is_empty() {
+            allowed.push_str("allowed-tools:\n");
+            for rule in &self.allowed_tools {
+                allowed.push_str(&format!("  - \"{}\"\n", escape_yaml_double_quoted(rule)));
+            }
+        }
         format!(
-            "---\nname: {}\ndescription: \"{}\"\n---\n\n{}\n",
-            self.name, escaped_desc, self.body
+            "---\nname: {}\ndescription: \"{}\"\n{}---\n\n{}\n",
+            self.name, escaped_desc, allowed, self.body
         )
     }
 }
@@ -226,7 +312,7 @@ impl SkillDraft {
 ///
 /// Handles all YAML 1.1 special escape sequences so the value is safe
 /// when placed between double quotes in YAML frontmatter.
-fn escape_yaml_double_quoted(s: &str) -> String {
+pub(crate) fn escape_yaml_double_quoted(s: &str) -> String {
     let mut out = String::with_capacity(s.len() + 16);
     for ch in s.chars() {
         match ch {
@@ -254,9 +340,10 @@ fn escape_yaml_double_quoted(s: &str) -> String {
 // ── State management types ──
 
 /// Status of a skill draft in the review pipeline
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DraftStatus {
+    #[default]
     Draft,
     Approved,
     Deployed,
@@ -275,7 +362,7 @@ impl fmt::Display for DraftStatus {
 }
 
 /// A single entry in the drafts manifest
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DraftEntry {
     pub slug: String,
     pub domain: String,
@@ -292,6 +379,183 @@ pub struct DraftEntry {
     /// Number of times this skill was invoked
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fire_count: Option<usize>,
+    /// Project root for project-scoped drafts (None = global skill)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Hash of the skill folder (`layout::hash_skill`: SKILL.md and supporting files) at last
+    /// deploy (detects hand edits to the deployed copy)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_hash: Option<String>,
+    /// Content-addressed history of the skill folder, oldest first (v1 = first)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<DraftVersion>,
+    /// Review metadata (tags, owner, notes, comments); kept across regeneration
+    #[serde(default, skip_serializing_if = "DraftMeta::is_empty")]
+    pub meta: DraftMeta,
+    /// Deploy targets (empty = the global skills dir only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<DeployTarget>,
+    /// Targets were set by hand; regeneration no longer infers them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub targets_pinned: bool,
+    /// Exports to other agents' rule formats, with their state in each repository
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<RuleExport>,
+}
+
+/// Where a draft is deployed, with its deployment state there.
+/// The state of the global target stays in `DraftEntry::deployed_at`/`deployed_hash`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeployTarget {
+    /// Project root (deploys to `<path>/.claude/skills`), or "global"
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_at: Option<DateTime<Utc>>,
+    /// Hash of the skill folder (`layout::hash_skill`) at last deploy to this target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_hash: Option<String>,
+}
+
+/// Rule format of another coding agent that skills can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentFormat {
+    /// Cursor rule: `.cursor/rules/<slug>.mdc`
+    Cursor,
+    /// Section of `.github/copilot-instructions.md`
+    Copilot,
+    /// Section of `AGENTS.md` (Codex and other agents)
+    AgentsMd,
+}
+
+impl fmt::Display for AgentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AgentFormat::Cursor => "cursor",
+            AgentFormat::Copilot => "copilot",
+            AgentFormat::AgentsMd => "agents-md",
+        };
+        f.pad(s)
+    }
+}
+
+/// A draft exported in another agent's rule format to a repository, with its state there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleExport {
+    pub format: AgentFormat,
+    /// Repository root the rule was written to
+    pub repo: String,
+    pub deployed_at: DateTime<Utc>,
+    /// Hash of the rendered rule (detects hand edits to the exported file or section)
+    pub deployed_hash: String,
+}
+
+/// Human review metadata of a draft
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DraftMeta {
+    /// Free-form tags (e.g. "windows", "needs-example")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Reviewer responsible for the draft
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Review comments, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<ReviewComment>,
+}
+
+/// A review comment; replies point at the comment they answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewComment {
+    /// 1-based number within the draft
+    pub id: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub at: DateTime<Utc>,
+    pub text: String,
+}
+
+impl DraftMeta {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.owner.is_none() && self.notes.is_none() && self.comments.is_empty()
+    }
+
+    /// Add tags that are not present yet.
+    pub fn add_tags(&mut self, tags: &[String]) {
+        for tag in tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+    }
+
+    /// Append a comment and return its id. Replies must point at an existing comment.
+    pub fn add_comment(
+        &mut self,
+        text: &str,
+        author: Option<String>,
+        reply_to: Option<usize>,
+    ) -> Result<usize, String> {
+        if let Some(parent) = reply_to {
+            if !self.comments.iter().any(|c| c.id == parent) {
+                return Err(format!("no comment #{}", parent));
+            }
+        }
+        let id = self.comments.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        self.comments.push(ReviewComment {
+            id,
+            reply_to,
+            author,
+            at: Utc::now(),
+            text: text.to_string(),
+        });
+        Ok(id)
+    }
+
+    /// Merge metadata from another copy of the same draft (e.g. an imported bundle).
+    /// Tags are unioned, owner and notes are only filled in when missing, and comments
+    /// not present yet are appended with new ids (replies follow their parents).
+    pub fn merge(&mut self, other: &DraftMeta) {
+        self.add_tags(&other.tags);
+        if self.owner.is_none() {
+            self.owner = other.owner.clone();
+        }
+        if self.notes.is_none() {
+            self.notes = other.notes.clone();
+        }
+        let mut ids: HashMap<usize, usize> = HashMap::new();
+        for comment in &other.comments {
+            let existing = self
+                .comments
+                .iter()
+                .find(|c| c.at == comment.at && c.author == comment.author && c.text == comment.text);
+            let id = match existing {
+                Some(c) => c.id,
+                None => {
+                    let id = self.comments.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+                    self.comments.push(ReviewComment {
+                        id,
+                        reply_to: comment.reply_to.and_then(|p| ids.get(&p).copied()),
+                        ..comment.clone()
+                    });
+                    id
+                }
+            };
+            ids.insert(comment.id, id);
+        }
+    }
+}
+
+/// One stored version of a draft, keyed by its folder hash (`layout::hash_skill`). The
+/// folder lives in `<drafts>/.versions/<hash>/` (`<hash>.md` for single-file drafts).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftVersion {
+    pub hash: String,
+    pub at: DateTime<Utc>,
 }
 
 /// Manifest tracking all skill drafts and their states
@@ -306,6 +570,113 @@ pub struct Manifest {
     /// Classified but not yet extracted (domains that failed due to timeout, etc.)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub pending_extracts: Vec<ClassifiedConversation>,
+    /// Conversations and projects removed by `forget`; they are never mined again
+    #[serde(default, skip_serializing_if = "ForgetList::is_empty")]
+    pub forgotten: ForgetList,
+    /// Context for the audit events of the next write, keyed by slug (not persisted)
+    #[serde(skip)]
+    pub audit_notes: HashMap<String, AuditNote>,
+}
+
+/// Deny-list of conversations excluded from mining
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ForgetList {
+    /// Conversation IDs
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub ids: BTreeSet<String>,
+    /// Normalized project paths; every conversation run in one (or below it) is excluded
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub projects: BTreeSet<String>,
+}
+
+impl ForgetList {
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.projects.is_empty()
+    }
+
+    /// Whether a conversation with this ID and working directory is forgotten.
+    pub fn contains(&self, id: &str, cwd: Option<&str>) -> bool {
+        if self.ids.contains(id) {
+            return true;
+        }
+        let Some(cwd) = cwd.map(crate::project::normalize_project) else {
+            return false;
+        };
+        self.projects.iter().any(|p| {
+            cwd == *p || cwd.strip_prefix(p.as_str()).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Generated,
+    Regenerated,
+    Approved,
+    Rejected,
+    Deployed,
+    /// Moved back to draft (un-approve, reconsider, regenerate)
+    Reopened,
+    Consolidated,
+    Imported,
+    Removed,
+    RolledBack,
+    /// Deployed copies removed (the entry goes back to approved or rejected)
+    Undeployed,
+    /// Hand-edited or unknown deployed skill copied back into the drafts
+    Adopted,
+    /// Written in another agent's rule format (Cursor, Copilot, AGENTS.md)
+    Exported,
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AuditAction::Generated => "generated",
+            AuditAction::Regenerated => "regenerated",
+            AuditAction::Approved => "approved",
+            AuditAction::Rejected => "rejected",
+            AuditAction::Deployed => "deployed",
+            AuditAction::Reopened => "reopened",
+            AuditAction::Consolidated => "consolidated",
+            AuditAction::Imported => "imported",
+            AuditAction::Removed => "removed",
+            AuditAction::RolledBack => "rolled-back",
+            AuditAction::Undeployed => "undeployed",
+            AuditAction::Adopted => "adopted",
+            AuditAction::Exported => "exported",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// One line of the append-only audit log next to a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub at: DateTime<Utc>,
+    pub slug: String,
+    pub action: AuditAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<DraftStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<DraftStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// User who ran the command ($USER)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+}
+
+/// Why a draft is about to change, attached to its next audit event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditNote {
+    /// Overrides the action inferred from the manifest diff (e.g. Imported instead of Generated)
+    pub action: Option<AuditAction>,
+    pub reason: Option<String>,
 }
 
 
@@ -351,6 +722,12 @@ pub struct BundleSkill {
     /// Referenced memory/context file paths (relative)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<String>,
+    /// Supporting files of a folder skill (relative to `skills/<slug>/`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Review metadata (left out of public bundles)
+    #[serde(default, skip_serializing_if = "DraftMeta::is_empty")]
+    pub meta: DraftMeta,
 }
 
 /// Result of deploying a single skill
@@ -359,6 +736,157 @@ pub struct DeployResult {
     pub slug: String,
     pub target_path: PathBuf,
     pub was_update: bool,
+    /// Hand edits in the deployed file were three-way merged
+    pub merged: bool,
+    /// Sections left with conflict markers for review
+    pub conflicts: Vec<String>,
+}
+
+/// Result of undeploying a single skill
+#[derive(Debug, Clone)]
+pub struct UndeployResult {
+    pub slug: String,
+    /// Deployed skill paths that were removed
+    pub removed: Vec<PathBuf>,
+    /// Where the removed copies were archived (with `archive`)
+    pub archived: Vec<PathBuf>,
+    /// Status of the entry afterwards
+    pub status: DraftStatus,
+}
+
+/// Result of exporting a single skill in another agent's rule format
+#[derive(Debug, Clone)]
+pub struct RuleExportResult {
+    pub slug: String,
+    pub format: AgentFormat,
+    /// File written (the rule file, or the document holding the section)
+    pub path: PathBuf,
+    pub was_update: bool,
+    /// The previous export was edited by hand and has been overwritten
+    pub overwrote_edits: bool,
+}
+
+/// Options for undeploying skills
+#[derive(Debug, Clone, Default)]
+pub struct UndeployOptions {
+    /// Only undeploy from this target (None = every target)
+    pub target: Option<String>,
+    /// Mark the entry rejected instead of approved
+    pub reject: bool,
+    /// Move the deployed copies to `<drafts>/.archive/` instead of deleting them
+    pub archive: bool,
+}
+
+/// How a deployed skill differs from what the manifest recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftKind {
+    /// Edited since it was deployed
+    Modified,
+    /// Recorded as deployed, but no longer on disk
+    Missing,
+    /// In a skills dir, but not in the manifest
+    Unknown,
+}
+
+impl fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            DriftKind::Modified => "modified",
+            DriftKind::Missing => "missing",
+            DriftKind::Unknown => "unknown",
+        };
+        f.pad(s)
+    }
+}
+
+/// A deployed skill out of sync with the manifest
+#[derive(Debug, Clone)]
+pub struct SkillDrift {
+    pub slug: String,
+    /// Deploy target ("global" or a project path)
+    pub target: String,
+    pub kind: DriftKind,
+    /// Skill markdown file in the target skills dir
+    pub path: PathBuf,
+    /// A deployed slash command (`commands/` manifest) rather than a skill
+    pub command: bool,
+}
+
+/// Suggested fix for two skills that collide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapSuggestion {
+    /// Same ground in description and keywords: keep one skill
+    Merge,
+    /// Related but distinct: make the descriptions say when each applies
+    Reword,
+}
+
+impl fmt::Display for OverlapSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            OverlapSuggestion::Merge => "merge",
+            OverlapSuggestion::Reword => "reword",
+        };
+        f.pad(s)
+    }
+}
+
+/// Two skills likely to be picked for the same prompts
+#[derive(Debug, Clone)]
+pub struct OverlapPair {
+    pub a: String,
+    pub b: String,
+    /// Cosine similarity of the descriptions (TF-IDF, 0..1)
+    pub description_similarity: f64,
+    /// Jaccard similarity of the keywords (slug words, pattern titles)
+    pub keyword_similarity: f64,
+    /// Real trigger prompts that matched both skills about equally well
+    pub collisions: usize,
+    /// Some of those prompts
+    pub examples: Vec<String>,
+    /// Most distinctive terms the two descriptions share
+    pub shared_terms: Vec<String>,
+    pub suggestion: OverlapSuggestion,
+}
+
+/// Every factor of a skill's consolidation score (see `scorer`)
+#[derive(Debug, Clone)]
+pub struct ScoreBreakdown {
+    pub slug: String,
+    pub fire_count: usize,
+    pub max_fire_count: usize,
+    /// fire_count / max_fire_count
+    pub fire_score: f64,
+    /// Sum of the frequencies of the domain's patterns
+    pub pattern_freq_sum: usize,
+    pub max_pattern_freq_sum: usize,
+    /// pattern_freq_sum / max_pattern_freq_sum
+    pub pattern_score: f64,
+    pub productive_count: usize,
+    /// productive_count / fire_count (1.0 if no fires)
+    pub productive_rate: f64,
+    /// Mean outcome of the invocations (1.0 if none, see `scorer::outcome_score`)
+    pub outcome_rate: f64,
+    pub last_invoked: Option<DateTime<Utc>>,
+    /// Days since the last invocation or deploy, whichever is later (None: neither)
+    pub days_inactive: Option<f64>,
+    /// 0.5 ^ (days_inactive / half_life_days)
+    pub decay: f64,
+    /// Weighted fire and pattern scores
+    pub base_score: f64,
+    pub productive_multiplier: f64,
+    pub outcome_multiplier: f64,
+    pub score: f64,
+}
+
+/// How often one section (pattern) of a skill was followed after the skill was invoked
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionUsage {
+    pub title: String,
+    /// Invocations followed by a tool call matching the section's commands or files
+    pub used: usize,
+    /// Invocations of the skill
+    pub invocations: usize,
 }
 
 /// Options for pruning drafts
@@ -401,6 +929,12 @@ pub struct MineConfig {
     pub projects_dir: PathBuf,
     /// Path to existing skills directory
     pub skills_dir: PathBuf,
+    /// Path to slash commands directory
+    pub commands_dir: PathBuf,
+    /// Path to subagents directory
+    pub agents_dir: PathBuf,
+    /// Path to user settings.json (hooks)
+    pub settings_path: PathBuf,
     /// Path to history.jsonl
     pub history_path: PathBuf,
     /// How many days back to look
@@ -421,6 +955,9 @@ impl Default for MineConfig {
         Self {
             projects_dir: home.join(".claude/projects"),
             skills_dir: home.join(".claude/skills"),
+            commands_dir: home.join(".claude/commands"),
+            agents_dir: home.join(".claude/agents"),
+            settings_path: home.join(".claude/settings.json"),
             history_path: home.join(".claude/history.jsonl"),
             days_back: 30,
             min_messages: 4,
@@ -52,6 +52,24 @@ pub fn normalize_path(p: &Path) -> String {
     p.to_string_lossy().replace('\\', "/")
 }
 
+/// Write a file atomically: write a temp file next to it, then rename it into place.
+/// Readers see either the old or the new content, never a partial write.
+pub fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
 /// Get the user's home directory (cross-platform).
 pub fn home_dir() -> PathBuf {
     std::env::var("USERPROFILE")
@@ -121,6 +139,17 @@ mod tests {
         value: i32,
     }
 
+    #[test]
+    fn test_write_atomic_replaces_without_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.toml");
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        let names: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(names, vec!["manifest.toml"]);
+    }
+
     #[test]
     fn test_truncate_short_string() {
         assert_eq!(truncate("hello", 10), "hello");
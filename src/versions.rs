@@ -0,0 +1,287 @@
+// Versions module: content-addressed history of drafts
+// Every content a draft had when its manifest was written (SKILL.md and supporting files)
+// is stored once under `<drafts>/.versions/<hash>/` (`<hash>.md` for single-file drafts);
+// each DraftEntry keeps the ordered hash chain.
+
+use crate::error::SkillMinerError;
+use crate::layout;
+use crate::manifest;
+use crate::types::{DraftEntry, DraftVersion, Manifest};
+use std::path::{Path, PathBuf};
+
+/// Object store directory inside a drafts directory.
+pub const VERSIONS_DIR: &str = ".versions";
+
+fn objects_dir(dir: &Path) -> PathBuf {
+    dir.join(VERSIONS_DIR)
+}
+
+/// Store a draft's current content in the object store (once per distinct content).
+/// Returns its hash (`layout::hash_skill`).
+pub fn store(dir: &Path, slug: &str) -> Result<String, SkillMinerError> {
+    let hash = layout::hash_skill(dir, slug)?;
+    let objects = objects_dir(dir);
+    if !layout::exists(&objects, &hash) {
+        // Copied under a temp name first so a crash never leaves a partial object
+        let tmp = format!(".{}.tmp", hash);
+        layout::remove_skill(&objects, &tmp)?;
+        let written = layout::copy_skill(dir, slug, &objects, &tmp)?;
+        if layout::is_folder(&objects, &tmp) {
+            std::fs::rename(objects.join(&tmp), objects.join(&hash))?;
+        } else {
+            std::fs::rename(written, objects.join(format!("{}.md", hash)))?;
+        }
+    }
+    Ok(hash)
+}
+
+fn check_stored(dir: &Path, hash: &str) -> Result<(), SkillMinerError> {
+    if layout::exists(&objects_dir(dir), hash) {
+        Ok(())
+    } else {
+        Err(SkillMinerError::Config(format!(
+            "version {} is missing from {}",
+            short(hash),
+            objects_dir(dir).display()
+        )))
+    }
+}
+
+/// Load a stored version's SKILL.md content.
+pub fn load(dir: &Path, hash: &str) -> Result<String, SkillMinerError> {
+    check_stored(dir, hash)?;
+    layout::read_skill_md(&objects_dir(dir), hash)
+}
+
+/// Abbreviated hash for display.
+pub fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
+/// Extend each entry's chain with the draft's current content if it changed.
+/// Chains are append-only, so the longer of the in-memory and on-disk chain wins
+/// (a manifest loaded before another write in the same run keeps its history).
+pub fn record(
+    dir: &Path,
+    manifest: &mut Manifest,
+    previous: Option<&Manifest>,
+) -> Result<(), SkillMinerError> {
+    for entry in &mut manifest.entries {
+        if let Some(old) = previous.and_then(|p| manifest::find_entry(p, &entry.slug)) {
+            if old.versions.len() > entry.versions.len() {
+                entry.versions = old.versions.clone();
+            }
+        }
+        if !layout::exists(dir, &entry.slug) {
+            continue;
+        }
+        let hash = store(dir, &entry.slug)?;
+        if entry.versions.last().map(|v| v.hash.as_str()) != Some(hash.as_str()) {
+            entry.versions.push(DraftVersion {
+                hash,
+                at: chrono::Utc::now(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a version reference: `v3` / `3` (1-based) or a hash prefix.
+/// Returns the 1-based number and the version.
+pub fn resolve<'a>(
+    entry: &'a DraftEntry,
+    reference: &str,
+) -> Result<(usize, &'a DraftVersion), SkillMinerError> {
+    let number = reference.strip_prefix('v').unwrap_or(reference);
+    if let Ok(n) = number.parse::<usize>() {
+        return match entry.versions.get(n.wrapping_sub(1)) {
+            Some(version) => Ok((n, version)),
+            None => Err(SkillMinerError::Config(format!(
+                "{} has versions v1..v{}, not {}",
+                entry.slug,
+                entry.versions.len(),
+                reference
+            ))),
+        };
+    }
+    let matches: Vec<(usize, &DraftVersion)> = entry
+        .versions
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.hash.starts_with(reference))
+        .map(|(i, v)| (i + 1, v))
+        .collect();
+    match matches.as_slice() {
+        [found] => Ok(*found),
+        [] => Err(SkillMinerError::Config(format!(
+            "no version {} of {}",
+            reference, entry.slug
+        ))),
+        // The same content can recur (e.g. after a rollback); the latest occurrence wins
+        many if many.iter().all(|(_, v)| v.hash == many[0].1.hash) => Ok(*many.last().unwrap()),
+        _ => Err(SkillMinerError::Config(format!(
+            "version {} of {} is ambiguous",
+            reference, entry.slug
+        ))),
+    }
+}
+
+/// Unified diff between two versions of a draft.
+pub fn diff(
+    dir: &Path,
+    entry: &DraftEntry,
+    from: &str,
+    to: &str,
+) -> Result<String, SkillMinerError> {
+    let (from_n, from_v) = resolve(entry, from)?;
+    let (to_n, to_v) = resolve(entry, to)?;
+    let result = if from_v.hash == to_v.hash {
+        Default::default()
+    } else {
+        crate::diff::compute(&load(dir, &from_v.hash)?, &load(dir, &to_v.hash)?)
+    };
+    Ok(result.unified(
+        &format!("{} v{}", entry.slug, from_n),
+        &format!("{} v{}", entry.slug, to_n),
+    ))
+}
+
+/// Restore a stored version as the draft (SKILL.md and supporting files) and return its
+/// 1-based number.
+/// The caller writes the manifest, which records the restored content as a new version.
+pub fn rollback(
+    dir: &Path,
+    manifest: &mut Manifest,
+    slug: &str,
+    reference: &str,
+) -> Result<usize, SkillMinerError> {
+    let entry = manifest::find_entry(manifest, slug)
+        .ok_or_else(|| SkillMinerError::Config(format!("draft not found: {}", slug)))?;
+    let (number, version) = resolve(entry, reference)?;
+    let hash = version.hash.clone();
+    check_stored(dir, &hash)?;
+    layout::copy_skill(&objects_dir(dir), &hash, dir, slug)?;
+
+    if let Some(entry) = manifest::find_entry_mut(manifest, slug) {
+        entry.content_hash = hash;
+    }
+    manifest::annotate(
+        manifest,
+        slug,
+        Some(crate::types::AuditAction::RolledBack),
+        &format!("rollback to v{}", number),
+    );
+    Ok(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DraftStatus;
+    use std::collections::HashSet;
+
+    fn setup(dir: &Path, content: &str) -> Manifest {
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::write(dir.join("a").join("SKILL.md"), content).unwrap();
+        Manifest {
+            version: manifest::current_version(),
+            generated_at: chrono::Utc::now(),
+            entries: vec![DraftEntry {
+                slug: "a".to_string(),
+                domain: "Test".to_string(),
+                status: DraftStatus::Draft,
+                pattern_count: 1,
+                conversation_count: 1,
+                generated_at: chrono::Utc::now(),
+                content_hash: manifest::compute_hash(content),
+                ..Default::default()
+            }],
+            mined_ids: HashSet::new(),
+            pending_extracts: Vec::new(),
+            forgotten: Default::default(),
+            audit_notes: Default::default(),
+        }
+    }
+
+    fn write_draft(dir: &Path, content: &str) {
+        std::fs::write(dir.join("a").join("SKILL.md"), content).unwrap();
+    }
+
+    #[test]
+    fn write_manifest_records_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let mf = setup(dir.path(), "one\n");
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        // Unchanged content adds nothing, even from a stale in-memory manifest
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        write_draft(dir.path(), "two\n");
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+
+        let loaded = manifest::read_manifest(dir.path()).unwrap();
+        let chain = &loaded.entries[0].versions;
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].hash, manifest::compute_hash("one\n"));
+        assert_eq!(load(dir.path(), &chain[1].hash).unwrap(), "two\n");
+    }
+
+    #[test]
+    fn resolve_numbers_and_prefixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mf = setup(dir.path(), "one\n");
+        record(dir.path(), &mut mf, None).unwrap();
+        write_draft(dir.path(), "two\n");
+        record(dir.path(), &mut mf, None).unwrap();
+        let entry = &mf.entries[0];
+
+        assert_eq!(resolve(entry, "v2").unwrap().0, 2);
+        assert_eq!(resolve(entry, "1").unwrap().0, 1);
+        let prefix = &entry.versions[1].hash[..10];
+        assert_eq!(resolve(entry, prefix).unwrap().0, 2);
+        assert!(resolve(entry, "v3").is_err());
+        assert!(resolve(entry, "v0").is_err());
+        assert!(diff(dir.path(), entry, "v1", "v2").unwrap().contains("+two"));
+    }
+
+    #[test]
+    fn rollback_restores_and_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let mf = setup(dir.path(), "one\n");
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        write_draft(dir.path(), "two\n");
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+
+        let mut mf = manifest::read_manifest(dir.path()).unwrap();
+        assert_eq!(rollback(dir.path(), &mut mf, "a", "v1").unwrap(), 1);
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+
+        assert_eq!(layout::read_skill_md(dir.path(), "a").unwrap(), "one\n");
+        let loaded = manifest::read_manifest(dir.path()).unwrap();
+        let chain = &loaded.entries[0].versions;
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[2].hash, chain[0].hash);
+        let events = crate::audit::history(dir.path(), "a").unwrap();
+        assert_eq!(events.last().unwrap().action, crate::types::AuditAction::RolledBack);
+    }
+
+    #[test]
+    fn versions_cover_supporting_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let example = dir.path().join("a").join("examples").join("1-1.rs");
+        let mf = setup(dir.path(), "one\n");
+        std::fs::create_dir_all(example.parent().unwrap()).unwrap();
+        std::fs::write(&example, "fn one() {}\n").unwrap();
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+
+        // Only a supporting file changes, and a new one is added
+        std::fs::write(&example, "fn two() {}\n").unwrap();
+        std::fs::write(dir.path().join("a").join("notes.md"), "new\n").unwrap();
+        manifest::write_manifest(dir.path(), &mf).unwrap();
+        let mut mf = manifest::read_manifest(dir.path()).unwrap();
+        assert_eq!(mf.entries[0].versions.len(), 2);
+
+        rollback(dir.path(), &mut mf, "a", "v1").unwrap();
+        assert_eq!(std::fs::read_to_string(&example).unwrap(), "fn one() {}\n");
+        assert!(!dir.path().join("a").join("notes.md").exists());
+        assert_eq!(mf.entries[0].content_hash, layout::hash_skill(dir.path(), "a").unwrap());
+    }
+}
@@ -147,6 +147,7 @@ fn e2e_fixture_classify_to_generate() {
                 source_ids: vec!["conv001".to_string()],
                 frequency: p.frequency,
                 skill_slug: None,
+                project: None,
             })
             .collect(),
     };
@@ -217,6 +218,7 @@ fn e2e_multiple_clusters_generate_multiple_drafts() {
             source_ids: vec!["id1".to_string()],
             frequency: 2,
             skill_slug: None,
+            project: None,
         }],
     };
     let cluster2 = DomainCluster {
@@ -230,6 +232,7 @@ fn e2e_multiple_clusters_generate_multiple_drafts() {
             source_ids: vec!["id2".to_string()],
             frequency: 1,
             skill_slug: None,
+            project: None,
         }],
     };
 
@@ -258,6 +261,7 @@ fn e2e_skill_draft_format_contains_steps() {
             source_ids: vec!["s1".to_string()],
             frequency: 5,
             skill_slug: None,
+            project: None,
         }],
     };
 
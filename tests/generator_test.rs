@@ -19,6 +19,7 @@ fn make_test_cluster() -> DomainCluster {
                 frequency: 5,
                 code_examples: vec![],
                 skill_slug: None,
+                project: None,
             },
             KnowledgePattern {
                 title: "Test-driven development".to_string(),
@@ -28,6 +29,7 @@ fn make_test_cluster() -> DomainCluster {
                 frequency: 3,
                 code_examples: vec![],
                 skill_slug: None,
+                project: None,
             },
         ],
     }
@@ -41,6 +43,8 @@ fn make_test_draft() -> SkillDraft {
         sources: vec!["abc12345".to_string()],
         existing_skill: None,
         diff: None,
+        files: Vec::new(),
+        allowed_tools: Vec::new(),
     }
 }
 
@@ -57,6 +61,20 @@ fn format_skill_md_has_yaml_frontmatter() {
     assert!(parts.len() >= 3, "Should have opening and closing --- delimiters");
 }
 
+#[test]
+fn format_skill_md_emits_allowed_tools_list() {
+    let mut draft = make_test_draft();
+    assert!(!generator::format_skill_md(&draft).contains("allowed-tools"));
+
+    draft.allowed_tools = vec!["Read".to_string(), "Bash(git status:*)".to_string()];
+    let md = generator::format_skill_md(&draft);
+    assert!(
+        md.contains("\nallowed-tools:\n  - \"Read\"\n  - \"Bash(git status:*)\"\n---\n"),
+        "allowed-tools should be a quoted YAML block list, got: {}",
+        md
+    );
+}
+
 #[test]
 fn format_skill_md_escapes_quotes_in_description() {
     let mut draft = make_test_draft();